@@ -0,0 +1,378 @@
+use std::io::BufRead;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
+use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::NETWORK_DEFAULT_PORT;
+use crate::game_module::level_datas::spawn_point::SpawnPointType;
+use crate::resource::project_resource::ProjectResources;
+
+pub const DEDICATED_SERVER_DEFAULT_SCENE: &str = "default";
+pub const DEDICATED_SERVER_DEFAULT_TICK_RATE: u32 = 60;
+pub const DEDICATED_SERVER_MAX_TICK_RATE: u32 = 240;
+// the slow frames catch up with this many ticks at most, the rest of the time is dropped
+pub const DEDICATED_SERVER_MAX_TICKS_PER_FRAME: u32 = 8;
+
+pub struct DedicatedServerOptions {
+    pub _port: u16,
+    pub _scene_name: String,
+    pub _tick_rate: u32,
+    // empty is the game mode of the scene
+    pub _game_mode_data_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdminCommand {
+    Status,
+    Kick(u32),
+    ChangeMap(String),
+    ChangeGameMode(String),
+    Restart,
+    Quit,
+    Help,
+}
+
+// the game client of the server is updated with the fixed tick, the clients are accepted by the network manager.
+pub struct DedicatedServer {
+    pub _game_client: *const GameClient,
+    pub _options: DedicatedServerOptions,
+    pub _admin_commands: Receiver<AdminCommand>,
+    pub _scene_name: String,
+    pub _accumulated_time: f32,
+    pub _tick: u64,
+    pub _match_time: f32,
+}
+
+// Implementation
+impl Default for DedicatedServerOptions {
+    fn default() -> DedicatedServerOptions {
+        DedicatedServerOptions {
+            _port: NETWORK_DEFAULT_PORT,
+            _scene_name: DEDICATED_SERVER_DEFAULT_SCENE.to_string(),
+            _tick_rate: DEDICATED_SERVER_DEFAULT_TICK_RATE,
+            _game_mode_data_name: String::new(),
+        }
+    }
+}
+
+impl DedicatedServerOptions {
+    // --port <port> --scene <scene name> --tick-rate <hz> --game-mode <game mode name>
+    pub fn parse_options(args: &[String]) -> DedicatedServerOptions {
+        let mut options = DedicatedServerOptions::default();
+        let mut index = 0;
+        while index < args.len() {
+            let value = args.get(index + 1);
+            match (args[index].as_str(), value) {
+                ("--port", Some(value)) => {
+                    options._port = value.parse::<u16>().unwrap_or(NETWORK_DEFAULT_PORT);
+                    index += 1;
+                },
+                ("--scene", Some(value)) => {
+                    options._scene_name = value.clone();
+                    index += 1;
+                },
+                ("--tick-rate", Some(value)) => {
+                    options._tick_rate = value.parse::<u32>().unwrap_or(DEDICATED_SERVER_DEFAULT_TICK_RATE).clamp(1, DEDICATED_SERVER_MAX_TICK_RATE);
+                    index += 1;
+                },
+                ("--game-mode", Some(value)) => {
+                    options._game_mode_data_name = value.clone();
+                    index += 1;
+                },
+                (arg, _) => log::warn!("unknown option: {}", arg),
+            }
+            index += 1;
+        }
+        options
+    }
+}
+
+impl AdminCommand {
+    pub fn parse_admin_command(line: &str) -> Option<AdminCommand> {
+        let mut words = line.split_whitespace();
+        let command = words.next()?;
+        let argument = words.next();
+        match (command, argument) {
+            ("status", _) => Some(AdminCommand::Status),
+            ("kick", Some(client_id)) => client_id.parse::<u32>().ok().map(AdminCommand::Kick),
+            ("map", Some(scene_name)) => Some(AdminCommand::ChangeMap(scene_name.to_string())),
+            ("mode", Some(game_mode_data_name)) => Some(AdminCommand::ChangeGameMode(game_mode_data_name.to_string())),
+            ("restart", _) => Some(AdminCommand::Restart),
+            ("quit", _) | ("exit", _) => Some(AdminCommand::Quit),
+            ("help", _) => Some(AdminCommand::Help),
+            _ => None,
+        }
+    }
+}
+
+// the stdin lines are read on the other thread, the server polls the commands on every frame
+fn spawn_admin_console() -> Receiver<AdminCommand> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let stdin = std::io::stdin();
+        for line in stdin.lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match AdminCommand::parse_admin_command(&line) {
+                Some(admin_command) => {
+                    if sender.send(admin_command).is_err() {
+                        break;
+                    }
+                },
+                None => log::warn!("unknown command: {} (help: list of commands)", line.trim()),
+            }
+        }
+    });
+    receiver
+}
+
+impl DedicatedServer {
+    pub fn create_dedicated_server(options: DedicatedServerOptions) -> Box<DedicatedServer> {
+        Box::new(DedicatedServer {
+            _game_client: std::ptr::null(),
+            _scene_name: options._scene_name.clone(),
+            _options: options,
+            _admin_commands: spawn_admin_console(),
+            _accumulated_time: 0.0,
+            _tick: 0,
+            _match_time: 0.0,
+        })
+    }
+
+    pub fn initialize_dedicated_server(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+        log::info!("dedicated server: port {} scene {} tick rate {}", self._options._port, self._scene_name, self._options._tick_rate);
+        if false == game_client.get_project_resources().has_scene_data(&self._scene_name) {
+            log::warn!("initialize_dedicated_server: not found scene {}, {} is opened", self._scene_name, DEDICATED_SERVER_DEFAULT_SCENE);
+            self._scene_name = DEDICATED_SERVER_DEFAULT_SCENE.to_string();
+        }
+        let game_mode_data_name = &self._options._game_mode_data_name;
+        if false == game_mode_data_name.is_empty() {
+            if game_client.get_project_resources().has_game_mode_data(game_mode_data_name) {
+                game_client.get_game_mode_manager_mut().select_game_mode(game_mode_data_name);
+            } else {
+                log::warn!("initialize_dedicated_server: not found game mode {}, the game mode of the scene is used", game_mode_data_name);
+            }
+        }
+        if false == game_client.get_network_manager_mut().start_server(self._options._port) {
+            log::error!("initialize_dedicated_server failed: port {}", self._options._port);
+            std::process::exit(1);
+        }
+        let scene_name = self._scene_name.clone();
+        self.get_game_client_mut().start_game(&scene_name);
+        self.on_match_started();
+    }
+
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
+    pub fn get_tick_delta_time(&self) -> f32 {
+        1.0 / self._options._tick_rate as f32
+    }
+
+    // the spawn points of the level refer to the ship datas of the game datas
+    fn validate_level_data(&self) {
+        let game_client = self.get_game_client();
+        let project_resources = game_client.get_project_resources();
+        let level_data = game_client.get_project_scene_manager().get_level_data();
+        for spawn_point_type in level_data._spawn_point_datas.iter() {
+            let ship_data_name = match spawn_point_type {
+                SpawnPointType::Player(spawn_point_data) | SpawnPointType::NonPlayer(spawn_point_data) => &spawn_point_data._ship_data_name,
+                SpawnPointType::None => continue,
+            };
+            if false == project_resources.has_ship_data(ship_data_name) {
+                log::warn!("validate_level_data: unknown ship data {} in {}", ship_data_name, self._scene_name);
+            }
+        }
+    }
+
+    fn on_match_started(&mut self) {
+        self._accumulated_time = 0.0;
+        self._tick = 0;
+        self._match_time = 0.0;
+        self.validate_level_data();
+        let game_client = self.get_game_client();
+        let level_data = game_client.get_project_scene_manager().get_level_data();
+        log::info!(
+            "start match: {} game mode {:?} spawn points {} buildings {} pickups {} resource nodes {} actors {}",
+            self._scene_name,
+            game_client.get_game_mode_manager().get_game_mode_type(),
+            level_data._spawn_point_datas.len(),
+            level_data._building_placement_datas.len(),
+            level_data._pickup_placement_datas.len(),
+            level_data._resource_node_placement_datas.len(),
+            game_client.get_actor_manager()._actors.len()
+        );
+    }
+
+    // the remote clients keep the connection and get the new ships
+    pub fn restart_match(&mut self) {
+        let scene_name = self._scene_name.clone();
+        self.get_game_client_mut().restart_game(&scene_name);
+        self.on_match_started();
+    }
+
+    // the clients have loaded the previous map, they join the new map again
+    pub fn change_map(&mut self, scene_name: &str) {
+        if false == self.get_game_client().get_project_resources().has_scene_data(scene_name) {
+            log::warn!("change_map failed: not found scene {}", scene_name);
+            return;
+        }
+        self.get_game_client_mut().get_network_manager_mut().kick_all_clients(&format!("the server changes the map to {}", scene_name));
+        self._scene_name = scene_name.to_string();
+        self.restart_match();
+    }
+
+    // the match is restarted with the game mode, the clients keep the connection
+    pub fn change_game_mode(&mut self, game_mode_data_name: &str) {
+        if false == self.get_game_client().get_project_resources().has_game_mode_data(game_mode_data_name) {
+            log::warn!("change_game_mode failed: not found game mode {}", game_mode_data_name);
+            return;
+        }
+        self.get_game_client_mut().get_game_mode_manager_mut().select_game_mode(game_mode_data_name);
+        self.restart_match();
+    }
+
+    pub fn kick_client(&mut self, client_id: u32) {
+        if self.get_game_client_mut().get_network_manager_mut().kick_client(client_id, "kicked by the server") {
+            log::info!("kick_client: client {}", client_id);
+        } else {
+            log::warn!("kick_client: client {} is not connected", client_id);
+        }
+    }
+
+    fn log_status(&self) {
+        let game_client = self.get_game_client();
+        let remote_clients = game_client.get_network_manager().get_remote_clients();
+        log::info!(
+            "status: {} port {} tick {} ({} hz) match time {:.1}s actors {} clients {}",
+            self._scene_name,
+            self._options._port,
+            self._tick,
+            self._options._tick_rate,
+            self._match_time,
+            game_client.get_actor_manager()._actors.len(),
+            remote_clients.len()
+        );
+        for remote_client in remote_clients.values() {
+            log::info!(
+                "    client {} {} actor {} idle {:.1}s",
+                remote_client._client_id,
+                remote_client._address,
+                remote_client._actor_handle.to_id(),
+                remote_client._idle_time
+            );
+        }
+    }
+
+    fn quit(&mut self) {
+        self.get_game_client_mut().get_network_manager_mut().kick_all_clients("the server is closed");
+        log::info!("dedicated server is closed");
+        std::process::exit(0);
+    }
+
+    fn process_admin_commands(&mut self) {
+        loop {
+            let admin_command = match self._admin_commands.try_recv() {
+                Ok(admin_command) => admin_command,
+                Err(TryRecvError::Empty) => break,
+                // stdin is closed, the server keeps running without the console
+                Err(TryRecvError::Disconnected) => break,
+            };
+            match admin_command {
+                AdminCommand::Status => self.log_status(),
+                AdminCommand::Kick(client_id) => self.kick_client(client_id),
+                AdminCommand::ChangeMap(scene_name) => self.change_map(&scene_name),
+                AdminCommand::ChangeGameMode(game_mode_data_name) => self.change_game_mode(&game_mode_data_name),
+                AdminCommand::Restart => self.restart_match(),
+                AdminCommand::Quit => self.quit(),
+                AdminCommand::Help => log::info!("commands: status, kick <client id>, map <scene name>, mode <game mode name>, restart, quit"),
+            }
+        }
+    }
+
+    // the game is updated with the fixed tick, the late ticks are caught up
+    pub fn update_dedicated_server(&mut self, delta_time: f32) {
+        self.process_admin_commands();
+
+        let tick_delta_time = self.get_tick_delta_time();
+        self._accumulated_time += delta_time;
+        let mut tick_count: u32 = 0;
+        while tick_delta_time <= self._accumulated_time {
+            if DEDICATED_SERVER_MAX_TICKS_PER_FRAME <= tick_count {
+                // too late, the missed ticks are dropped
+                self._accumulated_time = 0.0;
+                break;
+            }
+            self.get_game_client_mut().update_game_client_with_delta_time(tick_delta_time);
+            self._accumulated_time -= tick_delta_time;
+            self._tick += 1;
+            self._match_time += tick_delta_time;
+            tick_count += 1;
+        }
+    }
+}
+
+// the engine application initializes the logger of the game, the dedicated server writes the log to stdout.
+struct DedicatedServerLogger;
+
+impl Log for DedicatedServerLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            println!("[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {
+    }
+}
+
+static DEDICATED_SERVER_LOGGER: DedicatedServerLogger = DedicatedServerLogger;
+
+// the server runs without the application, the window and the vulkan device.
+// the game datas, the scenes and the height maps are read from the resource files.
+pub fn run_dedicated_server() {
+    if log::set_logger(&DEDICATED_SERVER_LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Info);
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = DedicatedServerOptions::parse_options(&args);
+
+    let mut project_resources = ProjectResources::create_project_resources();
+    project_resources.load_game_resources();
+    let mut project_scene_manager = ProjectSceneManager::create_project_scene_manager();
+    project_scene_manager.initialize_headless_project_scene_manager(&project_resources);
+    let mut game_client = GameClient::create_game_client();
+    game_client.initialize_headless_game_client(&project_resources, &project_scene_manager);
+    let mut dedicated_server = DedicatedServer::create_dedicated_server(options);
+    dedicated_server.initialize_dedicated_server(&game_client);
+
+    let tick_duration = Duration::from_secs_f32(dedicated_server.get_tick_delta_time());
+    let mut last_time = Instant::now();
+    loop {
+        let frame_start_time = Instant::now();
+        let delta_time = frame_start_time.duration_since(last_time).as_secs_f32();
+        last_time = frame_start_time;
+        dedicated_server.update_dedicated_server(delta_time);
+
+        // sleep for the rest of the tick
+        let elapsed_time = frame_start_time.elapsed();
+        if elapsed_time < tick_duration {
+            thread::sleep(tick_duration - elapsed_time);
+        }
+    }
+}
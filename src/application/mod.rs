@@ -1,2 +1,3 @@
+pub mod dedicated_server;
 pub mod project_application;
 pub mod project_scene_manager;
\ No newline at end of file
@@ -51,7 +51,7 @@ impl ProjectApplicationBase for ProjectApplication {
         self.get_game_client_mut().initialize_game_client(self);
 
         // start game
-        self.get_game_client_mut().start_game();
+        self.get_game_client_mut().start_game("default");
     }
 
     fn terminate_project_application(&mut self) {
@@ -188,6 +188,8 @@ impl ProjectApplicationBase for ProjectApplication {
     fn update_project_application(&mut self, delta_time: f64) {
         if self._is_game_mode {
             self._game_client.update_game_client();
+        } else {
+            self._game_client.get_zone_manager().render_zone_wireframes();
         }
 
         let engine_application = unsafe { &*self._engine_application };
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{ PathBuf };
 use std::rc::Rc;
 
@@ -47,9 +47,32 @@ pub struct SceneDataCreateInfo {
     pub _effects: HashMap<String, EffectCreateInfo>,
     pub _static_objects: HashMap<String, RenderObjectCreateInfo>,
     pub _skeletal_objects: HashMap<String, RenderObjectCreateInfo>,
+    pub _static_colliders: Vec<String>,
+    pub _collision_data: SceneCollisionData,
     pub _level_data: LevelData,
 }
 
+// the world bounds are measured from the models when the scene is opened with the renderer and saved with the scene,
+// the dedicated server has no model data and reads them from the scene file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SceneCollisionData {
+    pub _stage_bound_min: Vector3<f32>,
+    pub _stage_bound_max: Vector3<f32>,
+    // (min, max) by the name of the static object
+    pub _static_collider_bounds: HashMap<String, (Vector3<f32>, Vector3<f32>)>,
+}
+
+impl Default for SceneCollisionData {
+    fn default() -> SceneCollisionData {
+        SceneCollisionData {
+            _stage_bound_min: Vector3::zeros(),
+            _stage_bound_max: Vector3::zeros(),
+            _static_collider_bounds: HashMap::new(),
+        }
+    }
+}
+
 impl Default for SceneDataCreateInfo {
     fn default() -> SceneDataCreateInfo {
         SceneDataCreateInfo {
@@ -59,6 +82,8 @@ impl Default for SceneDataCreateInfo {
             _effects: HashMap::new(),
             _static_objects: HashMap::new(),
             _skeletal_objects: HashMap::new(),
+            _static_colliders: Vec::new(),
+            _collision_data: SceneCollisionData::default(),
             _level_data: LevelData::default(),
         }
     }
@@ -80,6 +105,7 @@ pub struct ProjectSceneManager {
     pub _directional_light_object_map: DirectionalLightObjectMap,
     pub _effect_id_map: EffectIDMap,
     pub _height_map_data: HeightMapData,
+    pub _height_map_texture_name: String,
     pub _static_render_object_map: RenderObjectMap,
     pub _skeletal_render_object_map: RenderObjectMap,
     pub _static_render_elements: Vec<RenderElementData>,
@@ -88,6 +114,10 @@ pub struct ProjectSceneManager {
     pub _skeletal_shadow_render_elements: Vec<RenderElementData>,
     pub _render_element_transform_count: usize,
     pub _render_element_transform_matrices: Vec<Matrix4<f32>>,
+    pub _collision_data: SceneCollisionData,
+    // (min, max) of the static colliders, the stage is collided by the height map.
+    pub _static_colliders: Vec<(Vector3<f32>, Vector3<f32>)>,
+    pub _hidden_render_object_names: HashSet<String>,
     pub _level_data: LevelData,
 }
 
@@ -154,6 +184,7 @@ impl ProjectSceneManager {
             _directional_light_object_map: HashMap::new(),
             _effect_id_map: HashMap::default(),
             _height_map_data: HeightMapData::default(),
+            _height_map_texture_name: String::new(),
             _static_render_object_map: HashMap::new(),
             _skeletal_render_object_map: HashMap::new(),
             _static_render_elements: Vec::new(),
@@ -162,6 +193,9 @@ impl ProjectSceneManager {
             _skeletal_shadow_render_elements: Vec::new(),
             _render_element_transform_count: 0,
             _render_element_transform_matrices: vec![Matrix4::identity(); MAX_TRANSFORM_COUNT],
+            _collision_data: SceneCollisionData::default(),
+            _static_colliders: Vec::new(),
+            _hidden_render_object_names: HashSet::new(),
             _level_data: LevelData::default(),
         })
     }
@@ -178,19 +212,58 @@ impl ProjectSceneManager {
         self._project_resources = engine_resources._project_resources as *const ProjectResources;
         self.resized_window(window_size.x, window_size.y);
     }
+    // the dedicated server has no renderer and no effect manager, only the simulation data of the scene is opened.
+    pub fn initialize_headless_project_scene_manager(&mut self, project_resources: &ProjectResources) {
+        self._renderer_data = std::ptr::null();
+        self._effect_manager = std::ptr::null();
+        self._project_resources = project_resources;
+    }
+    pub fn is_headless(&self) -> bool { self._renderer_data.is_null() }
     pub fn get_project_resources(&self) -> &ProjectResources { unsafe { &*self._project_resources } }
     pub fn get_project_resources_mut(&self) -> &mut ProjectResources { unsafe { &mut *(self._project_resources as *mut ProjectResources) } }
     pub fn get_height_map_data(&self) -> &HeightMapData { &self._height_map_data }
     pub fn get_height_map_collision_point(&self, start_pos: &Vector3<f32>, dir: &Vector3<f32>, limit_dist: f32, collision_point: &mut Vector3<f32>) -> bool {
         self._height_map_data.get_collision_point(start_pos, dir, limit_dist, collision_point)
     }
+    pub fn is_line_of_sight_blocked(&self, start_pos: &Vector3<f32>, end_pos: &Vector3<f32>) -> bool {
+        self._height_map_data.is_line_of_sight_blocked(start_pos, end_pos)
+    }
     pub fn get_height_bilinear(&self, pos: &Vector3<f32>, lod: usize) -> f32 {
         return self._height_map_data.get_height_bilinear(pos, lod);
     }
+    pub fn get_height_normal(&self, pos: &Vector3<f32>, lod: usize) -> Vector3<f32> {
+        self._height_map_data.get_normal_bilinear(pos, lod)
+    }
     pub fn get_height_point(&self, pos: &Vector3<f32>, lod: usize) -> f32 {
         self._height_map_data.get_height_point(pos, lod)
     }
+    pub fn add_crater(&mut self, pos: &Vector3<f32>, radius: f32, depth: f32) {
+        self._height_map_data.add_crater(pos, radius, depth);
+    }
+    // the hidden render objects are skipped when the render elements are gathered
+    pub fn set_render_object_hidden(&mut self, object_name: &str, hidden: bool) {
+        if hidden {
+            self._hidden_render_object_names.insert(object_name.to_string());
+        } else {
+            self._hidden_render_object_names.remove(object_name);
+        }
+    }
+    // the texture is created from the resource file, the pixels must have the same size and format (rgba8).
+    pub fn upload_texture_pixels(&self, engine_application: &EngineApplication, texture_name: &str, pixels: &Vec<u8>, region_min: &Vector2<i32>, region_max: &Vector2<i32>) {
+        let project_resources = self.get_project_resources();
+        if project_resources.has_texture_data(texture_name) {
+            let texture_data = project_resources.get_texture_data(texture_name);
+            engine_application.get_renderer_context().upload_texture_data(&texture_data.borrow(), pixels, region_min, region_max);
+        }
+    }
+    // push the modified height map to the stage texture, the region is tracked for the partial upload.
+    pub fn update_height_map_texture(&mut self, engine_application: &EngineApplication) {
+        if let Some((region_min, region_max)) = self._height_map_data.take_dirty_region() {
+            self.upload_texture_pixels(engine_application, &self._height_map_texture_name, self._height_map_data.get_texture_pixels(), &region_min, &region_max);
+        }
+    }
     pub fn get_level_data(&self) -> &LevelData { &self._level_data }
+    pub fn get_static_colliders(&self) -> &Vec<(Vector3<f32>, Vector3<f32>)> { &self._static_colliders }
     pub fn get_renderer_data(&self) -> &RendererData { unsafe { &*self._renderer_data } }
     pub fn get_renderer_data_mut(&self) -> &mut RendererData { unsafe { &mut *(self._renderer_data as *mut RendererData) } }
     pub fn get_engine_resources(&self) -> &EngineResources { self.get_project_resources().get_engine_resources() }
@@ -212,23 +285,34 @@ impl ProjectSceneManager {
         light_object_data
     }
 
-    pub fn add_static_render_object(&mut self, object_name: &str, render_object_create_info: &RenderObjectCreateInfo) -> RcRefCell<RenderObjectData> {
+    // returns None without the renderer, the game keeps its own transforms.
+    pub fn add_static_render_object(&mut self, object_name: &str, render_object_create_info: &RenderObjectCreateInfo) -> Option<RcRefCell<RenderObjectData>> {
+        if self.is_headless() {
+            return None;
+        }
         let model_data = self.get_engine_resources().get_model_data(&render_object_create_info._model_data_name);
         let new_object_name = system::generate_unique_name(&self._static_render_object_map, &object_name);
         let render_object_data = newRcRefCell(RenderObjectData::create_render_object_data(&new_object_name, &model_data, &render_object_create_info));
         self._static_render_object_map.insert(new_object_name, render_object_data.clone());
-        render_object_data
+        Some(render_object_data)
     }
 
-    pub fn add_skeletal_render_object(&mut self, object_name: &str, render_object_create_info: &RenderObjectCreateInfo) -> RcRefCell<RenderObjectData> {
+    pub fn add_skeletal_render_object(&mut self, object_name: &str, render_object_create_info: &RenderObjectCreateInfo) -> Option<RcRefCell<RenderObjectData>> {
+        if self.is_headless() {
+            return None;
+        }
         let model_data = self.get_engine_resources().get_model_data(&render_object_create_info._model_data_name);
         let new_object_name = system::generate_unique_name(&self._skeletal_render_object_map, &object_name);
         let render_object_data = newRcRefCell(RenderObjectData::create_render_object_data(&new_object_name, model_data, &render_object_create_info));
         self._skeletal_render_object_map.insert(new_object_name, render_object_data.clone());
-        render_object_data
+        Some(render_object_data)
     }
 
+    // the effects are skipped without the renderer
     pub fn add_effect(&mut self, object_name: &str, effect_create_info: &EffectCreateInfo) -> i64 {
+        if self.is_headless() {
+            return -1;
+        }
         let new_object_name = system::generate_unique_name(&self._effect_id_map, &object_name);
         let effect_data = self.get_project_resources().get_effect_data(&effect_create_info._effect_data_name);
         let effect_id = self.get_effect_manager_mut().create_effect(effect_create_info, &effect_data);
@@ -250,9 +334,13 @@ impl ProjectSceneManager {
 
     pub fn remove_skeletal_render_object(&mut self, object_name: &str) {
         self._skeletal_render_object_map.remove(object_name);
+        self._hidden_render_object_names.remove(object_name);
     }
 
     pub fn get_effect(&self, effect_id: i64) -> Option<&RcRefCell<EffectInstance>> {
+        if self.is_headless() {
+            return None;
+        }
         self.get_effect_manager().get_effect(effect_id)
     }
 
@@ -301,6 +389,7 @@ impl ProjectSceneManager {
         camera: &CameraObjectData,
         light: &DirectionalLightData,
         render_object_map: &RenderObjectMap,
+        hidden_render_object_names: &HashSet<String>,
         render_elements: &mut Vec<RenderElementData>,
         render_shadow_elements: &mut Vec<RenderElementData>,
         render_element_transform_offset: &mut usize,
@@ -309,7 +398,11 @@ impl ProjectSceneManager {
         render_elements.clear();
         render_shadow_elements.clear();
 
-        for (_key, render_object_data_ref) in render_object_map.iter() {
+        for (key, render_object_data_ref) in render_object_map.iter() {
+            if hidden_render_object_names.contains(key) {
+                continue;
+            }
+
             let render_object_data = render_object_data_ref.borrow();
             let model_data = ptr_as_ref(render_object_data.get_model_data().as_ptr());
             let mesh_data = model_data.get_mesh_data().borrow();
@@ -399,6 +492,8 @@ impl ProjectSceneManager {
             _effects: HashMap::new(),
             _static_objects: HashMap::new(),
             _skeletal_objects: HashMap::new(),
+            _static_colliders: Vec::new(),
+            _collision_data: SceneCollisionData::default(),
             _level_data: LevelData::default(),
         };
 
@@ -491,6 +586,9 @@ impl ProjectSceneManager {
                 ..Default::default()
             }
         );
+        // the stage model is a unit square with the heights from 0 to 1, the editor measures it again when the scene is saved.
+        scene_data_create_info._collision_data._stage_bound_min = Vector3::new(-1000.0, -20.0, -1000.0);
+        scene_data_create_info._collision_data._stage_bound_max = Vector3::new(1000.0, 980.0, 1000.0);
         scene_data_create_info._level_data = LevelData::get_test_level_data();
 
         self.get_project_resources_mut().save_scene_data(scene_data_name, &scene_data_create_info);
@@ -499,8 +597,6 @@ impl ProjectSceneManager {
     pub fn open_scene_data(&mut self, scene_data_name: &str) {
         self._scene_name = String::from(scene_data_name);
 
-        self.initialize_light_probe_cameras();
-
         let project_resources = unsafe { &*self._project_resources };
 
         if false == project_resources.has_scene_data(scene_data_name) {
@@ -510,6 +606,50 @@ impl ProjectSceneManager {
         let scene_data_create_info = project_resources.get_scene_data(scene_data_name).borrow();
 
         self._sea_height = scene_data_create_info._sea_height;
+        self._collision_data = scene_data_create_info._collision_data.clone();
+
+        if false == self.is_headless() {
+            self.open_scene_render_objects(&scene_data_create_info);
+        }
+
+        // static colliders, the stage is collided by the height map.
+        for object_name in scene_data_create_info._static_colliders.iter() {
+            match self._collision_data._static_collider_bounds.get(object_name) {
+                Some((bound_min, bound_max)) => self._static_colliders.push((bound_min.clone_owned(), bound_max.clone_owned())),
+                None => log::error!("open_scene_data: not found the bounds of static collider {}", object_name),
+            }
+        }
+
+        // height map, the heights are read from the source image without the texture of the renderer
+        match scene_data_create_info._static_objects.get("stage") {
+            Some(stage_create_info) => {
+                let stage_height_map_name: String = stage_create_info._model_data_name.clone() + "_heightmap";
+                let stage_bound_box = BoundingBox::create_bounding_box(&self._collision_data._stage_bound_min, &self._collision_data._stage_bound_max);
+                let texture_directory = PathBuf::from(TEXTURE_SOURCE_DIRECTORY);
+                let mut height_map_directory: PathBuf = texture_directory.clone();
+                height_map_directory.push("stages");
+                let height_map_files = project_resources.collect_resources(height_map_directory.as_path(), &EXT_IMAGE_SOURCE);
+                for height_map_file in height_map_files.iter() {
+                    let resource_name = get_resource_name_from_file_path(&texture_directory, &height_map_file);
+                    if resource_name == stage_height_map_name {
+                        let (image_width, image_height, _image_layers, image_data, _image_format) = EngineResources::load_image_data(height_map_file);
+                        self._height_map_data.initialize_height_map_data(&stage_bound_box, image_width as i32, image_height as i32, image_data, scene_data_create_info._sea_height);
+                        self._height_map_texture_name = stage_height_map_name.clone();
+                        break;
+                    }
+                }
+            },
+            None => log::error!("open_scene_data: not found the stage of scene {}", scene_data_name),
+        }
+
+        // level data
+        self._level_data = scene_data_create_info._level_data.clone();
+    }
+
+    // the cameras, the lights, the effects and the render objects of the scene.
+    // the world bounds of the stage and the static colliders are measured from the models for the collision data.
+    fn open_scene_render_objects(&mut self, scene_data_create_info: &SceneDataCreateInfo) {
+        self.initialize_light_probe_cameras();
         self.get_renderer_data_mut()._fft_ocean.set_height(scene_data_create_info._sea_height);
 
         // cameras
@@ -558,30 +698,41 @@ impl ProjectSceneManager {
             self.add_skeletal_render_object(object_name, render_object_create_info);
         }
 
-        // height map
-        let maybe_stage_model = self._static_render_object_map.get("stage");
-        {
-            let mut stage_model = maybe_stage_model.unwrap().borrow_mut();
-            let stage_height_map_name: String = stage_model._model_data.borrow()._model_data_name.clone() + "_heightmap";
-            let texture_directory = PathBuf::from(TEXTURE_SOURCE_DIRECTORY);
-            let mut height_map_directory: PathBuf = texture_directory.clone();
-            height_map_directory.push("stages");
-            let height_map_files = project_resources.get_engine_resources().collect_resources(height_map_directory.as_path(), &EXT_IMAGE_SOURCE);
-            for height_map_file in height_map_files.iter() {
-                let resource_name = get_resource_name_from_file_path(&texture_directory, &height_map_file);
-                if resource_name == stage_height_map_name {
-                    let (image_width, image_height, _image_layers, image_data, _image_format) = EngineResources::load_image_data(height_map_file);
-                    stage_model._transform_object.update_transform_object();
-                    let stage_transform = ptr_as_ref(stage_model._transform_object.get_matrix());
-                    stage_model.update_bound_box(stage_transform);
-                    self._height_map_data.initialize_height_map_data(&stage_model._bound_box, image_width as i32, image_height as i32, image_data, scene_data_create_info._sea_height);
-                    break;
-                }
+        // static colliders
+        for object_name in scene_data_create_info._static_colliders.iter() {
+            match self._static_render_object_map.get(object_name) {
+                Some(render_object) => {
+                    let bound_box = ProjectSceneManager::measure_bound_box(render_object);
+                    self._collision_data._static_collider_bounds.insert(object_name.clone(), (bound_box._min, bound_box._max));
+                },
+                None => log::error!("open_scene_data: not found static collider {}", object_name),
             }
         }
 
-        // level data
-        self._level_data = scene_data_create_info._level_data.clone();
+        // stage
+        if let Some(stage_model) = self._static_render_object_map.get("stage") {
+            let bound_box = ProjectSceneManager::measure_bound_box(stage_model);
+            if bound_box._min != self._collision_data._stage_bound_min || bound_box._max != self._collision_data._stage_bound_max {
+                log::warn!(
+                    "open_scene_data: the stage bounds of {} are {:?} {:?}, the collision data of the scene has {:?} {:?}",
+                    self._scene_name,
+                    bound_box._min,
+                    bound_box._max,
+                    self._collision_data._stage_bound_min,
+                    self._collision_data._stage_bound_max
+                );
+            }
+            self._collision_data._stage_bound_min = bound_box._min;
+            self._collision_data._stage_bound_max = bound_box._max;
+        }
+    }
+
+    fn measure_bound_box(render_object: &RcRefCell<RenderObjectData>) -> BoundingBox {
+        let mut render_object = render_object.borrow_mut();
+        render_object._transform_object.update_transform_object();
+        let transform = ptr_as_ref(render_object._transform_object.get_matrix());
+        render_object.update_bound_box(transform);
+        render_object._bound_box.clone()
     }
 
     pub fn close_scene_data(&mut self) {
@@ -590,6 +741,8 @@ impl ProjectSceneManager {
         self._effect_id_map.clear();
         self._static_render_object_map.clear();
         self._skeletal_render_object_map.clear();
+        self._static_colliders.clear();
+        self._hidden_render_object_names.clear();
         self._static_render_elements.clear();
         self._static_shadow_render_elements.clear();
         self._skeletal_render_elements.clear();
@@ -604,6 +757,8 @@ impl ProjectSceneManager {
             _effects: HashMap::new(),
             _static_objects: HashMap::new(),
             _skeletal_objects: HashMap::new(),
+            _static_colliders: self._collision_data._static_collider_bounds.keys().cloned().collect(),
+            _collision_data: self._collision_data.clone(),
             _level_data: LevelData::default(),
         };
 
@@ -674,6 +829,8 @@ impl ProjectSceneManager {
         let font_manager = engine_application.get_font_manager_mut();
         let delta_time: f64 = time_data._delta_time;
 
+        self.update_height_map_texture(engine_application);
+
         let main_camera = ptr_as_mut(self.get_main_camera());
         main_camera.update_camera_object_data();
         let camera_position = &main_camera.get_camera_position();
@@ -701,6 +858,7 @@ impl ProjectSceneManager {
                 &main_camera,
                 &main_light,
                 &self._static_render_object_map,
+                &self._hidden_render_object_names,
                 &mut self._static_render_elements,
                 &mut self._static_shadow_render_elements,
                 &mut self._render_element_transform_count,
@@ -712,6 +870,7 @@ impl ProjectSceneManager {
                 &main_camera,
                 &main_light,
                 &self._skeletal_render_object_map,
+                &self._hidden_render_object_names,
                 &mut self._skeletal_render_elements,
                 &mut self._skeletal_shadow_render_elements,
                 &mut self._render_element_transform_count,
@@ -0,0 +1,5 @@
+use destruction_zone::application::dedicated_server::run_dedicated_server;
+
+pub fn main() {
+    run_dedicated_server();
+}
@@ -1,34 +1,95 @@
-use std::collections::HashMap;
-use std::rc::Rc;
+use std::cmp::Ordering;
 
-use rust_engine_3d::renderer::render_object::{RenderObjectData, RenderObjectCreateInfo};
+use nalgebra::Vector3;
+
+use rust_engine_3d::effect::effect_data::EffectCreateInfo;
+use rust_engine_3d::renderer::render_object::RenderObjectCreateInfo;
 use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
 use crate::application::project_scene_manager::ProjectSceneManager;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actors::actor::{ActorController, ActorHandle};
+use crate::game_module::actors::wreck::Wreck;
 use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::{
+    COLLISION_RESTITUTION,
+    RAMMING_DAMAGE_MIN_SPEED,
+    CAMERA_SHAKE_SHIP_DESTROY_TRAUMA,
+    CAMERA_SHAKE_SHIP_DESTROY_RADIUS,
+    PLAYER_INVULNERABLE_EFFECT_INTERVAL,
+};
+use crate::game_module::game_controller::MatchResult;
+use crate::game_module::handle::HandleMap;
+use crate::game_module::ship::ship::{ShipData, ShipInstance};
 use crate::game_module::level_datas::spawn_point::{SpawnPointType, ShipSpawnPointData};
+use crate::game_module::scripts::script_event::ScriptEvent;
 
 
-pub type ActorMap = HashMap<u64, Rc<ActorController>>;
+pub type ActorMap = HandleMap<ActorController>;
 
 pub struct ActorManager {
     pub _game_client: *const GameClient,
-    pub _id_generator: u64,
-    pub _player_actor: *const ActorController,
+    pub _player_actor: Option<ActorHandle>,
+    // the first spawn of the player ship, the respawn uses the ship data of it
+    pub _player_spawn_point_data: Option<ShipSpawnPointData>,
+    pub _player_death_position: Vector3<f32>,
+    pub _player_death_count: i32,
+    // the countdown while the player ship is destroyed
+    pub _player_respawn_time: Option<f32>,
+    pub _player_effect_time: f32,
     pub _actors: ActorMap,
+    pub _wrecks: Vec<Wreck>,
 }
 
-pub fn calc_floating_height(render_object: &RenderObjectData) -> f32 {
-    render_object._bound_box._size.y * 0.5 + 2.0
+pub fn calc_floating_height(ship_data: &ShipData) -> f32 {
+    ship_data._collision_half_extents.y + 2.0
+}
+
+// separates the ships along the normal by the inverse mass ratio and applies the collision impulse.
+// the normal points from ship_b to ship_a, ship_b is None for the static colliders.
+// returns the impact speed along the normal.
+fn resolve_ship_collision(ship_a: &mut ShipInstance, ship_b: Option<&mut ShipInstance>, normal: &Vector3<f32>, penetration: f32) -> f32 {
+    let inverse_mass_a = 1.0 / ship_a.get_mass();
+    let (inverse_mass_b, velocity_b) = match &ship_b {
+        Some(ship_b) => (1.0 / ship_b.get_mass(), ship_b.get_controller().get_velocity().clone_owned()),
+        None => (0.0, Vector3::zeros()),
+    };
+    let inverse_mass_sum = inverse_mass_a + inverse_mass_b;
+
+    // separation
+    let position_a: Vector3<f32> = ship_a.get_controller().get_position() + normal * (penetration * inverse_mass_a / inverse_mass_sum);
+    ship_a.set_position(&position_a);
+
+    // impulse
+    let relative_velocity: Vector3<f32> = ship_a.get_controller().get_velocity() - &velocity_b;
+    let impact_speed = -relative_velocity.dot(normal);
+    let impulse: Vector3<f32> = if 0.0 < impact_speed {
+        normal * ((1.0 + COLLISION_RESTITUTION) * impact_speed / inverse_mass_sum)
+    } else {
+        Vector3::zeros()
+    };
+    let velocity_a: Vector3<f32> = ship_a.get_controller().get_velocity() + &impulse * inverse_mass_a;
+    ship_a.get_controller_mut().set_velocity(&velocity_a);
+
+    if let Some(ship_b) = ship_b {
+        let position_b: Vector3<f32> = ship_b.get_controller().get_position() - normal * (penetration * inverse_mass_b / inverse_mass_sum);
+        ship_b.set_position(&position_b);
+        let velocity_b: Vector3<f32> = velocity_b - &impulse * inverse_mass_b;
+        ship_b.get_controller_mut().set_velocity(&velocity_b);
+    }
+    0f32.max(impact_speed)
 }
 
 impl ActorManager {
     pub fn create_actor_manager() -> Box<ActorManager> {
         Box::new(ActorManager {
             _game_client: std::ptr::null(),
-            _id_generator: 0,
-            _player_actor: std::ptr::null(),
-            _actors: HashMap::new(),
+            _player_actor: None,
+            _player_spawn_point_data: None,
+            _player_death_position: Vector3::zeros(),
+            _player_death_count: 0,
+            _player_respawn_time: None,
+            _player_effect_time: 0.0,
+            _actors: HandleMap::new(),
+            _wrecks: Vec::new(),
         })
     }
 
@@ -36,18 +97,29 @@ impl ActorManager {
         self._game_client = game_client;
     }
     pub fn destroy_actor_manager(&mut self) {
-
+        self._wrecks.clear();
+        self._actors.clear();
+        self._player_actor = None;
+        self.reset_player_respawn();
     }
     pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
     pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
-    pub fn generate_id(&mut self) -> u64 {
-        let id = self._id_generator;
-        self._id_generator += 1;
-        id
+    // None when the actor is removed, the handle of the removed actor never points to another actor
+    pub fn get_actor(&self, actor_handle: ActorHandle) -> Option<&ActorController> {
+        self._actors.get(actor_handle)
+    }
+    pub fn get_actor_mut(&mut self, actor_handle: ActorHandle) -> Option<&mut ActorController> {
+        self._actors.get_mut(actor_handle)
+    }
+    // the id of the scripts and the network
+    pub fn get_actor_by_id(&self, actor_id: u64) -> Option<&ActorController> {
+        self._actors.get(ActorHandle::from_id(actor_id))
+    }
+    pub fn has_actor(&self, actor_handle: ActorHandle) -> bool {
+        self._actors.contains(actor_handle)
     }
 
-    pub fn create_actor(&mut self, game_client: &GameClient, spawn_point_data: &ShipSpawnPointData, is_player_actor: bool) {
-        let id = self.generate_id();
+    pub fn create_actor(&mut self, game_client: &GameClient, spawn_point_data: &ShipSpawnPointData, is_player_actor: bool) -> ActorHandle {
         let project_scene_manager = game_client.get_project_scene_manager_mut();
 
         // create ship render object
@@ -65,31 +137,238 @@ impl ActorManager {
             &render_object_create_info
         );
 
-        // create actor
-        let actor = ActorController::create_actor_controller(id, &ship_data, &actor_render_object, is_player_actor);
-        if is_player_actor {
-            self._player_actor = actor.as_ref();
+        // regist actor, the weapons of the ship keep the handle of the owner
+        let actor_handle = self._actors.insert(ActorController::create_actor_controller(
+            &ship_data,
+            &spawn_point_data._position,
+            &spawn_point_data._rotation,
+            actor_render_object,
+            is_player_actor
+        ));
+        let actor = self._actors.get_mut(actor_handle).unwrap();
+        actor.initialize_actor(actor_handle, project_scene_manager);
+
+        // the first combat ship of the player team is controlled by the player, the later ones only by the respawn
+        if is_player_actor && self._player_spawn_point_data.is_none() && false == actor.get_ship().is_harvester() && false == game_client.is_dedicated_server() {
+            self._player_spawn_point_data = Some(spawn_point_data.clone());
+            self.set_player_actor(actor_handle);
+        };
+        actor_handle
+    }
+    pub fn remove_actor(&mut self, project_scene_manager: &mut ProjectSceneManager, actor_handle: ActorHandle) {
+        if Some(actor_handle) == self._player_actor {
+            self._player_actor = None;
+        }
+        if let Some(mut actor) = self._actors.remove(actor_handle) {
+            actor.remove_actor(project_scene_manager);
+        }
+    }
+    // the kill is logged and the death explosion of the ship is queued before the actor is removed.
+    pub fn destroy_actor(&mut self, actor_handle: ActorHandle, attacker_name: &str, weapon_name: &str) {
+        let game_client = ptr_as_ref(self._game_client);
+        let actor = match self._actors.get(actor_handle) {
+            Some(actor) => ptr_as_ref(actor),
+            None => return,
         };
-        ptr_as_mut(actor.as_ref()).initialize_actor(project_scene_manager);
+        let actor_position = actor.get_transform().get_position().clone_owned();
+        game_client.get_game_ui_manager_mut().add_kill_event_log(attacker_name, weapon_name, &actor.get_actor_name());
+        game_client.get_game_controller_mut().add_camera_shake_at(CAMERA_SHAKE_SHIP_DESTROY_TRAUMA, &actor_position, CAMERA_SHAKE_SHIP_DESTROY_RADIUS);
 
-        // regist actor
-        self._actors.insert(id, actor);
+        let explosion_data_name = &actor.get_ship().get_ship_data()._destroy_explosion_data_name;
+        let project_resources = game_client.get_project_resources();
+        if false == explosion_data_name.is_empty() && project_resources.has_explosion_data(explosion_data_name) {
+            game_client.get_weapon_manager_mut().add_explosion(
+                project_resources.get_explosion_data(explosion_data_name),
+                &actor_position,
+                None,
+                None,
+                &actor.get_actor_name(),
+                explosion_data_name
+            );
+        }
+
+        // the wreck replaces the ship
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let wreck = Wreck::create_wreck(
+            &actor.get_ship()._ship_data,
+            &actor_position,
+            actor.get_transform().get_rotation(),
+            actor.get_velocity(),
+            project_scene_manager
+        );
+        self._wrecks.push(wreck);
+        game_client.get_script_manager_mut().add_script_event(ScriptEvent::ActorDestroyed {
+            _actor_id: actor.get_actor_id(),
+            _is_player_team: actor.is_player_actor(),
+        });
+        game_client.get_game_mode_manager_mut().add_kill_score(actor.is_player_actor());
+        if false == actor.is_player_actor() {
+            game_client.get_pickup_manager_mut().drop_pickup(&actor.get_ship().get_ship_data()._drop_table_data_name, &actor_position);
+        }
+        let is_player_destroyed = Some(actor_handle) == self._player_actor;
+        self.remove_actor(project_scene_manager, actor_handle);
+        if is_player_destroyed {
+            self.on_player_actor_destroyed(&actor_position);
+        }
     }
-    pub fn remove_actor(&mut self, project_scene_manager: &mut ProjectSceneManager, actor: &mut ActorController) {
-        actor.remove_actor(project_scene_manager);
-        self._actors.remove(&actor.get_actor_id());
+    // None while the player ship is destroyed
+    pub fn get_player_actor(&self) -> Option<&ActorController> {
+        self._player_actor.and_then(|actor_handle| self.get_actor(actor_handle))
+    }
+    pub fn get_player_actor_mut(&mut self) -> Option<&mut ActorController> {
+        self._player_actor.and_then(|actor_handle| self.get_actor_mut(actor_handle))
+    }
+    // the camera and the huds look at the place of the death while the player waits for the respawn
+    pub fn get_player_focus_position(&self) -> Vector3<f32> {
+        match self.get_player_actor() {
+            Some(player_actor) => player_actor.get_transform().get_position().clone_owned(),
+            None => self._player_death_position.clone_owned(),
+        }
     }
-    pub fn get_player_actor(&self) -> &ActorController {
-        ptr_as_ref(self._player_actor)
+    pub fn get_player_death_position(&self) -> &Vector3<f32> { &self._player_death_position }
+    pub fn get_player_respawn_time(&self) -> Option<f32> { self._player_respawn_time }
+    pub fn is_player_respawning(&self) -> bool { self._player_respawn_time.is_some() }
+    // None is the endless respawn
+    pub fn get_player_remaining_lives(&self) -> Option<i32> {
+        let game_mode_data = self.get_game_client().get_game_mode_manager().get_game_mode_data();
+        if game_mode_data.has_player_lives() { Some(0.max(game_mode_data._player_lives - self._player_death_count)) } else { None }
+    }
+    // the network client, the server decides the death and the respawn of the player ship
+    pub fn set_player_actor(&mut self, actor_handle: ActorHandle) {
+        if let Some(player_actor) = self.get_player_actor_mut() {
+            player_actor.set_controlled_by_player(false);
+        }
+        self._player_actor = Some(actor_handle);
+        if let Some(player_actor) = self.get_actor_mut(actor_handle) {
+            player_actor.set_controlled_by_player(true);
+        }
+    }
+    pub fn set_player_respawn_time(&mut self, respawn_time: Option<f32>) { self._player_respawn_time = respawn_time; }
+    pub fn remove_player_actor(&mut self, project_scene_manager: &mut ProjectSceneManager) {
+        if let Some(player_actor) = self._player_actor {
+            self._player_death_position = self.get_player_focus_position();
+            self.remove_actor(project_scene_manager, player_actor);
+        }
+    }
+    pub fn reset_player_respawn(&mut self) {
+        self._player_spawn_point_data = None;
+        self._player_death_position = Vector3::zeros();
+        self._player_death_count = 0;
+        self._player_respawn_time = None;
+        self._player_effect_time = 0.0;
+    }
+
+    // the countdown starts, the match is lost when the player has no lives left
+    fn on_player_actor_destroyed(&mut self, death_position: &Vector3<f32>) {
+        let game_client = ptr_as_ref(self._game_client);
+        self._player_death_position = death_position.clone_owned();
+        self._player_death_count += 1;
+        if Some(0) == self.get_player_remaining_lives() {
+            game_client.get_game_ui_manager_mut().add_objective_event_log("No lives left");
+            game_client.get_game_controller_mut().end_match(MatchResult::Defeat);
+            return;
+        }
+        let game_mode_data = game_client.get_game_mode_manager().get_game_mode_data();
+        self._player_respawn_time = Some(game_mode_data._player_respawn_time);
+    }
+
+    // the player spawn point of the same ship which is the farthest from the enemies
+    fn find_player_respawn_point(&self, game_client: &GameClient) -> Option<ShipSpawnPointData> {
+        let player_spawn_point_data = self._player_spawn_point_data.as_ref()?;
+        let level_data = game_client.get_project_scene_manager().get_level_data();
+        let mut spawn_point_datas: Vec<&ShipSpawnPointData> = level_data._spawn_point_datas.iter().filter_map(|spawn_point_type| match spawn_point_type {
+            SpawnPointType::Player(spawn_point_data) if player_spawn_point_data._ship_data_name == spawn_point_data._ship_data_name => Some(spawn_point_data),
+            _ => None,
+        }).collect();
+        if spawn_point_datas.is_empty() {
+            spawn_point_datas.push(player_spawn_point_data);
+        }
+
+        let enemy_positions: Vec<&Vector3<f32>> = self._actors.values()
+            .filter(|actor| false == actor.is_player_actor())
+            .map(|actor| actor.get_transform().get_position())
+            .collect();
+        let get_nearest_enemy_distance = |spawn_point_data: &ShipSpawnPointData| -> f32 {
+            enemy_positions.iter().map(|position| (*position - &spawn_point_data._position).norm()).fold(f32::MAX, f32::min)
+        };
+        spawn_point_datas.iter()
+            .max_by(|a, b| get_nearest_enemy_distance(a).total_cmp(&get_nearest_enemy_distance(b)))
+            .map(|spawn_point_data| (*spawn_point_data).clone())
+    }
+
+    fn respawn_player_actor(&mut self, game_client: &GameClient) {
+        let spawn_point_data = match self.find_player_respawn_point(game_client) {
+            Some(spawn_point_data) => spawn_point_data,
+            None => {
+                log::error!("respawn_player_actor: the player has no spawn point");
+                return;
+            },
+        };
+        let player_actor_handle = self.create_actor(game_client, &spawn_point_data, true);
+        self.set_player_actor(player_actor_handle);
+        let player_actor = self.get_actor_mut(player_actor_handle).unwrap();
+        let game_mode_data = game_client.get_game_mode_manager().get_game_mode_data();
+        player_actor.get_ship_mut().set_invulnerable_time(game_mode_data._player_invulnerable_time);
+        self._player_effect_time = 0.0;
+
+        let game_controller = game_client.get_game_controller_mut();
+        game_controller.clear_locked_target();
+        game_controller.move_camera_to(&spawn_point_data._position);
+        let message = match self.get_player_remaining_lives() {
+            Some(remaining_lives) => format!("Respawned, {} lives left", remaining_lives),
+            None => "Respawned".to_string(),
+        };
+        game_client.get_game_ui_manager_mut().add_reinforcement_event_log(&message);
+    }
+
+    // the network manager respawns the ships of the remote players, the client follows the snapshots
+    fn update_player_respawn(&mut self, game_client: &GameClient, delta_time: f32) {
+        if game_client.get_network_manager().is_client() {
+            return;
+        }
+
+        if let Some(respawn_time) = self._player_respawn_time {
+            let respawn_time = respawn_time - delta_time;
+            if 0.0 < respawn_time {
+                self._player_respawn_time = Some(respawn_time);
+            } else {
+                self._player_respawn_time = None;
+                self.respawn_player_actor(game_client);
+            }
+            return;
+        }
+
+        // the effect blinks on the player ship while it is invulnerable
+        let player_position = match self.get_player_actor() {
+            Some(player_actor) if player_actor.get_ship().is_invulnerable() => player_actor.get_transform().get_position().clone_owned(),
+            _ => return,
+        };
+        self._player_effect_time -= delta_time;
+        if 0.0 < self._player_effect_time {
+            return;
+        }
+        self._player_effect_time += PLAYER_INVULNERABLE_EFFECT_INTERVAL;
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let game_mode_data = game_client.get_game_mode_manager().get_game_mode_data();
+        for effect_data_name in game_mode_data._player_respawn_effects.iter() {
+            let effect_create_info = EffectCreateInfo {
+                _effect_position: player_position.clone_owned(),
+                _effect_data_name: effect_data_name.clone(),
+                ..Default::default()
+            };
+            project_scene_manager.add_effect(effect_data_name, &effect_create_info);
+        }
     }
-    pub fn get_player_actor_mut(&self) -> &mut ActorController { ptr_as_mut(self._player_actor) }
     pub fn spawn_actors(&mut self) {
         let game_client = ptr_as_ref(self._game_client);
+        self.reset_player_respawn();
         let level_data = game_client.get_project_scene_manager().get_level_data();
         for spawn_point_type in level_data._spawn_point_datas.iter() {
             match spawn_point_type {
-                SpawnPointType::Player(spawn_point_data) => self.create_actor(game_client, spawn_point_data, true),
-                SpawnPointType::NonPlayer(spawn_point_data) => self.create_actor(game_client, spawn_point_data, false),
+                // the remote players take the player spawn points of the dedicated server
+                SpawnPointType::Player(_) if game_client.is_dedicated_server() => (),
+                SpawnPointType::Player(spawn_point_data) => { self.create_actor(game_client, spawn_point_data, true); },
+                SpawnPointType::NonPlayer(spawn_point_data) => { self.create_actor(game_client, spawn_point_data, false); },
                 _ => (),
             }
         }
@@ -97,11 +376,130 @@ impl ActorManager {
 
     pub fn update_actor_manager(&mut self, delta_time: f32) {
         let game_client = ptr_as_ref(self._game_client);
-        for actor_ref in self._actors.values() {
-            let actor = ptr_as_mut(actor_ref.as_ref());
-            if actor._is_player_actor {
+        let network_manager = game_client.get_network_manager();
+        for actor in self._actors.values_mut() {
+            // the snapshots move the remote actors, the inputs of the clients move their ships on the server
+            if actor.is_remote_actor() || network_manager.is_remote_client_actor(actor.get_actor_handle()) {
+                continue;
+            }
+            // the other ships are simulated while they carry out a command of a script
+            if actor._is_player_actor || actor.get_ship().is_harvester() || actor.has_command() {
                 actor.update_actor_controller(game_client, delta_time);
+            } else {
+                // the idle ships stay still, their energy, shields and weapon heat keep recovering
+                let ship = actor.get_ship_mut();
+                ship.update_ship_systems(delta_time);
+                ship.update_ship_transform(delta_time);
             }
         }
+        self.update_actor_collisions(game_client);
+        self.update_wrecks(game_client, delta_time);
+        self.update_player_respawn(game_client, delta_time);
+    }
+
+    pub fn update_wrecks(&mut self, game_client: &GameClient, delta_time: f32) {
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        for wreck in self._wrecks.iter_mut() {
+            wreck.update_wreck(delta_time, project_scene_manager);
+            if wreck.is_expired() {
+                wreck.remove_wreck(project_scene_manager);
+            }
+        }
+        self._wrecks.retain(|wreck| false == wreck.is_expired());
+    }
+
+    pub fn update_actor_collisions(&mut self, game_client: &GameClient) {
+        let project_scene_manager = game_client.get_project_scene_manager();
+
+        // static obstacles, the static colliders of the scene and the standing buildings
+        let mut obstacles: Vec<(Vector3<f32>, Vector3<f32>)> = Vec::new();
+        for (collider_min, collider_max) in project_scene_manager.get_static_colliders().iter() {
+            obstacles.push((collider_min.clone_owned(), collider_max.clone_owned()));
+        }
+        for building in game_client.get_building_manager().get_buildings().values() {
+            if false == building.is_destroyed() {
+                obstacles.push((building.get_collision_min().clone_owned(), building.get_collision_max().clone_owned()));
+            }
+        }
+
+        // broadphase, sweep and prune along the x axis
+        let mut actors: Vec<*const ActorController> = self._actors.values().map(|actor| actor as *const ActorController).collect();
+        actors.sort_by(|a, b| {
+            let bound_box_a = ptr_as_ref(*a).get_bound_box();
+            let bound_box_b = ptr_as_ref(*b).get_bound_box();
+            (bound_box_a._center.x - bound_box_a._radius).partial_cmp(&(bound_box_b._center.x - bound_box_b._radius)).unwrap_or(Ordering::Equal)
+        });
+
+        // (victim, attacker name), the attacker may be destroyed together.
+        let mut destroyed_actors: Vec<(ActorHandle, String)> = Vec::new();
+        for (index, actor_a) in actors.iter().enumerate() {
+            let actor_a = ptr_as_mut(*actor_a);
+            for actor_b in actors[(index + 1)..].iter() {
+                let actor_b = ptr_as_mut(*actor_b);
+                let bound_box_a = actor_a.get_bound_box();
+                let bound_box_b = actor_b.get_bound_box();
+                if (bound_box_a._center.x + bound_box_a._radius) < (bound_box_b._center.x - bound_box_b._radius) {
+                    break;
+                }
+
+                // the server resolves the collisions of the remote actors
+                if actor_a.is_remote_actor() || actor_b.is_remote_actor() {
+                    continue;
+                }
+
+                // narrowphase, bounding spheres
+                let to_actor_a: Vector3<f32> = &bound_box_a._center - &bound_box_b._center;
+                let distance = to_actor_a.norm();
+                let penetration = bound_box_a._radius + bound_box_b._radius - distance;
+                if penetration <= 0.0 {
+                    continue;
+                }
+                let normal: Vector3<f32> = if 0.0 < distance { to_actor_a / distance } else { Vector3::y() };
+                let impact_speed = resolve_ship_collision(actor_a.get_ship_mut(), Some(actor_b.get_ship_mut()), &normal, penetration);
+
+                // ramming damage
+                if RAMMING_DAMAGE_MIN_SPEED < impact_speed {
+                    let ramming_speed = impact_speed - RAMMING_DAMAGE_MIN_SPEED;
+                    let damage_a = ramming_speed * actor_b.get_ship().get_ship_data()._ramming_damage;
+                    let damage_b = ramming_speed * actor_a.get_ship().get_ship_data()._ramming_damage;
+                    if 0.0 < damage_a && false == actor_a.get_ship().is_destroyed() {
+                        actor_a.get_ship_mut().apply_damage(damage_a, damage_a);
+                        if actor_a.get_ship().is_destroyed() {
+                            destroyed_actors.push((actor_a.get_actor_handle(), actor_b.get_actor_name()));
+                        }
+                    }
+                    if 0.0 < damage_b && false == actor_b.get_ship().is_destroyed() {
+                        actor_b.get_ship_mut().apply_damage(damage_b, damage_b);
+                        if actor_b.get_ship().is_destroyed() {
+                            destroyed_actors.push((actor_b.get_actor_handle(), actor_a.get_actor_name()));
+                        }
+                    }
+                }
+            }
+
+            // static obstacles, bounding sphere against the bounding box
+            if actor_a.is_remote_actor() {
+                continue;
+            }
+            for (obstacle_min, obstacle_max) in obstacles.iter() {
+                let bound_box = actor_a.get_bound_box();
+                let closest_point: Vector3<f32> = bound_box._center.sup(obstacle_min).inf(obstacle_max);
+                let to_actor: Vector3<f32> = &bound_box._center - &closest_point;
+                let distance = to_actor.norm();
+                if bound_box._radius <= distance {
+                    continue;
+                }
+                let (normal, penetration) = if 0.0 < distance {
+                    (to_actor / distance, bound_box._radius - distance)
+                } else {
+                    (Vector3::y(), bound_box._radius + obstacle_max.y - bound_box._center.y)
+                };
+                resolve_ship_collision(actor_a.get_ship_mut(), None, &normal, penetration);
+            }
+        }
+
+        for (victim, attacker_name) in destroyed_actors.iter() {
+            self.destroy_actor(*victim, attacker_name, "ramming");
+        }
     }
 }
\ No newline at end of file
@@ -1,4 +1,3 @@
-use std::rc::Rc;
 use nalgebra::Vector3;
 
 use rust_engine_3d::application::scene_manager::ProjectSceneManagerBase;
@@ -8,11 +7,15 @@ use rust_engine_3d::utilities::bounding_box::BoundingBox;
 use rust_engine_3d::utilities::math;
 use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_mut, ptr_as_ref};
 use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::economy::harvester::HarvestState;
 use crate::game_module::game_client::GameClient;
 use crate::game_module::game_constants::{CHECK_TARGET_DISTANCE_MAX};
+use crate::game_module::handle::Handle;
 use crate::game_module::ship::ship::{ShipInstance, ShipData};
 use crate::game_module::ship::ship_controller::{ ShipController };
 
+pub type ActorHandle = Handle<ActorController>;
+
 pub struct ActorData {
 }
 
@@ -27,49 +30,85 @@ pub enum ActorControllerState {
 
 // ActorController
 pub struct ActorController {
-    pub _id: u64,
+    pub _actor_handle: ActorHandle,
     pub _actor_data: ActorData,
     pub _ship: ShipInstance,
     pub _actor_controller_state: ActorControllerState,
     pub _target_position: Vector3<f32>,
     pub _is_player_actor: bool,
+    pub _is_remote_actor: bool,
+    pub _is_controlled_by_player: bool,
     pub _command_move: bool,
     pub _command_rotate: bool,
-    pub _command_attack: bool
+    pub _command_attack: bool,
+    pub _harvest_state: HarvestState,
 }
 
 impl ActorController {
     pub fn create_actor_controller(
-        id: u64,
         ship_data: &RcRefCell<ShipData>,
-        render_object: &RcRefCell<RenderObjectData>,
+        position: &Vector3<f32>,
+        rotation: &Vector3<f32>,
+        render_object: Option<RcRefCell<RenderObjectData>>,
         is_player_actor: bool
-    ) -> Rc<ActorController> {
-        Rc::new(ActorController {
-            _id: id,
+    ) -> Box<ActorController> {
+        Box::new(ActorController {
+            _actor_handle: ActorHandle::new(0, 0),
             _actor_data: ActorData {},
-            _ship: ShipInstance::create_ship_instance(ship_data, render_object),
+            _ship: ShipInstance::create_ship_instance(ship_data, position, rotation, render_object),
             _actor_controller_state: ActorControllerState::None,
             _target_position: Vector3::zeros(),
             _is_player_actor: is_player_actor,
+            _is_remote_actor: false,
+            _is_controlled_by_player: false,
             _command_move: false,
             _command_rotate: false,
             _command_attack: false,
+            _harvest_state: HarvestState::Idle,
         })
     }
 
-    pub fn initialize_actor(&mut self, project_scene_manager: &mut ProjectSceneManager) {
-        self._ship.initialize_ship_instance(self, project_scene_manager);
+    // the handle is given by the actor manager when the actor is registered
+    pub fn initialize_actor(&mut self, actor_handle: ActorHandle, project_scene_manager: &mut ProjectSceneManager) {
+        self._actor_handle = actor_handle;
+        self._ship.initialize_ship_instance(actor_handle, project_scene_manager);
     }
     pub fn remove_actor(&mut self, project_scene_manager: &mut ProjectSceneManager) {
         self._ship.remove_ship_instance(project_scene_manager);
     }
+    pub fn get_actor_handle(&self) -> ActorHandle {
+        self._actor_handle
+    }
     pub fn get_actor_id(&self) -> u64 {
-        self._id
+        self._actor_handle.to_id()
     }
     pub fn is_player_actor(&self) -> bool {
         self._is_player_actor
     }
+    // the remote actor is moved by the network snapshots instead of the simulation
+    pub fn is_remote_actor(&self) -> bool {
+        self._is_remote_actor
+    }
+    pub fn set_remote_actor(&mut self, is_remote_actor: bool) {
+        self._is_remote_actor = is_remote_actor;
+    }
+    // the ship of the local player, is_player_actor is only the team of the actor
+    pub fn is_controlled_by_player(&self) -> bool {
+        self._is_controlled_by_player
+    }
+    pub fn set_controlled_by_player(&mut self, is_controlled_by_player: bool) {
+        self._is_controlled_by_player = is_controlled_by_player;
+    }
+    pub fn get_actor_name(&self) -> String {
+        let ship_name = &self._ship.get_ship_data()._ship_name;
+        if self._is_controlled_by_player {
+            format!("Player({})", ship_name)
+        } else if self._is_player_actor {
+            format!("Ally_{}({})", self._actor_handle.to_id(), ship_name)
+        } else {
+            format!("Enemy_{}({})", self._actor_handle.to_id(), ship_name)
+        }
+    }
     pub fn get_actor_data(&self) -> &ActorData {
         &self._actor_data
     }
@@ -95,6 +134,9 @@ impl ActorController {
     pub fn can_manual_controll(&self) -> bool {
         ActorControllerState::None == self._actor_controller_state
     }
+    pub fn has_command(&self) -> bool {
+        ActorControllerState::None != self._actor_controller_state
+    }
 
     pub fn manual_actor_attack(&mut self, game_client: &GameClient) {
         let project_scene_manager = game_client.get_project_scene_manager();
@@ -243,7 +285,8 @@ impl ActorController {
                 }
             }
 
-            if self._command_attack && false == self._command_rotate {
+            // wait for the weapons to reload or cool down
+            if self._command_attack && false == self._command_rotate && self._ship.can_fire() {
                 // fire
                 let fire_start = self.get_transform().get_position().clone_owned();
                 let fire_dir = (&self._target_position - ship_controller.get_position()).normalize();
@@ -261,7 +304,7 @@ impl ActorController {
             self.update_command_actor_move(delta_time);
         } else if ActorControllerState::Attack == self._actor_controller_state {
             self.update_command_actor_attack(delta_time, game_client);
-        } else if false == self._is_player_actor {
+        } else if false == self._is_player_actor && false == self._ship.is_harvester() {
             let ship_controller = ptr_as_mut(&self.get_ship()._controller);
             ship_controller.set_velocity_yaw(1.0);
             ship_controller.acceleration_forward(1.0);
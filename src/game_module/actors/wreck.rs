@@ -0,0 +1,175 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::effect::effect_data::EffectCreateInfo;
+use rust_engine_3d::renderer::render_object::{RenderObjectData, RenderObjectCreateInfo};
+use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_mut};
+use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::game_constants::{
+    GRAVITY,
+    DEBRIS_ANGULAR_SPEED,
+    DEBRIS_RESTITUTION,
+    DEBRIS_SETTLE_SPEED,
+};
+use crate::game_module::ship::ship::ShipData;
+
+pub struct Debris {
+    pub _render_object: RcRefCell<RenderObjectData>,
+    pub _velocity: Vector3<f32>,
+    pub _angular_velocity: Vector3<f32>,
+    pub _is_settled: bool,
+}
+
+// the burning remains of a destroyed ship, the debris are removed together with the wreck.
+pub struct Wreck {
+    pub _ship_data: RcRefCell<ShipData>,
+    pub _position: Vector3<f32>,
+    pub _render_object: Option<RcRefCell<RenderObjectData>>,
+    pub _debris: Vec<Debris>,
+    pub _elapsed_time: f32,
+    pub _next_effect_time: f32,
+}
+
+impl Debris {
+    pub fn update_debris(&mut self, delta_time: f32, project_scene_manager: &ProjectSceneManager) {
+        if self._is_settled {
+            return;
+        }
+
+        let transform = &mut ptr_as_mut(self._render_object.as_ptr())._transform_object;
+        self._velocity.y -= GRAVITY * delta_time;
+        transform.move_position(&(&self._velocity * delta_time));
+        transform.rotation_pitch(self._angular_velocity.x * delta_time);
+        transform.rotation_yaw(self._angular_velocity.y * delta_time);
+        transform.rotation_roll(self._angular_velocity.z * delta_time);
+
+        // bounce on the height map until the debris settles
+        let mut position = transform.get_position().clone_owned();
+        let height = project_scene_manager.get_height_bilinear(&position, 0);
+        if position.y <= height {
+            position.y = height;
+            transform.set_position(&position);
+            if self._velocity.y.abs() < DEBRIS_SETTLE_SPEED {
+                self._velocity = Vector3::zeros();
+                self._angular_velocity = Vector3::zeros();
+                self._is_settled = true;
+            } else {
+                self._velocity.x *= DEBRIS_RESTITUTION;
+                self._velocity.y *= -DEBRIS_RESTITUTION;
+                self._velocity.z *= DEBRIS_RESTITUTION;
+                self._angular_velocity *= DEBRIS_RESTITUTION;
+            }
+        }
+        transform.update_transform_object();
+    }
+}
+
+impl Wreck {
+    pub fn create_wreck(
+        ship_data: &RcRefCell<ShipData>,
+        position: &Vector3<f32>,
+        rotation: &Vector3<f32>,
+        velocity: &Vector3<f32>,
+        project_scene_manager: &mut ProjectSceneManager
+    ) -> Wreck {
+        let mut wreck = Wreck {
+            _ship_data: ship_data.clone(),
+            _position: position.clone_owned(),
+            _render_object: None,
+            _debris: Vec::new(),
+            _elapsed_time: 0.0,
+            _next_effect_time: 0.0,
+        };
+        let ship_data = ship_data.borrow();
+
+        // destruction effects
+        for effect_data_name in ship_data._destroy_effects.iter() {
+            let effect_create_info = EffectCreateInfo {
+                _effect_position: position.clone_owned(),
+                _effect_data_name: effect_data_name.clone(),
+                ..Default::default()
+            };
+            project_scene_manager.add_effect(effect_data_name, &effect_create_info);
+        }
+
+        // the wreck lies on the ground
+        if false == ship_data._wreck_model_data_name.is_empty() {
+            wreck._position.y = project_scene_manager.get_height_bilinear(position, 0);
+            let render_object_create_info = RenderObjectCreateInfo {
+                _model_data_name: ship_data._wreck_model_data_name.clone(),
+                _position: wreck._position.clone_owned(),
+                _rotation: rotation.clone_owned(),
+                ..Default::default()
+            };
+            wreck._render_object = project_scene_manager.add_static_render_object("Wreck", &render_object_create_info);
+        }
+
+        // debris fly out in random directions over the upper hemisphere, they are only visual and skipped without the renderer
+        let debris_model_count = ship_data._debris_model_data_names.len();
+        if 0 < debris_model_count {
+            for i in 0..ship_data._debris_count {
+                let yaw = rand::random::<f32>() * std::f32::consts::PI * 2.0;
+                let pitch = rand::random::<f32>() * std::f32::consts::PI * 0.5;
+                let direction = Vector3::new(yaw.cos() * pitch.cos(), pitch.sin(), yaw.sin() * pitch.cos());
+                let speed = ship_data._debris_speed * (0.5 + rand::random::<f32>() * 0.5);
+                let render_object_create_info = RenderObjectCreateInfo {
+                    _model_data_name: ship_data._debris_model_data_names[i as usize % debris_model_count].clone(),
+                    _position: position.clone_owned(),
+                    _rotation: rotation.clone_owned(),
+                    ..Default::default()
+                };
+                if let Some(render_object) = project_scene_manager.add_static_render_object("Debris", &render_object_create_info) {
+                    wreck._debris.push(Debris {
+                        _render_object: render_object,
+                        _velocity: velocity + direction * speed,
+                        _angular_velocity: Vector3::new(
+                            rand::random::<f32>() * 2.0 - 1.0,
+                            rand::random::<f32>() * 2.0 - 1.0,
+                            rand::random::<f32>() * 2.0 - 1.0
+                        ) * DEBRIS_ANGULAR_SPEED,
+                        _is_settled: false,
+                    });
+                }
+            }
+        }
+        wreck
+    }
+
+    pub fn remove_wreck(&mut self, project_scene_manager: &mut ProjectSceneManager) {
+        if let Some(render_object) = &self._render_object {
+            project_scene_manager.remove_static_render_object(&render_object.borrow()._render_object_name);
+        }
+        for debris in self._debris.iter() {
+            project_scene_manager.remove_static_render_object(&debris._render_object.borrow()._render_object_name);
+        }
+        self._render_object = None;
+        self._debris.clear();
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self._ship_data.borrow()._wreck_life_time <= self._elapsed_time
+    }
+
+    pub fn update_wreck(&mut self, delta_time: f32, project_scene_manager: &mut ProjectSceneManager) {
+        for debris in self._debris.iter_mut() {
+            debris.update_debris(delta_time, project_scene_manager);
+        }
+
+        // the burning effects are spawned again and again while the wreck remains
+        let ship_data = self._ship_data.borrow();
+        if 0.0 < ship_data._wreck_effect_interval && false == ship_data._wreck_effects.is_empty() {
+            self._next_effect_time -= delta_time;
+            if self._next_effect_time <= 0.0 {
+                self._next_effect_time += ship_data._wreck_effect_interval;
+                for effect_data_name in ship_data._wreck_effects.iter() {
+                    let effect_create_info = EffectCreateInfo {
+                        _effect_position: self._position.clone_owned(),
+                        _effect_data_name: effect_data_name.clone(),
+                        ..Default::default()
+                    };
+                    project_scene_manager.add_effect(effect_data_name, &effect_create_info);
+                }
+            }
+        }
+        self._elapsed_time += delta_time;
+    }
+}
@@ -0,0 +1,243 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::effect::effect_data::EffectCreateInfo;
+use rust_engine_3d::renderer::render_object::RenderObjectCreateInfo;
+use rust_engine_3d::utilities::system::ptr_as_ref;
+use crate::game_module::buildings::building::{BuildingHandle, BuildingInstance};
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::{CAMERA_SHAKE_SHIP_DESTROY_RADIUS, CAMERA_SHAKE_SHIP_DESTROY_TRAUMA};
+use crate::game_module::handle::HandleMap;
+use crate::game_module::level_datas::building_placement::BuildingPlacementData;
+use crate::game_module::level_datas::spawn_point::ShipSpawnPointData;
+
+
+pub struct BuildingManager {
+    pub _game_client: *const GameClient,
+    pub _buildings: HandleMap<BuildingInstance>,
+}
+
+impl BuildingManager {
+    pub fn create_building_manager() -> Box<BuildingManager> {
+        Box::new(BuildingManager {
+            _game_client: std::ptr::null(),
+            _buildings: HandleMap::new(),
+        })
+    }
+
+    pub fn initialize_building_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+    pub fn destroy_building_manager(&mut self) {
+        self._buildings.clear();
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_buildings(&self) -> &HandleMap<BuildingInstance> { &self._buildings }
+    // None when the building is removed
+    pub fn get_building(&self, building_handle: BuildingHandle) -> Option<&BuildingInstance> { self._buildings.get(building_handle) }
+    pub fn get_building_mut(&mut self, building_handle: BuildingHandle) -> Option<&mut BuildingInstance> { self._buildings.get_mut(building_handle) }
+
+    pub fn create_building(&mut self, game_client: &GameClient, building_placement_data: &BuildingPlacementData) {
+        let project_resources = game_client.get_project_resources();
+        if false == project_resources.has_building_data(&building_placement_data._building_data_name) {
+            log::error!("create_building: not found building data {}", building_placement_data._building_data_name);
+            return;
+        }
+
+        // the building stands on the terrain
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let mut position = building_placement_data._position.clone_owned();
+        position.y = project_scene_manager.get_height_bilinear(&position, 0);
+
+        let building_data = project_resources.get_building_data(&building_placement_data._building_data_name);
+        let render_object_create_info = RenderObjectCreateInfo {
+            _model_data_name: building_data.borrow()._model_data_name.clone(),
+            _position: position.clone_owned(),
+            _rotation: building_placement_data._rotation.clone_owned(),
+            _scale: building_placement_data._scale.clone_owned(),
+            ..Default::default()
+        };
+        let render_object = project_scene_manager.add_static_render_object("Building", &render_object_create_info);
+        let building = BuildingInstance::create_building_instance(
+            &building_placement_data._building_data_name,
+            building_data,
+            &position,
+            &building_placement_data._rotation,
+            &building_placement_data._scale,
+            render_object,
+            building_placement_data._is_player_building
+        );
+        let building_handle = self._buildings.insert(building);
+        self._buildings.get_mut(building_handle).unwrap().initialize_building(building_handle);
+    }
+
+    pub fn spawn_buildings(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let level_data = game_client.get_project_scene_manager().get_level_data();
+        for building_placement_data in level_data._building_placement_datas.iter() {
+            self.create_building(game_client, building_placement_data);
+        }
+        game_client.get_game_ui_manager_mut().update_objective_markers();
+    }
+
+    // returns the building of the team hit by the position, the buildings of the other team are skipped
+    pub fn find_building_at_mut(&mut self, position: &Vector3<f32>, is_player_team: bool) -> Option<(BuildingHandle, &mut BuildingInstance)> {
+        self._buildings.iter_mut().find(|(_, building)| {
+            is_player_team == building.is_player_building() && false == building.is_destroyed() && building.contains_point(position)
+        })
+    }
+
+    pub fn destroy_building(&mut self, building_handle: BuildingHandle, attacker_name: &str, weapon_name: &str) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let building = match self._buildings.get_mut(building_handle) {
+            Some(building) => building,
+            None => return,
+        };
+        let building_data = building.get_building_data();
+        let building_center = building.get_center();
+
+        // effects
+        for effect_data_name in building_data._destroy_effects.iter() {
+            let effect_create_info = EffectCreateInfo {
+                _effect_position: building_center.clone_owned(),
+                _effect_data_name: effect_data_name.clone(),
+                ..Default::default()
+            };
+            project_scene_manager.add_effect(effect_data_name, &effect_create_info);
+        }
+        if false == building_data._destroy_sound_bank.is_empty() {
+            game_client.play_audio_bank(&building_data._destroy_sound_bank);
+        }
+        game_client.get_game_controller_mut().add_camera_shake_at(CAMERA_SHAKE_SHIP_DESTROY_TRAUMA, &building_center, CAMERA_SHAKE_SHIP_DESTROY_RADIUS);
+
+        // the production in progress is lost
+        building._production_queue.clear();
+        building._production_time = 0.0;
+
+        // replace the model with the destroyed model, the rubble is no longer an obstacle.
+        if let Some(render_object) = building.get_render_object() {
+            project_scene_manager.remove_static_render_object(&render_object.borrow()._render_object_name);
+        }
+        let mut destroyed_render_object = None;
+        if false == building_data._destroyed_model_data_name.is_empty() {
+            let transform = building.get_transform();
+            let render_object_create_info = RenderObjectCreateInfo {
+                _model_data_name: building_data._destroyed_model_data_name.clone(),
+                _position: transform.get_position().clone_owned(),
+                _rotation: transform.get_rotation().clone_owned(),
+                _scale: transform.get_scale().clone_owned(),
+                ..Default::default()
+            };
+            destroyed_render_object = project_scene_manager.add_static_render_object("Building", &render_object_create_info);
+        }
+        building.set_render_object(destroyed_render_object);
+
+        // objective
+        let game_ui_manager = game_client.get_game_ui_manager_mut();
+        if building.is_objective() {
+            game_ui_manager.add_objective_event_log(&format!("{} destroyed the objective {}", attacker_name, building.get_building_name()));
+            game_ui_manager.update_objective_markers();
+        } else {
+            game_ui_manager.add_kill_event_log(attacker_name, weapon_name, &building.get_building_name());
+        }
+    }
+
+    // returns the factory of the team at the position
+    pub fn find_factory_at(&self, position: &Vector3<f32>, is_player_team: bool) -> Option<BuildingHandle> {
+        for (building_handle, building) in self._buildings.iter() {
+            if building.is_factory() && is_player_team == building.is_player_building() && false == building.is_destroyed() && building.contains_point_xz(position) {
+                return Some(building_handle);
+            }
+        }
+        None
+    }
+
+    fn get_production_factory_mut(&mut self, building_handle: BuildingHandle) -> Option<&mut BuildingInstance> {
+        match self._buildings.get_mut(building_handle) {
+            Some(building) if building.is_factory() && false == building.is_destroyed() => Some(building),
+            _ => None,
+        }
+    }
+
+    // the cost is paid when the ship is queued, returns false when the team can not afford it or the queue is full.
+    pub fn queue_production(&mut self, building_handle: BuildingHandle, ship_data_name: &str) -> bool {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_resources = game_client.get_project_resources();
+        let building = match self.get_production_factory_mut(building_handle) {
+            Some(building) => building,
+            None => return false,
+        };
+        let building_data = building.get_building_data();
+        if false == building_data._production_ship_data_names.iter().any(|name| name == ship_data_name) ||
+            building_data._production_queue_size <= building._production_queue.len() ||
+            false == project_resources.has_ship_data(ship_data_name) {
+            return false;
+        }
+
+        let cost = project_resources.get_ship_data(ship_data_name).borrow()._cost;
+        if false == game_client.get_economy_manager_mut().spend_team_resources(building.is_player_building(), cost) {
+            return false;
+        }
+        building._production_queue.push(ship_data_name.to_string());
+        true
+    }
+
+    // the cost is refunded
+    pub fn cancel_production(&mut self, building_handle: BuildingHandle, queue_index: usize) {
+        let game_client = ptr_as_ref(self._game_client);
+        let building = match self.get_production_factory_mut(building_handle) {
+            Some(building) => building,
+            None => return,
+        };
+        if building._production_queue.len() <= queue_index {
+            return;
+        }
+
+        let ship_data_name = building._production_queue.remove(queue_index);
+        if 0 == queue_index {
+            building._production_time = 0.0;
+        }
+        let cost = game_client.get_project_resources().get_ship_data(&ship_data_name).borrow()._cost;
+        game_client.get_economy_manager_mut().add_team_resources(building.is_player_building(), cost);
+    }
+
+    pub fn set_rally_point(&mut self, building_handle: BuildingHandle, rally_point: &Vector3<f32>) {
+        if let Some(building) = self.get_production_factory_mut(building_handle) {
+            building.set_rally_point(rally_point);
+        }
+    }
+
+    pub fn update_building_manager(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_resources = game_client.get_project_resources();
+        let actor_manager = game_client.get_actor_manager_mut();
+        for building in self._buildings.values_mut() {
+            if building.is_destroyed() || building._production_queue.is_empty() {
+                continue;
+            }
+
+            let ship_data_name = building._production_queue[0].clone();
+            let build_time = project_resources.get_ship_data(&ship_data_name).borrow()._build_time;
+            building._production_time += delta_time;
+            if build_time <= building._production_time {
+                building._production_queue.remove(0);
+                building._production_time = 0.0;
+
+                // the finished ship moves to the rally point
+                let spawn_point_data = ShipSpawnPointData {
+                    _ship_data_name: ship_data_name,
+                    _position: building.get_production_spawn_position(),
+                    _rotation: building.get_transform().get_rotation().clone_owned(),
+                };
+                let actor_handle = actor_manager.create_actor(game_client, &spawn_point_data, building.is_player_building());
+                if let Some(actor) = actor_manager.get_actor_mut(actor_handle) {
+                    actor.set_command_actor_move(building.get_rally_point());
+                }
+            }
+        }
+    }
+
+    pub fn get_remaining_objective_count(&self) -> usize {
+        self._buildings.values().filter(|building| building.is_objective() && false == building.is_destroyed()).count()
+    }
+}
@@ -0,0 +1,154 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+use rust_engine_3d::renderer::render_object::RenderObjectData;
+use rust_engine_3d::renderer::transform_object::TransformObjectData;
+use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_ref};
+use crate::game_module::handle::Handle;
+
+pub type BuildingHandle = Handle<BuildingInstance>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BuildingData {
+    pub _model_data_name: String,
+    pub _destroyed_model_data_name: String,
+    pub _max_hull: f32,
+    pub _hull_armor: f32,
+    // half extents of the collision box in the local space, the box is scaled with the building.
+    pub _collision_half_extents: Vector3<f32>,
+    pub _destroy_effects: Vec<String>,
+    pub _destroy_sound_bank: String,
+    pub _is_objective: bool,
+    // the ships produced by the factory, empty is not a factory
+    pub _production_ship_data_names: Vec<String>,
+    pub _production_queue_size: usize,
+    // offset of the produced ships from the building position
+    pub _production_spawn_offset: Vector3<f32>,
+}
+
+impl Default for BuildingData {
+    fn default() -> BuildingData {
+        BuildingData {
+            _model_data_name: "".to_string(),
+            _destroyed_model_data_name: "".to_string(),
+            _max_hull: 200.0,
+            _hull_armor: 0.0,
+            _collision_half_extents: Vector3::new(0.5, 1.0, 0.5),
+            _destroy_effects: Vec::new(),
+            _destroy_sound_bank: "".to_string(),
+            _is_objective: false,
+            _production_ship_data_names: Vec::new(),
+            _production_queue_size: 5,
+            _production_spawn_offset: Vector3::new(0.0, 0.0, 10.0),
+        }
+    }
+}
+
+pub struct BuildingInstance {
+    pub _building_handle: BuildingHandle,
+    pub _building_data_name: String,
+    pub _building_data: RcRefCell<BuildingData>,
+    pub _transform_object: TransformObjectData,
+    // None on the dedicated server
+    pub _render_object: Option<RcRefCell<RenderObjectData>>,
+    pub _hull: f32,
+    pub _collision_min: Vector3<f32>,
+    pub _collision_max: Vector3<f32>,
+    pub _is_player_building: bool,
+    pub _production_queue: Vec<String>,
+    pub _production_time: f32,
+    pub _rally_point: Vector3<f32>,
+}
+
+impl BuildingInstance {
+    pub fn create_building_instance(
+        building_data_name: &str,
+        building_data: &RcRefCell<BuildingData>,
+        position: &Vector3<f32>,
+        rotation: &Vector3<f32>,
+        scale: &Vector3<f32>,
+        render_object: Option<RcRefCell<RenderObjectData>>,
+        is_player_building: bool
+    ) -> Box<BuildingInstance> {
+        let mut transform_object = TransformObjectData::new_transform_object_data();
+        transform_object.set_position(position);
+        transform_object.set_rotation(rotation);
+        transform_object.set_scale(scale);
+        transform_object.update_transform_object();
+        let rally_point: Vector3<f32> = position + &building_data.borrow()._production_spawn_offset * 2.0;
+        let mut building = BuildingInstance {
+            _building_handle: BuildingHandle::new(0, 0),
+            _building_data_name: building_data_name.to_string(),
+            _building_data: building_data.clone(),
+            _transform_object: transform_object,
+            _render_object: render_object,
+            _hull: building_data.borrow()._max_hull,
+            _collision_min: Vector3::zeros(),
+            _collision_max: Vector3::zeros(),
+            _is_player_building: is_player_building,
+            _production_queue: Vec::new(),
+            _production_time: 0.0,
+            _rally_point: rally_point,
+        };
+        building.update_collision_bounds();
+        Box::new(building)
+    }
+
+    // the handle is given by the building manager when the building is registered
+    pub fn initialize_building(&mut self, building_handle: BuildingHandle) {
+        self._building_handle = building_handle;
+    }
+
+    pub fn get_building_handle(&self) -> BuildingHandle { self._building_handle }
+    pub fn get_building_id(&self) -> u64 { self._building_handle.to_id() }
+    pub fn get_building_data(&self) -> &BuildingData { ptr_as_ref(self._building_data.as_ptr()) }
+    pub fn get_building_name(&self) -> String { format!("{}_{}", self._building_data_name, self.get_building_id()) }
+    pub fn get_render_object(&self) -> Option<&RcRefCell<RenderObjectData>> { self._render_object.as_ref() }
+    pub fn get_transform(&self) -> &TransformObjectData { &self._transform_object }
+    pub fn get_position(&self) -> &Vector3<f32> { self._transform_object.get_position() }
+    pub fn get_center(&self) -> Vector3<f32> { (&self._collision_min + &self._collision_max) * 0.5 }
+    pub fn get_collision_min(&self) -> &Vector3<f32> { &self._collision_min }
+    pub fn get_collision_max(&self) -> &Vector3<f32> { &self._collision_max }
+    pub fn get_hull_point(&self) -> f32 { self._hull }
+    pub fn get_max_hull_point(&self) -> f32 { self.get_building_data()._max_hull }
+    pub fn is_objective(&self) -> bool { self.get_building_data()._is_objective }
+    pub fn is_destroyed(&self) -> bool { self._hull <= 0.0 }
+    pub fn is_player_building(&self) -> bool { self._is_player_building }
+    pub fn is_factory(&self) -> bool { false == self.get_building_data()._production_ship_data_names.is_empty() }
+    pub fn get_production_queue(&self) -> &Vec<String> { &self._production_queue }
+    pub fn get_production_time(&self) -> f32 { self._production_time }
+    pub fn get_rally_point(&self) -> &Vector3<f32> { &self._rally_point }
+    pub fn set_rally_point(&mut self, rally_point: &Vector3<f32>) { self._rally_point.clone_from(rally_point); }
+    pub fn get_production_spawn_position(&self) -> Vector3<f32> { self.get_position() + &self.get_building_data()._production_spawn_offset }
+
+    pub fn update_collision_bounds(&mut self) {
+        let position = self._transform_object.get_position();
+        let scale = self._transform_object.get_scale();
+        let half_extents: Vector3<f32> = self.get_building_data()._collision_half_extents.component_mul(scale);
+        self._collision_min = position - &half_extents;
+        self._collision_max = position + &half_extents;
+    }
+
+    pub fn set_render_object(&mut self, render_object: Option<RcRefCell<RenderObjectData>>) {
+        self._render_object = render_object;
+    }
+
+    pub fn contains_point_xz(&self, position: &Vector3<f32>) -> bool {
+        self._collision_min.x <= position.x && position.x <= self._collision_max.x &&
+        self._collision_min.z <= position.z && position.z <= self._collision_max.z
+    }
+
+    pub fn contains_point(&self, position: &Vector3<f32>) -> bool {
+        self._collision_min.x <= position.x && position.x <= self._collision_max.x &&
+        self._collision_min.y <= position.y && position.y <= self._collision_max.y &&
+        self._collision_min.z <= position.z && position.z <= self._collision_max.z
+    }
+
+    // returns the applied hull damage
+    pub fn apply_damage(&mut self, hull_damage: f32) -> f32 {
+        let applied_hull_damage = self._hull.min(0f32.max(hull_damage - self.get_building_data()._hull_armor));
+        self._hull -= applied_hull_damage;
+        applied_hull_damage
+    }
+}
@@ -0,0 +1 @@
+pub mod building;
\ No newline at end of file
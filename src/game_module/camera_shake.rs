@@ -0,0 +1,97 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::renderer::camera::CameraObjectData;
+use crate::game_module::game_constants::{
+    CAMERA_SHAKE_FREQUENCY,
+    CAMERA_SHAKE_MAX_OFFSET,
+    CAMERA_SHAKE_MAX_ROTATION,
+    CAMERA_SHAKE_TRAUMA_DECAY,
+};
+
+pub struct CameraShake {
+    pub _trauma: f32,
+    pub _intensity: f32,
+    pub _elapsed_time: f32,
+    pub _shake_offset: Vector3<f32>,
+    pub _shake_rotation: Vector3<f32>,
+}
+
+// smooth pseudo noise in range [-1, 1]
+fn shake_noise(time: f32, seed: f32) -> f32 {
+    ((time + seed).sin() * 0.6 + (time * 2.3 + seed * 1.7).sin() * 0.3 + (time * 5.1 + seed * 3.1).sin() * 0.1).clamp(-1.0, 1.0)
+}
+
+impl CameraShake {
+    pub fn create_camera_shake() -> CameraShake {
+        CameraShake {
+            _trauma: 0.0,
+            _intensity: 1.0,
+            _elapsed_time: 0.0,
+            _shake_offset: Vector3::zeros(),
+            _shake_rotation: Vector3::zeros(),
+        }
+    }
+
+    pub fn get_intensity(&self) -> f32 { self._intensity }
+
+    // 0.0 disables the camera shake.
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self._intensity = intensity.max(0.0);
+    }
+
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self._trauma = 1.0f32.min(self._trauma + trauma.max(0.0));
+    }
+
+    // the trauma fades out linearly with the distance between the impulse and the listener
+    pub fn add_trauma_at(&mut self, trauma: f32, position: &Vector3<f32>, listener_position: &Vector3<f32>, radius: f32) {
+        if 0.0 < radius {
+            let distance = (position - listener_position).norm();
+            if distance < radius {
+                self.add_trauma(trauma * (1.0 - distance / radius));
+            }
+        }
+    }
+
+    // remove the shake applied at the last frame, the camera controls work on the unshaken transform.
+    pub fn restore_camera(&mut self, main_camera: &mut CameraObjectData) {
+        let position = main_camera._transform_object.get_position() - &self._shake_offset;
+        let rotation = main_camera._transform_object.get_rotation() - &self._shake_rotation;
+        main_camera._transform_object.set_position(&position);
+        main_camera._transform_object.set_rotation(&rotation);
+        self._shake_offset = Vector3::zeros();
+        self._shake_rotation = Vector3::zeros();
+    }
+
+    pub fn apply_camera_shake(&mut self, main_camera: &mut CameraObjectData) {
+        let shake = self._trauma * self._trauma * self._intensity;
+        if 0.0 < shake {
+            let time = self._elapsed_time * CAMERA_SHAKE_FREQUENCY;
+            self._shake_offset = Vector3::new(
+                shake_noise(time, 0.0),
+                shake_noise(time, 10.0),
+                shake_noise(time, 20.0)
+            ) * CAMERA_SHAKE_MAX_OFFSET * shake;
+            self._shake_rotation = Vector3::new(
+                shake_noise(time, 30.0),
+                shake_noise(time, 40.0),
+                shake_noise(time, 50.0)
+            ) * CAMERA_SHAKE_MAX_ROTATION * shake;
+
+            let position = main_camera._transform_object.get_position() + &self._shake_offset;
+            let rotation = main_camera._transform_object.get_rotation() + &self._shake_rotation;
+            main_camera._transform_object.set_position(&position);
+            main_camera._transform_object.set_rotation(&rotation);
+            main_camera._transform_object.update_transform_object();
+        }
+    }
+
+    pub fn update_camera_shake(&mut self, delta_time: f32) {
+        if 0.0 < self._trauma {
+            self._trauma = 0.0f32.max(self._trauma - CAMERA_SHAKE_TRAUMA_DECAY * delta_time);
+            self._elapsed_time += delta_time;
+        } else {
+            self._elapsed_time = 0.0;
+        }
+    }
+}
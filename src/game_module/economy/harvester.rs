@@ -0,0 +1,10 @@
+use crate::game_module::economy::resource_node::{ResourceNodeHandle, ResourceDepotHandle};
+
+// the harvest cycle of a harvester ship, the handles refer to the nodes and the depots of the economy manager.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HarvestState {
+    Idle,
+    MoveToNode(ResourceNodeHandle),
+    Gathering(ResourceNodeHandle),
+    ReturnToDepot(ResourceDepotHandle),
+}
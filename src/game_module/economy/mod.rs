@@ -0,0 +1,2 @@
+pub mod harvester;
+pub mod resource_node;
\ No newline at end of file
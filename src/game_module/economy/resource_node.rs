@@ -0,0 +1,77 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::renderer::render_object::RenderObjectData;
+use rust_engine_3d::utilities::system::RcRefCell;
+use crate::game_module::handle::Handle;
+use crate::game_module::level_datas::resource_placement::{ResourceNodePlacementData, ResourceDepotPlacementData};
+
+pub type ResourceNodeHandle = Handle<ResourceNodeInstance>;
+pub type ResourceDepotHandle = Handle<ResourceDepotInstance>;
+
+pub struct ResourceNodeInstance {
+    pub _position: Vector3<f32>,
+    pub _remaining_amount: f32,
+    pub _harvest_radius: f32,
+    // None on the dedicated server
+    pub _render_object: Option<RcRefCell<RenderObjectData>>,
+}
+
+pub struct ResourceDepotInstance {
+    pub _position: Vector3<f32>,
+    pub _is_player_depot: bool,
+    pub _unload_radius: f32,
+    // None on the dedicated server
+    pub _render_object: Option<RcRefCell<RenderObjectData>>,
+}
+
+impl ResourceNodeInstance {
+    pub fn create_resource_node_instance(
+        placement_data: &ResourceNodePlacementData,
+        render_object: Option<RcRefCell<RenderObjectData>>
+    ) -> Box<ResourceNodeInstance> {
+        Box::new(ResourceNodeInstance {
+            _position: placement_data._position.clone_owned(),
+            _remaining_amount: placement_data._resource_amount,
+            _harvest_radius: placement_data._harvest_radius,
+            _render_object: render_object,
+        })
+    }
+
+    pub fn get_position(&self) -> &Vector3<f32> { &self._position }
+    pub fn get_remaining_amount(&self) -> f32 { self._remaining_amount }
+    pub fn is_depleted(&self) -> bool { self._remaining_amount <= 0.0 }
+    pub fn is_in_range(&self, position: &Vector3<f32>, radius: f32) -> bool {
+        let to_node: Vector3<f32> = &self._position - position;
+        let range = self._harvest_radius + radius;
+        (to_node.x * to_node.x + to_node.z * to_node.z) <= (range * range)
+    }
+
+    // returns the harvested amount
+    pub fn harvest(&mut self, amount: f32) -> f32 {
+        let harvested_amount = self._remaining_amount.min(amount);
+        self._remaining_amount -= harvested_amount;
+        harvested_amount
+    }
+}
+
+impl ResourceDepotInstance {
+    pub fn create_resource_depot_instance(
+        placement_data: &ResourceDepotPlacementData,
+        render_object: Option<RcRefCell<RenderObjectData>>
+    ) -> Box<ResourceDepotInstance> {
+        Box::new(ResourceDepotInstance {
+            _position: placement_data._position.clone_owned(),
+            _is_player_depot: placement_data._is_player_depot,
+            _unload_radius: placement_data._unload_radius,
+            _render_object: render_object,
+        })
+    }
+
+    pub fn get_position(&self) -> &Vector3<f32> { &self._position }
+    pub fn is_player_depot(&self) -> bool { self._is_player_depot }
+    pub fn is_in_range(&self, position: &Vector3<f32>, radius: f32) -> bool {
+        let to_depot: Vector3<f32> = &self._position - position;
+        let range = self._unload_radius + radius;
+        (to_depot.x * to_depot.x + to_depot.z * to_depot.z) <= (range * range)
+    }
+}
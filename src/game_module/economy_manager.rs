@@ -0,0 +1,225 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::renderer::render_object::RenderObjectCreateInfo;
+use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use crate::game_module::actors::actor::ActorController;
+use crate::game_module::economy::harvester::HarvestState;
+use crate::game_module::economy::resource_node::{ResourceNodeHandle, ResourceNodeInstance, ResourceDepotHandle, ResourceDepotInstance};
+use crate::game_module::game_client::GameClient;
+use crate::game_module::handle::HandleMap;
+
+
+pub struct EconomyManager {
+    pub _game_client: *const GameClient,
+    pub _resource_nodes: HandleMap<ResourceNodeInstance>,
+    pub _resource_depots: HandleMap<ResourceDepotInstance>,
+    pub _player_resources: f32,
+    pub _enemy_resources: f32,
+}
+
+impl EconomyManager {
+    pub fn create_economy_manager() -> Box<EconomyManager> {
+        Box::new(EconomyManager {
+            _game_client: std::ptr::null(),
+            _resource_nodes: HandleMap::new(),
+            _resource_depots: HandleMap::new(),
+            _player_resources: 0.0,
+            _enemy_resources: 0.0,
+        })
+    }
+
+    pub fn initialize_economy_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+    pub fn destroy_economy_manager(&mut self) {
+        self._resource_nodes.clear();
+        self._resource_depots.clear();
+        self._player_resources = 0.0;
+        self._enemy_resources = 0.0;
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_resource_nodes(&self) -> &HandleMap<ResourceNodeInstance> { &self._resource_nodes }
+    pub fn get_resource_depots(&self) -> &HandleMap<ResourceDepotInstance> { &self._resource_depots }
+    // None when the node is depleted and removed
+    pub fn get_resource_node(&self, node_handle: ResourceNodeHandle) -> Option<&ResourceNodeInstance> { self._resource_nodes.get(node_handle) }
+    pub fn get_resource_depot(&self, depot_handle: ResourceDepotHandle) -> Option<&ResourceDepotInstance> { self._resource_depots.get(depot_handle) }
+    pub fn get_team_resources(&self, is_player_team: bool) -> f32 {
+        if is_player_team { self._player_resources } else { self._enemy_resources }
+    }
+    pub fn add_team_resources(&mut self, is_player_team: bool, amount: f32) {
+        if is_player_team {
+            self._player_resources += amount;
+        } else {
+            self._enemy_resources += amount;
+        }
+    }
+    // returns false when the team can not afford the cost
+    pub fn spend_team_resources(&mut self, is_player_team: bool, cost: f32) -> bool {
+        if self.get_team_resources(is_player_team) < cost {
+            return false;
+        }
+        self.add_team_resources(is_player_team, -cost);
+        true
+    }
+
+    pub fn spawn_resources(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let level_data = game_client.get_project_scene_manager().get_level_data();
+        // the nodes and the depots are placed on the terrain
+        for placement_data in level_data._resource_node_placement_datas.iter() {
+            let mut placement_data = placement_data.clone();
+            placement_data._position.y = project_scene_manager.get_height_bilinear(&placement_data._position, 0);
+            let render_object_create_info = RenderObjectCreateInfo {
+                _model_data_name: placement_data._model_data_name.clone(),
+                _position: placement_data._position.clone_owned(),
+                _rotation: placement_data._rotation.clone_owned(),
+                _scale: placement_data._scale.clone_owned(),
+                ..Default::default()
+            };
+            let render_object = project_scene_manager.add_static_render_object("ResourceNode", &render_object_create_info);
+            self._resource_nodes.insert(ResourceNodeInstance::create_resource_node_instance(&placement_data, render_object));
+        }
+
+        for placement_data in level_data._resource_depot_placement_datas.iter() {
+            let mut placement_data = placement_data.clone();
+            placement_data._position.y = project_scene_manager.get_height_bilinear(&placement_data._position, 0);
+            let render_object_create_info = RenderObjectCreateInfo {
+                _model_data_name: placement_data._model_data_name.clone(),
+                _position: placement_data._position.clone_owned(),
+                _rotation: placement_data._rotation.clone_owned(),
+                _scale: placement_data._scale.clone_owned(),
+                ..Default::default()
+            };
+            let render_object = project_scene_manager.add_static_render_object("ResourceDepot", &render_object_create_info);
+            self._resource_depots.insert(ResourceDepotInstance::create_resource_depot_instance(&placement_data, render_object));
+        }
+    }
+
+    pub fn find_nearest_resource_node(&self, position: &Vector3<f32>) -> Option<(ResourceNodeHandle, &ResourceNodeInstance)> {
+        let mut nearest_node: Option<(ResourceNodeHandle, &ResourceNodeInstance)> = None;
+        let mut nearest_distance = f32::MAX;
+        for (node_handle, resource_node) in self._resource_nodes.iter() {
+            let distance = (resource_node.get_position() - position).norm_squared();
+            if false == resource_node.is_depleted() && distance < nearest_distance {
+                nearest_distance = distance;
+                nearest_node = Some((node_handle, resource_node));
+            }
+        }
+        nearest_node
+    }
+
+    pub fn find_nearest_resource_depot(&self, position: &Vector3<f32>, is_player_team: bool) -> Option<(ResourceDepotHandle, &ResourceDepotInstance)> {
+        let mut nearest_depot: Option<(ResourceDepotHandle, &ResourceDepotInstance)> = None;
+        let mut nearest_distance = f32::MAX;
+        for (depot_handle, resource_depot) in self._resource_depots.iter() {
+            let distance = (resource_depot.get_position() - position).norm_squared();
+            if is_player_team == resource_depot.is_player_depot() && distance < nearest_distance {
+                nearest_distance = distance;
+                nearest_depot = Some((depot_handle, resource_depot));
+            }
+        }
+        nearest_depot
+    }
+
+    fn remove_depleted_resource_nodes(&mut self) {
+        let project_scene_manager = ptr_as_ref(self._game_client).get_project_scene_manager_mut();
+        let mut depleted_nodes: Vec<ResourceNodeHandle> = Vec::new();
+        for (node_handle, resource_node) in self._resource_nodes.iter() {
+            if resource_node.is_depleted() {
+                if let Some(render_object) = &resource_node._render_object {
+                    project_scene_manager.remove_static_render_object(&render_object.borrow()._render_object_name);
+                }
+                depleted_nodes.push(node_handle);
+            }
+        }
+        for node_handle in depleted_nodes.iter() {
+            self._resource_nodes.remove(*node_handle);
+        }
+    }
+
+    // the harvest cycle drives the harvester with the move commands, a new state starts once the command is finished.
+    fn update_harvester(&mut self, actor: &mut ActorController, delta_time: f32) {
+        let position = actor.get_transform().get_position().clone_owned();
+        let radius = actor.get_bound_box()._radius;
+        let is_player_team = actor.is_player_actor();
+        let is_command_finished = actor.can_manual_controll();
+
+        match actor._harvest_state {
+            HarvestState::Idle => {
+                if actor.get_ship().is_cargo_full() {
+                    if let Some((depot_handle, resource_depot)) = self.find_nearest_resource_depot(&position, is_player_team) {
+                        actor.set_command_actor_move(resource_depot.get_position());
+                        actor._harvest_state = HarvestState::ReturnToDepot(depot_handle);
+                    }
+                } else if let Some((node_handle, resource_node)) = self.find_nearest_resource_node(&position) {
+                    actor.set_command_actor_move(resource_node.get_position());
+                    actor._harvest_state = HarvestState::MoveToNode(node_handle);
+                }
+            },
+            HarvestState::MoveToNode(node_handle) => {
+                match self._resource_nodes.get(node_handle) {
+                    Some(resource_node) if false == resource_node.is_depleted() => {
+                        if is_command_finished {
+                            if resource_node.is_in_range(&position, radius) {
+                                actor._harvest_state = HarvestState::Gathering(node_handle);
+                            } else {
+                                actor.set_command_actor_move(resource_node.get_position());
+                            }
+                        }
+                    },
+                    _ => actor._harvest_state = HarvestState::Idle,
+                }
+            },
+            HarvestState::Gathering(node_handle) => {
+                let is_gathering = match self._resource_nodes.get_mut(node_handle) {
+                    Some(resource_node) if false == resource_node.is_depleted() && false == actor.get_ship().is_cargo_full() => {
+                        let ship = actor.get_ship_mut();
+                        let harvest_amount = (ship.get_ship_data()._harvest_rate * delta_time).min(ship.get_cargo_space());
+                        let harvested_amount = resource_node.harvest(harvest_amount);
+                        ship.add_cargo(harvested_amount);
+                        true
+                    },
+                    _ => false,
+                };
+
+                if false == is_gathering {
+                    actor._harvest_state = HarvestState::Idle;
+                    if 0.0 < actor.get_ship().get_cargo() {
+                        if let Some((depot_handle, resource_depot)) = self.find_nearest_resource_depot(&position, is_player_team) {
+                            actor.set_command_actor_move(resource_depot.get_position());
+                            actor._harvest_state = HarvestState::ReturnToDepot(depot_handle);
+                        }
+                    }
+                }
+            },
+            HarvestState::ReturnToDepot(depot_handle) => {
+                match self._resource_depots.get(depot_handle) {
+                    Some(resource_depot) => {
+                        if is_command_finished {
+                            if resource_depot.is_in_range(&position, radius) {
+                                let cargo = actor.get_ship_mut().unload_cargo();
+                                self.add_team_resources(is_player_team, cargo);
+                                actor._harvest_state = HarvestState::Idle;
+                            } else {
+                                actor.set_command_actor_move(resource_depot.get_position());
+                            }
+                        }
+                    },
+                    None => actor._harvest_state = HarvestState::Idle,
+                }
+            },
+        }
+    }
+
+    pub fn update_economy_manager(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        for actor in game_client.get_actor_manager()._actors.values() {
+            let actor = ptr_as_mut(actor);
+            if actor.get_ship().is_harvester() {
+                self.update_harvester(actor, delta_time);
+            }
+        }
+        self.remove_depleted_resource_nodes();
+    }
+}
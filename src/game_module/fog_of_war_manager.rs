@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+
+use nalgebra::{Vector2, Vector3};
+
+use rust_engine_3d::utilities::system::ptr_as_ref;
+use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::actors::actor::{ActorController, ActorHandle};
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::{
+    FOG_OF_WAR_GRID_COUNT,
+    FOG_OF_WAR_UPDATE_INTERVAL,
+    FOG_OF_WAR_EYE_HEIGHT,
+    FOG_OF_WAR_EXPLORED_ALPHA,
+    FOG_OF_WAR_UNEXPLORED_ALPHA,
+    FOG_OF_WAR_MASK_TEXTURE_NAME,
+};
+use crate::game_module::height_map_data::HeightMapData;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FogOfWarCellState {
+    Unexplored,
+    Explored,
+    Visible,
+}
+
+// the visible cells and the visible actors of the other team
+pub struct TeamVisibility {
+    pub _cell_states: Vec<FogOfWarCellState>,
+    pub _visible_actor_ids: HashSet<u64>,
+}
+
+pub struct FogOfWarManager {
+    pub _game_client: *const GameClient,
+    pub _player_team: TeamVisibility,
+    pub _enemy_team: TeamVisibility,
+    pub _update_time: f32,
+    // the fog of the player team, it is uploaded to its own texture and drawn over the minimap
+    pub _fog_mask_pixels: Vec<u8>,
+    pub _fog_mask_dirty: bool,
+    pub _hidden_actors: HashSet<ActorHandle>,
+}
+
+// Implementation
+fn get_eye_position(actor: &ActorController) -> Vector3<f32> {
+    actor.get_transform().get_position() + Vector3::new(0.0, FOG_OF_WAR_EYE_HEIGHT, 0.0)
+}
+
+impl TeamVisibility {
+    pub fn create_team_visibility() -> TeamVisibility {
+        TeamVisibility {
+            _cell_states: vec![FogOfWarCellState::Unexplored; FOG_OF_WAR_GRID_COUNT * FOG_OF_WAR_GRID_COUNT],
+            _visible_actor_ids: HashSet::new(),
+        }
+    }
+
+    pub fn reset_team_visibility(&mut self) {
+        self._cell_states.fill(FogOfWarCellState::Unexplored);
+        self._visible_actor_ids.clear();
+    }
+
+    // the cells are revealed by the sight radius of the team actors, the terrain blocks the line of sight.
+    pub fn update_team_visibility(&mut self, actors: &[&ActorController], is_player_team: bool, height_map_data: &HeightMapData) {
+        for cell_state in self._cell_states.iter_mut() {
+            if FogOfWarCellState::Visible == *cell_state {
+                *cell_state = FogOfWarCellState::Explored;
+            }
+        }
+        self._visible_actor_ids.clear();
+
+        let bounding_box = height_map_data.get_bounding_box();
+        let cell_size_x = bounding_box._size.x / FOG_OF_WAR_GRID_COUNT as f32;
+        let cell_size_z = bounding_box._size.z / FOG_OF_WAR_GRID_COUNT as f32;
+        if cell_size_x <= 0.0 || cell_size_z <= 0.0 {
+            return;
+        }
+
+        let viewers: Vec<&&ActorController> = actors.iter().filter(|actor| is_player_team == actor.is_player_actor() && false == actor.get_ship().is_destroyed()).collect();
+        for viewer in viewers.iter() {
+            let eye_position = get_eye_position(viewer);
+            let sight_radius = viewer.get_ship().get_ship_data()._sight_radius;
+            let cell_min_x = 0.max(((eye_position.x - sight_radius - bounding_box._min.x) / cell_size_x).floor() as i32);
+            let cell_min_z = 0.max(((eye_position.z - sight_radius - bounding_box._min.z) / cell_size_z).floor() as i32);
+            let cell_max_x = (FOG_OF_WAR_GRID_COUNT as i32 - 1).min(((eye_position.x + sight_radius - bounding_box._min.x) / cell_size_x).floor() as i32);
+            let cell_max_z = (FOG_OF_WAR_GRID_COUNT as i32 - 1).min(((eye_position.z + sight_radius - bounding_box._min.z) / cell_size_z).floor() as i32);
+            for cell_z in cell_min_z..=cell_max_z {
+                for cell_x in cell_min_x..=cell_max_x {
+                    let cell_index = cell_z as usize * FOG_OF_WAR_GRID_COUNT + cell_x as usize;
+                    if FogOfWarCellState::Visible == self._cell_states[cell_index] {
+                        continue;
+                    }
+
+                    let mut cell_center: Vector3<f32> = Vector3::new(
+                        bounding_box._min.x + (cell_x as f32 + 0.5) * cell_size_x,
+                        0.0,
+                        bounding_box._min.z + (cell_z as f32 + 0.5) * cell_size_z
+                    );
+                    let offset_x = cell_center.x - eye_position.x;
+                    let offset_z = cell_center.z - eye_position.z;
+                    if sight_radius * sight_radius < offset_x * offset_x + offset_z * offset_z {
+                        continue;
+                    }
+
+                    cell_center.y = height_map_data.get_height_bilinear(&cell_center, 0) + FOG_OF_WAR_EYE_HEIGHT;
+                    if false == height_map_data.is_line_of_sight_blocked(&eye_position, &cell_center) {
+                        self._cell_states[cell_index] = FogOfWarCellState::Visible;
+                    }
+                }
+            }
+        }
+
+        // the actors of the other team in the sight
+        for actor in actors.iter() {
+            if is_player_team == actor.is_player_actor() {
+                continue;
+            }
+
+            let target_position = actor.get_bound_box()._center.clone_owned();
+            let is_visible = viewers.iter().any(|viewer| {
+                let eye_position = get_eye_position(viewer);
+                let sight_radius = viewer.get_ship().get_ship_data()._sight_radius;
+                (target_position - eye_position).norm() <= sight_radius && false == height_map_data.is_line_of_sight_blocked(&eye_position, &target_position)
+            });
+            if is_visible {
+                self._visible_actor_ids.insert(actor.get_actor_id());
+            }
+        }
+    }
+}
+
+impl FogOfWarManager {
+    pub fn create_fog_of_war_manager() -> Box<FogOfWarManager> {
+        Box::new(FogOfWarManager {
+            _game_client: std::ptr::null(),
+            _player_team: TeamVisibility::create_team_visibility(),
+            _enemy_team: TeamVisibility::create_team_visibility(),
+            _update_time: 0.0,
+            _fog_mask_pixels: vec![0; FOG_OF_WAR_GRID_COUNT * FOG_OF_WAR_GRID_COUNT * 4],
+            _fog_mask_dirty: true,
+            _hidden_actors: HashSet::new(),
+        })
+    }
+
+    pub fn initialize_fog_of_war_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+    pub fn destroy_fog_of_war_manager(&mut self) {
+        self.reset_fog_of_war();
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_team_visibility(&self, is_player_team: bool) -> &TeamVisibility {
+        if is_player_team { &self._player_team } else { &self._enemy_team }
+    }
+
+    // the whole map is unexplored again, the next update reveals the sight of the spawned actors.
+    pub fn reset_fog_of_war(&mut self) {
+        self._player_team.reset_team_visibility();
+        self._enemy_team.reset_team_visibility();
+        self._update_time = 0.0;
+        self._hidden_actors.clear();
+        self.update_fog_mask();
+    }
+
+    pub fn get_cell_state(&self, cell_x: usize, cell_z: usize, is_player_team: bool) -> FogOfWarCellState {
+        self.get_team_visibility(is_player_team)._cell_states[cell_z * FOG_OF_WAR_GRID_COUNT + cell_x]
+    }
+
+    pub fn get_cell_state_at(&self, position: &Vector3<f32>, is_player_team: bool) -> FogOfWarCellState {
+        let bounding_box = self.get_game_client().get_project_scene_manager().get_height_map_data().get_bounding_box();
+        let texcoord_x = 0.0f32.max(1.0f32.min((position.x - bounding_box._min.x) / bounding_box._size.x));
+        let texcoord_z = 0.0f32.max(1.0f32.min((position.z - bounding_box._min.z) / bounding_box._size.z));
+        let cell_x = (FOG_OF_WAR_GRID_COUNT - 1).min((texcoord_x * FOG_OF_WAR_GRID_COUNT as f32) as usize);
+        let cell_z = (FOG_OF_WAR_GRID_COUNT - 1).min((texcoord_z * FOG_OF_WAR_GRID_COUNT as f32) as usize);
+        self.get_cell_state(cell_x, cell_z, is_player_team)
+    }
+
+    // the own team is always visible
+    pub fn is_actor_visible(&self, actor: &ActorController, is_player_team: bool) -> bool {
+        is_player_team == actor.is_player_actor() || self.get_team_visibility(is_player_team)._visible_actor_ids.contains(&actor.get_actor_id())
+    }
+
+    pub fn is_visible_to_player(&self, actor: &ActorController) -> bool {
+        self.is_actor_visible(actor, true)
+    }
+
+    pub fn get_fog_mask_pixels(&self) -> &Vec<u8> { &self._fog_mask_pixels }
+
+    // one black pixel per cell, the alpha is the darkness of the fog
+    fn update_fog_mask(&mut self) {
+        for (cell_index, cell_state) in self._player_team._cell_states.iter().enumerate() {
+            let alpha = match cell_state {
+                FogOfWarCellState::Unexplored => FOG_OF_WAR_UNEXPLORED_ALPHA,
+                FogOfWarCellState::Explored => FOG_OF_WAR_EXPLORED_ALPHA,
+                FogOfWarCellState::Visible => 0,
+            };
+            let pixel_index = cell_index * 4 + 3;
+            if self._fog_mask_pixels[pixel_index] != alpha {
+                self._fog_mask_pixels[pixel_index] = alpha;
+                self._fog_mask_dirty = true;
+            }
+        }
+    }
+
+    fn update_fog_mask_texture(&mut self, game_client: &GameClient, project_scene_manager: &ProjectSceneManager) {
+        if self._fog_mask_dirty {
+            self._fog_mask_dirty = false;
+            let grid_max = FOG_OF_WAR_GRID_COUNT as i32 - 1;
+            project_scene_manager.upload_texture_pixels(
+                game_client.get_project_application().get_engine_application(),
+                FOG_OF_WAR_MASK_TEXTURE_NAME,
+                &self._fog_mask_pixels,
+                &Vector2::new(0, 0),
+                &Vector2::new(grid_max, grid_max)
+            );
+        }
+    }
+
+    // the hidden enemies are not rendered, the ship and the weapons are hidden together.
+    // only the actors whose visibility changed are passed to the scene manager, the removed ships take their render objects with them.
+    fn update_hidden_render_objects(&mut self, actors: &[&ActorController], project_scene_manager: &mut ProjectSceneManager) {
+        self._hidden_actors.retain(|actor_handle| actors.iter().any(|actor| *actor_handle == actor.get_actor_handle()));
+        for actor in actors.iter() {
+            let hidden = false == self.is_visible_to_player(actor);
+            let actor_handle = actor.get_actor_handle();
+            if hidden == self._hidden_actors.contains(&actor_handle) {
+                continue;
+            }
+
+            if hidden {
+                self._hidden_actors.insert(actor_handle);
+            } else {
+                self._hidden_actors.remove(&actor_handle);
+            }
+            let ship = actor.get_ship();
+            if let Some(render_object) = &ship._render_object {
+                project_scene_manager.set_render_object_hidden(&render_object.borrow()._render_object_name, hidden);
+            }
+            for weapon in ship._weapons.values() {
+                if let Some(weapon_render_object) = weapon.get_weapon_render_object() {
+                    project_scene_manager.set_render_object_hidden(&weapon_render_object.borrow()._render_object_name, hidden);
+                }
+            }
+        }
+    }
+
+    pub fn update_fog_of_war_manager(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let actors: Vec<&ActorController> = game_client.get_actor_manager()._actors.values().collect();
+
+        self._update_time -= delta_time;
+        if self._update_time <= 0.0 {
+            self._update_time = FOG_OF_WAR_UPDATE_INTERVAL;
+            let height_map_data = project_scene_manager.get_height_map_data();
+            self._player_team.update_team_visibility(&actors, true, height_map_data);
+            self._enemy_team.update_team_visibility(&actors, false, height_map_data);
+            self.update_fog_mask();
+        }
+
+        // the dedicated server keeps the visibility for the snapshots, there is nothing to render
+        if game_client.is_dedicated_server() {
+            return;
+        }
+        self.update_fog_mask_texture(game_client, project_scene_manager);
+        self.update_hidden_render_objects(&actors, project_scene_manager);
+    }
+}
@@ -1,16 +1,25 @@
 use nalgebra::Vector2;
 use winit::event::VirtualKeyCode;
 
-use rust_engine_3d::application::audio_manager::AudioManager;
+use rust_engine_3d::application::audio_manager::{AudioLoop, AudioManager};
 use rust_engine_3d::effect::effect_manager::EffectManager;
 use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
 use crate::application::project_application::ProjectApplication;
 use crate::application::project_scene_manager::ProjectSceneManager;
 use crate::game_module::actor_manager::ActorManager;
-use crate::game_module::game_constants::SCROLL_DELTA_TO_CAMERA_DISTANCE_SPEED;
+use crate::game_module::building_manager::BuildingManager;
+use crate::game_module::economy_manager::EconomyManager;
+use crate::game_module::fog_of_war_manager::FogOfWarManager;
+use crate::game_module::game_constants::{SCROLL_DELTA_TO_CAMERA_DISTANCE_SPEED, SHIP_POWER_DISTRIBUTION_STEP};
 use crate::game_module::game_controller::{GameViewMode, GameController};
+use crate::game_module::game_mode_manager::GameModeManager;
 use crate::game_module::game_ui::GameUIManager;
+use crate::game_module::network_manager::NetworkManager;
+use crate::game_module::pickup_manager::PickupManager;
+use crate::game_module::script_manager::ScriptManager;
+use crate::game_module::ship::ship::ShipPowerType;
 use crate::game_module::weapon_manager::WeaponManager;
+use crate::game_module::zone_manager::ZoneManager;
 use crate::resource::project_resource::ProjectResources;
 use crate::renderer::project_ui::ProjectUIManager;
 use rust_engine_3d::application::scene_manager::ProjectSceneManagerBase;
@@ -24,9 +33,19 @@ pub struct GameClient {
     pub _audio_manager: *const AudioManager,
     pub _effect_manager: *const EffectManager,
     pub _actor_manager: Box<ActorManager>,
+    pub _building_manager: Box<BuildingManager>,
+    pub _pickup_manager: Box<PickupManager>,
+    pub _economy_manager: Box<EconomyManager>,
+    pub _fog_of_war_manager: Box<FogOfWarManager>,
+    pub _game_mode_manager: Box<GameModeManager>,
+    pub _network_manager: Box<NetworkManager>,
+    pub _script_manager: Box<ScriptManager>,
+    pub _zone_manager: Box<ZoneManager>,
     pub _game_controller: Box<GameController>,
     pub _game_ui_manager: Box<GameUIManager>,
-    pub _weapon_manager: Box<WeaponManager>
+    pub _weapon_manager: Box<WeaponManager>,
+    // the dedicated server has no local player and no input
+    pub _is_dedicated_server: bool,
 }
 
 impl GameClient {
@@ -39,9 +58,18 @@ impl GameClient {
             _audio_manager: std::ptr::null(),
             _effect_manager: std::ptr::null(),
             _actor_manager: ActorManager::create_actor_manager(),
+            _building_manager: BuildingManager::create_building_manager(),
+            _pickup_manager: PickupManager::create_pickup_manager(),
+            _economy_manager: EconomyManager::create_economy_manager(),
+            _fog_of_war_manager: FogOfWarManager::create_fog_of_war_manager(),
+            _game_mode_manager: GameModeManager::create_game_mode_manager(),
+            _network_manager: NetworkManager::create_network_manager(),
+            _script_manager: ScriptManager::create_script_manager(),
+            _zone_manager: ZoneManager::create_zone_manager(),
             _game_controller: GameController::create_game_controller(),
             _game_ui_manager: GameUIManager::create_game_ui_manager(),
             _weapon_manager: WeaponManager::create_weapon_manager(),
+            _is_dedicated_server: false,
         })
     }
 
@@ -54,18 +82,53 @@ impl GameClient {
         self._project_ui_manager = project_application.get_project_ui_manager();
         self._audio_manager = project_application.get_audio_manager();
         self._effect_manager = project_application.get_effect_manager();
+        self._is_dedicated_server = false;
+        self.initialize_game_managers(game_client);
+    }
+
+    // the dedicated server runs without the application, the window, the audio and the renderer
+    pub fn initialize_headless_game_client(&mut self, project_resources: &ProjectResources, project_scene_manager: &ProjectSceneManager) {
+        let game_client: *const GameClient = self;
+        self._project_application = std::ptr::null();
+        self._project_scene_manager = project_scene_manager;
+        self._project_resources = project_resources;
+        self._project_ui_manager = std::ptr::null();
+        self._audio_manager = std::ptr::null();
+        self._effect_manager = std::ptr::null();
+        self._is_dedicated_server = true;
+        self.initialize_game_managers(ptr_as_ref(game_client));
+    }
+
+    fn initialize_game_managers(&mut self, game_client: &GameClient) {
         self._game_ui_manager.initialize_game_ui_manager(game_client);
         self._game_controller.initialize_game_controller(game_client);
         self._actor_manager.initialize_actor_manager(game_client);
+        self._building_manager.initialize_building_manager(game_client);
+        self._pickup_manager.initialize_pickup_manager(game_client);
+        self._economy_manager.initialize_economy_manager(game_client);
+        self._fog_of_war_manager.initialize_fog_of_war_manager(game_client);
         self._weapon_manager.initialize_weapon_manager(game_client);
+        self._zone_manager.initialize_zone_manager(game_client);
+        self._game_mode_manager.initialize_game_mode_manager(game_client);
+        self._script_manager.initialize_script_manager(game_client);
+        self._network_manager.initialize_network_manager(game_client);
     }
 
     pub fn destroy_game_client(&mut self) {
+        self._network_manager.destroy_network_manager();
+        self._script_manager.destroy_script_manager();
+        self._game_mode_manager.destroy_game_mode_manager();
+        self._zone_manager.destroy_zone_manager();
         self._weapon_manager.destroy_weapon_manager();
+        self._fog_of_war_manager.destroy_fog_of_war_manager();
+        self._economy_manager.destroy_economy_manager();
+        self._pickup_manager.destroy_pickup_manager();
+        self._building_manager.destroy_building_manager();
         self._actor_manager.destroy_actor_manager();
         self._game_ui_manager.destroy_game_ui_manager();
     }
 
+    pub fn is_dedicated_server(&self) -> bool { self._is_dedicated_server }
     pub fn get_project_application(&self) -> &ProjectApplication { ptr_as_ref(self._project_application) }
     pub fn get_project_application_mut(&self) -> &mut ProjectApplication { ptr_as_mut(self._project_application) }
     pub fn get_project_scene_manager(&self) -> &ProjectSceneManager { ptr_as_ref(self._project_scene_manager) }
@@ -78,8 +141,35 @@ impl GameClient {
     pub fn get_audio_manager_mut(&self) -> &mut AudioManager { ptr_as_mut(self._audio_manager) }
     pub fn get_effect_manager(&self) -> &EffectManager { ptr_as_ref(self._effect_manager) }
     pub fn get_effect_manager_mut(&self) -> &mut EffectManager { ptr_as_mut(self._effect_manager) }
+    // the dedicated server has no audio device
+    pub fn play_audio(&self, audio_name: &str) {
+        if false == self._audio_manager.is_null() {
+            self.get_audio_manager_mut().create_audio_instance(audio_name, AudioLoop::ONCE);
+        }
+    }
+    pub fn play_audio_bank(&self, audio_bank_name: &str) {
+        if false == self._audio_manager.is_null() {
+            self.get_audio_manager_mut().create_audio_instance_from_bank(audio_bank_name, AudioLoop::ONCE);
+        }
+    }
     pub fn get_actor_manager(&self) -> &ActorManager { ptr_as_ref(self._actor_manager.as_ref()) }
     pub fn get_actor_manager_mut(&self) -> &mut ActorManager { ptr_as_mut(self._actor_manager.as_ref()) }
+    pub fn get_building_manager(&self) -> &BuildingManager { ptr_as_ref(self._building_manager.as_ref()) }
+    pub fn get_building_manager_mut(&self) -> &mut BuildingManager { ptr_as_mut(self._building_manager.as_ref()) }
+    pub fn get_pickup_manager(&self) -> &PickupManager { ptr_as_ref(self._pickup_manager.as_ref()) }
+    pub fn get_pickup_manager_mut(&self) -> &mut PickupManager { ptr_as_mut(self._pickup_manager.as_ref()) }
+    pub fn get_economy_manager(&self) -> &EconomyManager { ptr_as_ref(self._economy_manager.as_ref()) }
+    pub fn get_economy_manager_mut(&self) -> &mut EconomyManager { ptr_as_mut(self._economy_manager.as_ref()) }
+    pub fn get_fog_of_war_manager(&self) -> &FogOfWarManager { ptr_as_ref(self._fog_of_war_manager.as_ref()) }
+    pub fn get_fog_of_war_manager_mut(&self) -> &mut FogOfWarManager { ptr_as_mut(self._fog_of_war_manager.as_ref()) }
+    pub fn get_game_mode_manager(&self) -> &GameModeManager { ptr_as_ref(self._game_mode_manager.as_ref()) }
+    pub fn get_game_mode_manager_mut(&self) -> &mut GameModeManager { ptr_as_mut(self._game_mode_manager.as_ref()) }
+    pub fn get_network_manager(&self) -> &NetworkManager { ptr_as_ref(self._network_manager.as_ref()) }
+    pub fn get_network_manager_mut(&self) -> &mut NetworkManager { ptr_as_mut(self._network_manager.as_ref()) }
+    pub fn get_script_manager(&self) -> &ScriptManager { ptr_as_ref(self._script_manager.as_ref()) }
+    pub fn get_script_manager_mut(&self) -> &mut ScriptManager { ptr_as_mut(self._script_manager.as_ref()) }
+    pub fn get_zone_manager(&self) -> &ZoneManager { ptr_as_ref(self._zone_manager.as_ref()) }
+    pub fn get_zone_manager_mut(&self) -> &mut ZoneManager { ptr_as_mut(self._zone_manager.as_ref()) }
     pub fn get_game_controller(&self) -> &GameController { ptr_as_ref(self._game_controller.as_ref()) }
     pub fn get_game_controller_mut(&self) -> &mut GameController { ptr_as_mut(self._game_controller.as_ref()) }
     pub fn get_game_ui_manager(&self) -> &GameUIManager { ptr_as_ref(self._game_ui_manager.as_ref()) }
@@ -87,9 +177,35 @@ impl GameClient {
     pub fn get_weapon_manager(&self) -> &WeaponManager { ptr_as_ref(self._weapon_manager.as_ref()) }
     pub fn get_weapon_manager_mut(&self) -> &mut WeaponManager { ptr_as_mut(self._weapon_manager.as_ref()) }
 
-    pub fn start_game(&mut self) {
-        self.get_project_scene_manager_mut().open_scene_data("default");
+    pub fn start_game(&mut self, scene_name: &str) {
+        self.get_project_scene_manager_mut().open_scene_data(scene_name);
+        self.get_game_ui_manager_mut().build_minimap();
         self.get_actor_manager_mut().spawn_actors();
+        self.get_building_manager_mut().spawn_buildings();
+        self.get_pickup_manager_mut().spawn_pickups();
+        self.get_economy_manager_mut().spawn_resources();
+        self.get_zone_manager_mut().spawn_zones();
+        self.get_fog_of_war_manager_mut().reset_fog_of_war();
+        self.get_game_controller_mut().reset_match();
+        self.get_game_mode_manager_mut().start_game_mode();
+        self.get_script_manager_mut().start_scripts();
+    }
+
+    // the managers of the match are cleared and the scene is opened again, the network session is kept.
+    pub fn restart_game(&mut self, scene_name: &str) {
+        self._script_manager.destroy_script_manager();
+        self._game_mode_manager.destroy_game_mode_manager();
+        self._zone_manager.destroy_zone_manager();
+        self._weapon_manager.destroy_weapon_manager();
+        self._fog_of_war_manager.destroy_fog_of_war_manager();
+        self._economy_manager.destroy_economy_manager();
+        self._pickup_manager.destroy_pickup_manager();
+        self._building_manager.destroy_building_manager();
+        self._actor_manager.destroy_actor_manager();
+        self._game_ui_manager.clear_minimap_objective_markers();
+        self.get_project_scene_manager_mut().close_scene_data();
+        self.start_game(scene_name);
+        self._network_manager.respawn_remote_clients();
     }
 
     pub fn update_event(&mut self) {
@@ -104,9 +220,13 @@ impl GameClient {
         let mouse_delta: Vector2<f32> = Vector2::new(mouse_move_data._mouse_pos_delta.x as f32 / mouse_speed_ratio, mouse_move_data._mouse_pos_delta.y as f32 / mouse_speed_ratio);
         let scroll_delta = &mouse_move_data._scroll_delta;
         let pressed_key_c = keyboard_input_data.get_key_pressed(VirtualKeyCode::C);
+        let pressed_key_t = keyboard_input_data.get_key_pressed(VirtualKeyCode::T);
+        let pressed_key_f1 = keyboard_input_data.get_key_pressed(VirtualKeyCode::F1);
+        let pressed_key_f2 = keyboard_input_data.get_key_pressed(VirtualKeyCode::F2);
+        let pressed_key_f3 = keyboard_input_data.get_key_pressed(VirtualKeyCode::F3);
+        let pressed_key_f4 = keyboard_input_data.get_key_pressed(VirtualKeyCode::F4);
 
         let main_camera = project_scene_manager.get_main_camera_mut();
-        let player_actor = ptr_as_mut(self.get_actor_manager().get_player_actor());
 
         if 0 != mouse_move_data._mouse_pos_delta.x || 0 != mouse_move_data._mouse_pos_delta.y || 0 != scroll_delta.y || keyboard_input_data.is_any_key_hold() {
             self._game_controller.update_target_position(project_scene_manager, main_camera, &mouse_move_data._mouse_pos);
@@ -120,6 +240,28 @@ impl GameClient {
             self._game_controller.toggle_view_mode();
         }
 
+        if pressed_key_t {
+            let crosshair_pos = self._game_ui_manager.get_crosshair_screen_pos();
+            self._game_controller.lock_target_nearest_to_crosshair(&crosshair_pos);
+        }
+
+        // the death cam ignores the controls of the ship
+        let player_actor = match self.get_actor_manager().get_player_actor() {
+            Some(player_actor) => ptr_as_mut(player_actor),
+            None => return,
+        };
+
+        // power distribution
+        if pressed_key_f1 {
+            player_actor.get_ship_mut().add_power(ShipPowerType::Shields, SHIP_POWER_DISTRIBUTION_STEP);
+        } else if pressed_key_f2 {
+            player_actor.get_ship_mut().add_power(ShipPowerType::Weapons, SHIP_POWER_DISTRIBUTION_STEP);
+        } else if pressed_key_f3 {
+            player_actor.get_ship_mut().add_power(ShipPowerType::Engines, SHIP_POWER_DISTRIBUTION_STEP);
+        } else if pressed_key_f4 {
+            player_actor.get_ship_mut().reset_power_distribution();
+        }
+
         match self._game_controller._game_view_mode {
             GameViewMode::TopViewMode => self._game_controller.update_event_for_top_view_mode(
                 time_data,
@@ -145,9 +287,29 @@ impl GameClient {
 
     pub fn update_game_client(&mut self) {
         let delta_time = self.get_project_application().get_engine_application()._time_data._delta_time as f32;
+        self.update_game_client_with_delta_time(delta_time);
+    }
+
+    // the dedicated server updates the game with the fixed tick
+    pub fn update_game_client_with_delta_time(&mut self, delta_time: f32) {
         self._game_controller.update_game_controller(delta_time);
-        self._actor_manager.update_actor_manager(delta_time);
-        self._weapon_manager.update_weapon_manager(delta_time);
-        self._game_ui_manager.update_game_ui(delta_time);
+        // the world is frozen at the end of the match
+        if false == self._game_controller.is_match_ended() {
+            self._actor_manager.update_actor_manager(delta_time);
+        }
+        self._network_manager.update_network_manager(delta_time);
+        if false == self._game_controller.is_match_ended() {
+            self._building_manager.update_building_manager(delta_time);
+            self._pickup_manager.update_pickup_manager(delta_time);
+            self._economy_manager.update_economy_manager(delta_time);
+            self._zone_manager.update_zone_manager(delta_time);
+            self._game_mode_manager.update_game_mode_manager(delta_time);
+            self._script_manager.update_script_manager(delta_time);
+            self._fog_of_war_manager.update_fog_of_war_manager(delta_time);
+            self._weapon_manager.update_weapon_manager(delta_time);
+        }
+        if false == self._is_dedicated_server {
+            self._game_ui_manager.update_game_ui(delta_time);
+        }
     }
 }
\ No newline at end of file
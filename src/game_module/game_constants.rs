@@ -14,4 +14,65 @@ pub const CAMERA_EDGE_SCROLL_SPEED_BY_MOUSE: f32 = 0.05;
 
 pub const MOUSE_PITCH_MIN: f32 = -1.5;
 pub const MOUSE_PITCH_MAX: f32 = 1.5;
-pub const MOUSE_ROTATION_SPEED: f32 = 0.002;
\ No newline at end of file
+pub const MOUSE_ROTATION_SPEED: f32 = 0.002;
+
+pub const CAMERA_SHAKE_TRAUMA_DECAY: f32 = 1.5;
+pub const CAMERA_SHAKE_FREQUENCY: f32 = 25.0;
+pub const CAMERA_SHAKE_MAX_OFFSET: f32 = 0.5;
+pub const CAMERA_SHAKE_MAX_ROTATION: f32 = 0.05;
+pub const CAMERA_SHAKE_BULLET_IMPACT_TRAUMA: f32 = 0.3;
+pub const CAMERA_SHAKE_BULLET_IMPACT_RADIUS: f32 = 30.0;
+pub const CAMERA_SHAKE_SHIP_DESTROY_TRAUMA: f32 = 0.8;
+pub const CAMERA_SHAKE_SHIP_DESTROY_RADIUS: f32 = 100.0;
+
+pub const SHIP_POWER_DISTRIBUTION_STEP: f32 = 0.1;
+pub const SHIP_POWER_SCALE_MIN: f32 = 0.25;
+
+pub const COLLISION_RESTITUTION: f32 = 0.3;
+pub const RAMMING_DAMAGE_MIN_SPEED: f32 = 10.0;
+
+pub const DEBRIS_ANGULAR_SPEED: f32 = 10.0;
+pub const DEBRIS_RESTITUTION: f32 = 0.3;
+pub const DEBRIS_SETTLE_SPEED: f32 = 1.0;
+
+pub const PICKUP_FLOATING_HEIGHT: f32 = 1.5;
+pub const PICKUP_ROTATION_SPEED: f32 = 2.0;
+
+pub const FOG_OF_WAR_GRID_COUNT: usize = 64;
+pub const FOG_OF_WAR_UPDATE_INTERVAL: f32 = 0.25;
+pub const FOG_OF_WAR_EYE_HEIGHT: f32 = 2.0;
+pub const FOG_OF_WAR_EXPLORED_ALPHA: u8 = 120;
+pub const FOG_OF_WAR_UNEXPLORED_ALPHA: u8 = 230;
+// the mask texture has FOG_OF_WAR_GRID_COUNT x FOG_OF_WAR_GRID_COUNT rgba8 pixels
+pub const FOG_OF_WAR_MASK_TEXTURE_NAME: &str = "ui/fog_of_war_mask";
+// the minimap texture has MINIMAP_TEXTURE_SIZE x MINIMAP_TEXTURE_SIZE rgba8 pixels
+pub const MINIMAP_TEXTURE_SIZE: usize = 128;
+pub const MINIMAP_TEXTURE_NAME: &str = "ui/minimap";
+
+pub const NETWORK_PROTOCOL_VERSION: u32 = 3;
+pub const NETWORK_DEFAULT_PORT: u16 = 7777;
+pub const NETWORK_MAX_PACKET_SIZE: usize = 65507;
+pub const NETWORK_SNAPSHOT_INTERVAL: f32 = 0.05;
+pub const NETWORK_SNAPSHOT_HISTORY: usize = 64;
+pub const NETWORK_INTERPOLATION_DELAY: f32 = 0.1;
+pub const NETWORK_INPUT_REDUNDANCY: usize = 3;
+pub const NETWORK_MAX_PENDING_INPUTS: usize = 128;
+pub const NETWORK_MAX_INPUT_DELTA_TIME: f32 = 0.1;
+pub const NETWORK_DELTA_EPSILON: f32 = 0.001;
+pub const NETWORK_CONNECT_RETRY_INTERVAL: f32 = 1.0;
+pub const NETWORK_CONNECTION_TIMEOUT: f32 = 5.0;
+pub const NETWORK_SPAWN_SPACING: f32 = 20.0;
+pub const NETWORK_MAX_FIRE_START_DISTANCE: f32 = 120.0;
+
+pub const PLAYER_INVULNERABLE_EFFECT_INTERVAL: f32 = 0.5;
+pub const DEATH_CAMERA_PITCH: f32 = 35.0;
+pub const DEATH_CAMERA_YAW_SPEED: f32 = 0.3;
+
+pub const SCRIPT_MAX_OPERATIONS: u64 = 100000;
+pub const SCRIPT_MAX_CALL_LEVELS: usize = 32;
+pub const SCRIPT_MAX_TIMERS: usize = 64;
+
+pub const ZONE_WIREFRAME_SEGMENTS: usize = 24;
+
+// the capture speed does not grow with more ships than this
+pub const CAPTURE_MAX_ACTOR_COUNT: usize = 3;
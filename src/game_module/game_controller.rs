@@ -8,13 +8,17 @@ use rust_engine_3d::renderer::camera::CameraObjectData;
 use rust_engine_3d::utilities::math;
 use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
 use crate::application::project_scene_manager::ProjectSceneManager;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actors::actor::{ActorController, ActorHandle};
+use crate::game_module::buildings::building::BuildingHandle;
+use crate::game_module::camera_shake::CameraShake;
 use crate::game_module::game_constants::{
     CAMERA_DISTANCE_MIN,
     CAMERA_DISTANCE_MAX,
     CAMERA_DISTANCE_SPEED,
     CAMERA_EDGE_SCROLL_SPEED,
     CAMERA_EDGE_SCROLL_SPEED_BY_MOUSE,
+    DEATH_CAMERA_PITCH,
+    DEATH_CAMERA_YAW_SPEED,
     MOUSE_PITCH_MIN,
     MOUSE_PITCH_MAX,
     MOUSE_ROTATION_SPEED
@@ -31,6 +35,12 @@ pub enum GameViewMode {
     Count
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatchResult {
+    Victory,
+    Defeat,
+}
+
 pub struct GameController {
     pub _game_client: *const GameClient,
     pub _game_ui_manager: *const GameUIManager,
@@ -40,6 +50,10 @@ pub struct GameController {
     pub _target_direction: Vector3<f32>,
     pub _relative_target_position: Vector3<f32>,
     pub _game_view_mode: GameViewMode,
+    pub _camera_shake: CameraShake,
+    pub _locked_target: Option<ActorHandle>,
+    pub _selected_factory: Option<BuildingHandle>,
+    pub _match_result: Option<MatchResult>,
 }
 
 impl GameController {
@@ -54,12 +68,17 @@ impl GameController {
             _target_direction: Vector3::zeros(),
             _relative_target_position: Vector3::zeros(),
             _game_view_mode: GameViewMode::TopViewMode,
+            _camera_shake: CameraShake::create_camera_shake(),
+            _locked_target: None,
+            _selected_factory: None,
+            _match_result: None,
         })
     }
 
     pub fn initialize_game_controller(&mut self, game_client: &GameClient) {
         self._game_client = game_client;
         self._game_ui_manager = game_client._game_ui_manager.as_ref();
+        self.set_camera_shake_intensity(game_client.get_project_resources().get_game_options()._camera_shake_intensity);
         self.change_view_mode(GameViewMode::TopViewMode);
     }
     pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
@@ -84,6 +103,57 @@ impl GameController {
         let next_view_mode = (self._game_view_mode as i32 + 1) % GameViewMode::Count as i32;
         self.change_view_mode(unsafe { std::mem::transmute(next_view_mode) });
     }
+    pub fn get_locked_target(&self) -> Option<ActorHandle> { self._locked_target }
+    pub fn clear_locked_target(&mut self) { self._locked_target = None; }
+    pub fn get_selected_factory(&self) -> Option<BuildingHandle> { self._selected_factory }
+    pub fn clear_selected_factory(&mut self) { self._selected_factory = None; }
+    pub fn get_match_result(&self) -> Option<MatchResult> { self._match_result }
+    pub fn is_match_ended(&self) -> bool { self._match_result.is_some() }
+    pub fn reset_match(&mut self) { self._match_result = None; }
+    // the simulation stops at the end of the match, the camera and the ui keep running.
+    pub fn end_match(&mut self, match_result: MatchResult) {
+        if self.is_match_ended() {
+            return;
+        }
+        self._match_result = Some(match_result);
+        let message = match match_result {
+            MatchResult::Victory => "Victory",
+            MatchResult::Defeat => "Defeat",
+        };
+        self.get_game_ui_manager_mut().add_objective_event_log(message);
+    }
+    pub fn lock_target_nearest_to_crosshair(&mut self, crosshair_pos: &Vector2<f32>) {
+        let game_client = ptr_as_ref(self._game_client);
+        let main_camera = game_client.get_project_scene_manager().get_main_camera();
+        let camera_front = -main_camera.get_camera_front() as Vector3<f32>;
+        let mut nearest_distance: f32 = f32::MAX;
+        self._locked_target = None;
+        let fog_of_war_manager = game_client.get_fog_of_war_manager();
+        for (actor_handle, actor) in game_client.get_actor_manager()._actors.iter() {
+            if actor.is_player_actor() || false == fog_of_war_manager.is_visible_to_player(actor) {
+                continue;
+            }
+            let actor_pos = actor.get_transform().get_position();
+            if (actor_pos - main_camera.get_camera_position()).dot(&camera_front) <= 0.0 {
+                continue;
+            }
+            let screen_pos: Vector2<f32> = main_camera.convert_world_to_screen(actor_pos, false);
+            let distance = (screen_pos - crosshair_pos).norm();
+            if distance < nearest_distance {
+                nearest_distance = distance;
+                self._locked_target = Some(actor_handle);
+            }
+        }
+    }
+    pub fn get_camera_shake_intensity(&self) -> f32 { self._camera_shake.get_intensity() }
+    pub fn set_camera_shake_intensity(&mut self, intensity: f32) { self._camera_shake.set_intensity(intensity); }
+    pub fn add_camera_shake(&mut self, trauma: f32) {
+        self._camera_shake.add_trauma(trauma);
+    }
+    pub fn add_camera_shake_at(&mut self, trauma: f32, position: &Vector3<f32>, radius: f32) {
+        let player_position = self.get_game_client().get_actor_manager().get_player_focus_position();
+        self._camera_shake.add_trauma_at(trauma, position, &player_position, radius);
+    }
     pub fn get_camera_distance_ratio(&self) -> f32 {
         (self._camera_distance - CAMERA_DISTANCE_MIN) / (CAMERA_DISTANCE_MAX - CAMERA_DISTANCE_MIN)
     }
@@ -95,6 +165,21 @@ impl GameController {
             self._camera_goal_distance = CAMERA_DISTANCE_MAX;
         }
     }
+    pub fn move_camera_to(&mut self, target_position: &Vector3<f32>) {
+        let project_scene_manager = self.get_game_client().get_project_scene_manager();
+        let main_camera = project_scene_manager.get_main_camera_mut();
+        let camera_position = main_camera._transform_object.get_position().clone_owned();
+        let screen_center: Vector2<i32> = Vector2::new(main_camera._window_size.x / 2, main_camera._window_size.y / 2);
+        let relative_pos = main_camera.convert_screen_to_relative_world(&screen_center);
+        let mut look_at_position: Vector3<f32> = camera_position.clone_owned();
+        if project_scene_manager.get_height_map_collision_point(&camera_position, &relative_pos.normalize(), -1.0, &mut look_at_position) {
+            let move_delta = Vector3::new(target_position.x - look_at_position.x, 0.0, target_position.z - look_at_position.z);
+            main_camera._transform_object.move_position(&move_delta);
+        } else {
+            let camera_pos = Vector3::new(target_position.x, camera_position.y, target_position.z);
+            main_camera._transform_object.set_position(&camera_pos);
+        }
+    }
     pub fn update_target_position(&mut self, project_scene_manager: &ProjectSceneManager, main_camera: &CameraObjectData, mouse_pos: &Vector2<i32>) {
         let relative_pos = main_camera.convert_screen_to_relative_world(mouse_pos);
         if project_scene_manager.get_height_map_collision_point(main_camera._transform_object.get_position(), &relative_pos.normalize(), -1.0, &mut self._target_position) {
@@ -112,7 +197,7 @@ impl GameController {
         main_camera: &mut CameraObjectData,
         player_actor: &mut ActorController
     ) {
-        let _btn_left: bool = mouse_input_data._btn_l_pressed;
+        let btn_left: bool = mouse_input_data._btn_l_pressed;
         let btn_right: bool = mouse_input_data._btn_r_pressed;
         let btn_right_hold: bool = mouse_input_data._btn_r_hold;
         let pressed_key_a = keyboard_input_data.get_key_hold(VirtualKeyCode::A);
@@ -121,6 +206,8 @@ impl GameController {
         let pressed_key_s = keyboard_input_data.get_key_hold(VirtualKeyCode::S);
         let modifier_keys_shift = keyboard_input_data.get_key_hold(VirtualKeyCode::LShift);
         let modifier_keys_ctrl = keyboard_input_data.get_key_hold(VirtualKeyCode::LControl);
+        let pressed_key_r = keyboard_input_data.get_key_pressed(VirtualKeyCode::R);
+        let pressed_key_x = keyboard_input_data.get_key_pressed(VirtualKeyCode::X);
 
         let mut front_xz: Vector3<f32> = main_camera._transform_object.get_front().clone_owned();
         front_xz.y = 0.0;
@@ -168,9 +255,24 @@ impl GameController {
         // update cross hair
         self.get_game_ui_manager_mut().set_crosshair_pos(&mouse_move_data._mouse_pos);
 
-        // player controll
-        if btn_right {
-            if modifier_keys_ctrl {
+        // the clicks on the widgets are handled by the widgets
+        let is_ui_hit = self.get_game_ui_manager().is_ui_hit(&mouse_move_data._mouse_pos);
+
+        // factory selection
+        let building_manager = ptr_as_ref(self._game_client).get_building_manager_mut();
+        if btn_left && false == is_ui_hit {
+            if let Some(factory_handle) = building_manager.find_factory_at(&self._target_position, true) {
+                self._selected_factory = Some(factory_handle);
+            }
+        } else if pressed_key_x {
+            self._selected_factory = None;
+        }
+
+        // player controll, the right-click sets the rally point of the selected factory
+        if btn_right && false == is_ui_hit {
+            if let Some(factory_handle) = self._selected_factory {
+                building_manager.set_rally_point(factory_handle, &self._target_position);
+            } else if modifier_keys_ctrl {
                 player_actor.set_command_actor_attack(&self._target_position);
             } else {
                 player_actor.set_command_actor_move(&self._target_position);
@@ -191,6 +293,11 @@ impl GameController {
             player_actor.get_ship_mut().get_controller_mut().boost_on();
         }
 
+        if pressed_key_r {
+            player_actor.get_ship_mut().reload_weapons();
+            self.get_game_client().get_network_manager_mut().add_reload_command();
+        }
+
         // player ship project to height map
         // let relative_pos = main_camera.convert_screen_to_relative_world(&mouse_move_data._mouse_pos);
         // let mut actor_pos = main_camera._transform_object.get_position() + relative_pos.normalize() * self._camera_distance;
@@ -223,6 +330,13 @@ impl GameController {
         let hold_key_q = keyboard_input_data.get_key_hold(VirtualKeyCode::Q);
         let hold_key_e = keyboard_input_data.get_key_hold(VirtualKeyCode::E);
         let modifier_keys_shift = keyboard_input_data.get_key_hold(VirtualKeyCode::LShift);
+        let pressed_key_r = keyboard_input_data.get_key_pressed(VirtualKeyCode::R);
+
+        // reload
+        if pressed_key_r {
+            player_actor.get_ship_mut().reload_weapons();
+            self.get_game_client().get_network_manager_mut().add_reload_command();
+        }
 
         // fire
         if btn_left {
@@ -290,9 +404,29 @@ impl GameController {
             self._camera_distance = math::lerp(self._camera_distance, self._camera_goal_distance, 1.0f32.min(delta_time * CAMERA_DISTANCE_SPEED));
         }
 
-        let project_scene_manager = self.get_game_client().get_project_scene_manager();
-        let player_actor = self.get_game_client().get_actor_manager().get_player_actor();
-        let main_camera = self.get_main_camera_mut();
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager();
+        let actor_manager = game_client.get_actor_manager();
+        let main_camera = project_scene_manager.get_main_camera_mut();
+
+        // camera shake
+        self._camera_shake.restore_camera(main_camera);
+        self._camera_shake.update_camera_shake(delta_time);
+
+        let player_actor = match actor_manager.get_player_actor() {
+            Some(player_actor) => player_actor,
+            None => {
+                // death cam, the camera circles around the place of the death
+                let yaw = main_camera._transform_object.get_yaw() + DEATH_CAMERA_YAW_SPEED * delta_time;
+                main_camera._transform_object.set_yaw(yaw);
+                main_camera._transform_object.set_pitch(math::degree_to_radian(DEATH_CAMERA_PITCH));
+                main_camera._transform_object.update_transform_object();
+                let camera_pos = actor_manager.get_player_death_position() + main_camera._transform_object.get_front() * self._camera_distance;
+                main_camera._transform_object.set_position(&camera_pos);
+                self._camera_shake.apply_camera_shake(main_camera);
+                return;
+            },
+        };
         let player_transform = player_actor.get_transform();
 
         if GameViewMode::TopViewMode == self._game_view_mode {
@@ -342,9 +476,19 @@ impl GameController {
         } else {
             assert!(false, "Not implemented.");
         }
+
+        self._camera_shake.apply_camera_shake(main_camera);
     }
 
     pub fn update_game_controller(&mut self, delta_time: f32) {
-        self.update_camera(delta_time);
+        if let Some(locked_target) = self._locked_target {
+            if false == self.get_game_client().get_actor_manager().has_actor(locked_target) {
+                self.clear_locked_target();
+            }
+        }
+        // the dedicated server has no camera
+        if false == self.get_game_client().is_dedicated_server() {
+            self.update_camera(delta_time);
+        }
     }
 }
\ No newline at end of file
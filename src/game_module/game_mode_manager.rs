@@ -0,0 +1,335 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::utilities::system::ptr_as_ref;
+use crate::game_module::actors::actor::ActorHandle;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_controller::MatchResult;
+use crate::game_module::game_modes::capture_point::CapturePoint;
+use crate::game_module::game_modes::game_mode::{GameModeData, GameModeType};
+use crate::game_module::level_datas::spawn_point::{ShipSpawnPointData, SpawnPointType};
+
+pub struct GameModeManager {
+    pub _game_client: *const GameClient,
+    pub _game_mode_data: GameModeData,
+    // the game mode of the level is used when it is empty
+    pub _selected_game_mode_data_name: String,
+    pub _player_team_score: f32,
+    pub _enemy_team_score: f32,
+    pub _elapsed_time: f32,
+    pub _capture_points: Vec<CapturePoint>,
+    pub _wave_timer: f32,
+    pub _spawned_wave_count: i32,
+    pub _convoy_actor: Option<ActorHandle>,
+    pub _convoy_waypoint_index: usize,
+}
+
+// Implementation
+fn get_team_name(is_player_team: bool) -> &'static str {
+    if is_player_team { "the player team" } else { "the enemy team" }
+}
+
+impl GameModeManager {
+    pub fn create_game_mode_manager() -> Box<GameModeManager> {
+        Box::new(GameModeManager {
+            _game_client: std::ptr::null(),
+            _game_mode_data: GameModeData::default(),
+            _selected_game_mode_data_name: String::new(),
+            _player_team_score: 0.0,
+            _enemy_team_score: 0.0,
+            _elapsed_time: 0.0,
+            _capture_points: Vec::new(),
+            _wave_timer: 0.0,
+            _spawned_wave_count: 0,
+            _convoy_actor: None,
+            _convoy_waypoint_index: 0,
+        })
+    }
+
+    pub fn initialize_game_mode_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+        self._selected_game_mode_data_name = game_client.get_project_resources().get_game_options()._game_mode_data_name.clone();
+    }
+    pub fn destroy_game_mode_manager(&mut self) {
+        self._game_mode_data = GameModeData::default();
+        self._player_team_score = 0.0;
+        self._enemy_team_score = 0.0;
+        self._elapsed_time = 0.0;
+        self._capture_points.clear();
+        self._wave_timer = 0.0;
+        self._spawned_wave_count = 0;
+        self._convoy_actor = None;
+        self._convoy_waypoint_index = 0;
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_game_mode_data(&self) -> &GameModeData { &self._game_mode_data }
+    pub fn get_game_mode_type(&self) -> GameModeType { self._game_mode_data._game_mode_type }
+    pub fn get_selected_game_mode_data_name(&self) -> &str { &self._selected_game_mode_data_name }
+    // it is used from the next start of the game mode
+    pub fn select_game_mode(&mut self, game_mode_data_name: &str) {
+        self._selected_game_mode_data_name = game_mode_data_name.to_string();
+    }
+    pub fn get_team_score(&self, is_player_team: bool) -> f32 {
+        if is_player_team { self._player_team_score } else { self._enemy_team_score }
+    }
+    pub fn add_team_score(&mut self, is_player_team: bool, score: f32) {
+        if is_player_team {
+            self._player_team_score += score;
+        } else {
+            self._enemy_team_score += score;
+        }
+    }
+    // the client gets the scores from the server
+    pub fn set_team_scores(&mut self, player_team_score: f32, enemy_team_score: f32) {
+        self._player_team_score = player_team_score;
+        self._enemy_team_score = enemy_team_score;
+    }
+    pub fn get_elapsed_time(&self) -> f32 { self._elapsed_time }
+    // None is no time limit
+    pub fn get_remaining_time(&self) -> Option<f32> {
+        if self._game_mode_data.has_time_limit() { Some(0f32.max(self._game_mode_data._time_limit - self._elapsed_time)) } else { None }
+    }
+    pub fn get_capture_points(&self) -> &[CapturePoint] { &self._capture_points }
+    pub fn get_spawned_wave_count(&self) -> i32 { self._spawned_wave_count }
+    pub fn get_convoy_actor(&self) -> Option<ActorHandle> { self._convoy_actor }
+    pub fn get_convoy_waypoint_index(&self) -> usize { self._convoy_waypoint_index }
+
+    fn get_team_actor_count(&self, game_client: &GameClient, is_player_team: bool) -> usize {
+        game_client.get_actor_manager()._actors.values().filter(|actor| is_player_team == actor.is_player_actor()).count()
+    }
+
+    // the selected game mode or the game mode of the level, the level without the game mode is the skirmish
+    pub fn start_game_mode(&mut self) {
+        self.destroy_game_mode_manager();
+        let game_client = ptr_as_ref(self._game_client);
+        let game_mode_data_name = if self._selected_game_mode_data_name.is_empty() {
+            game_client.get_project_scene_manager().get_level_data()._game_mode_data_name.clone()
+        } else {
+            self._selected_game_mode_data_name.clone()
+        };
+        let game_mode_data_name = &game_mode_data_name;
+        let project_resources = game_client.get_project_resources();
+        if false == game_mode_data_name.is_empty() {
+            if project_resources.has_game_mode_data(game_mode_data_name) {
+                self._game_mode_data = project_resources.get_game_mode_data(game_mode_data_name).borrow().clone();
+            } else {
+                log::error!("start_game_mode: {} does not exist", game_mode_data_name);
+            }
+        }
+
+        match self._game_mode_data._game_mode_type {
+            GameModeType::KingOfTheHill => {
+                let zone_manager = game_client.get_zone_manager();
+                for zone_name in self._game_mode_data._capture_zone_names.iter() {
+                    if zone_manager.get_zone(zone_name).is_none() {
+                        log::error!("start_game_mode: the capture zone {} does not exist", zone_name);
+                        continue;
+                    }
+                    self._capture_points.push(CapturePoint::create_capture_point(zone_name));
+                }
+            },
+            GameModeType::Survival => {
+                self._wave_timer = self._game_mode_data._wave_interval;
+            },
+            GameModeType::Escort => self.spawn_convoy(game_client),
+            _ => (),
+        }
+
+        if GameModeType::Skirmish != self._game_mode_data._game_mode_type {
+            game_client.get_game_ui_manager_mut().add_objective_event_log(self._game_mode_data._game_mode_type.get_display_name());
+        }
+    }
+
+    // the kills of the opposing team
+    pub fn add_kill_score(&mut self, is_victim_player_team: bool) {
+        if GameModeType::TeamDeathmatch == self._game_mode_data._game_mode_type {
+            self.add_team_score(false == is_victim_player_team, 1.0);
+        }
+    }
+
+    fn spawn_convoy(&mut self, game_client: &GameClient) {
+        let convoy_ship_data_name = &self._game_mode_data._convoy_ship_data_name;
+        if false == game_client.get_project_resources().has_ship_data(convoy_ship_data_name) {
+            log::error!("spawn_convoy: unknown ship data {}", convoy_ship_data_name);
+            return;
+        }
+        let spawn_point_data = ShipSpawnPointData {
+            _ship_data_name: convoy_ship_data_name.clone(),
+            _position: self._game_mode_data._convoy_start_position.clone_owned(),
+            ..Default::default()
+        };
+        let actor_manager = game_client.get_actor_manager_mut();
+        let convoy_handle = actor_manager.create_actor(game_client, &spawn_point_data, true);
+        if let (Some(convoy), Some(waypoint)) = (actor_manager.get_actor_mut(convoy_handle), self._game_mode_data._convoy_waypoints.first()) {
+            convoy.set_command_actor_move(waypoint);
+        }
+        self._convoy_actor = Some(convoy_handle);
+        game_client.get_game_ui_manager_mut().add_objective_event_log("Escort the convoy");
+    }
+
+    // the team with only its own ships inside pushes the capture progress
+    fn update_capture_points(&mut self, game_client: &GameClient, delta_time: f32) {
+        let actors = &game_client.get_actor_manager()._actors;
+        let zone_manager = game_client.get_zone_manager();
+        let game_ui_manager = game_client.get_game_ui_manager_mut();
+        let capture_time = self._game_mode_data._capture_time;
+        let capture_score = self._game_mode_data._capture_score_per_second * delta_time;
+        let mut scores: [f32; 2] = [0.0, 0.0];
+        for capture_point in self._capture_points.iter_mut() {
+            let zone = match zone_manager.get_zone(capture_point.get_zone_name()) {
+                Some(zone) => zone,
+                None => continue,
+            };
+            let mut player_team_count: usize = 0;
+            let mut enemy_team_count: usize = 0;
+            for actor_handle in zone.get_actors_inside().iter() {
+                match actors.get(*actor_handle) {
+                    Some(actor) if actor.is_player_actor() => player_team_count += 1,
+                    Some(_) => enemy_team_count += 1,
+                    None => (),
+                }
+            }
+
+            if capture_point.update_capture_point(player_team_count, enemy_team_count, capture_time, delta_time) {
+                let message = match capture_point.get_owner_team() {
+                    Some(is_player_team) => format!("{} is captured by {}", capture_point.get_zone_name(), get_team_name(is_player_team)),
+                    None => format!("{} is neutral", capture_point.get_zone_name()),
+                };
+                game_ui_manager.add_objective_event_log(&message);
+            }
+
+            match capture_point.get_owner_team() {
+                Some(true) => scores[0] += capture_score,
+                Some(false) => scores[1] += capture_score,
+                None => (),
+            }
+        }
+        self.add_team_score(true, scores[0]);
+        self.add_team_score(false, scores[1]);
+    }
+
+    fn update_survival_waves(&mut self, game_client: &GameClient, delta_time: f32) {
+        let wave_count = self._game_mode_data._wave_count;
+        if 0 < wave_count && wave_count <= self._spawned_wave_count {
+            return;
+        }
+        self._wave_timer -= delta_time;
+        if 0.0 < self._wave_timer {
+            return;
+        }
+        self._wave_timer += self._game_mode_data._wave_interval.max(1.0);
+        self._spawned_wave_count += 1;
+
+        // the enemy wave attacks the nearest ship of the player team
+        let actor_manager = game_client.get_actor_manager_mut();
+        let player_team_positions: Vec<Vector3<f32>> = actor_manager._actors.values()
+            .filter(|actor| actor.is_player_actor())
+            .map(|actor| actor.get_transform().get_position().clone_owned())
+            .collect();
+        for spawn_point_type in self._game_mode_data._wave_spawn_point_datas.iter() {
+            match spawn_point_type {
+                SpawnPointType::Player(spawn_point_data) => { actor_manager.create_actor(game_client, spawn_point_data, true); },
+                SpawnPointType::NonPlayer(spawn_point_data) => {
+                    let actor_handle = actor_manager.create_actor(game_client, spawn_point_data, false);
+                    let target_position = player_team_positions.iter().min_by(|a, b| {
+                        let distance_a = (*a - &spawn_point_data._position).norm();
+                        let distance_b = (*b - &spawn_point_data._position).norm();
+                        distance_a.total_cmp(&distance_b)
+                    });
+                    if let (Some(actor), Some(target_position)) = (actor_manager.get_actor_mut(actor_handle), target_position) {
+                        actor.set_command_actor_attack(target_position);
+                    }
+                },
+                _ => (),
+            }
+        }
+        game_client.get_game_ui_manager_mut().add_reinforcement_event_log(&format!("Wave {}", self._spawned_wave_count));
+    }
+
+    // the convoy goes to the next waypoint when it is close enough or the move command is done
+    fn update_convoy(&mut self, game_client: &GameClient) {
+        let convoy_actor = match self._convoy_actor {
+            Some(convoy_actor) => convoy_actor,
+            None => return,
+        };
+        let convoy = match game_client.get_actor_manager_mut().get_actor_mut(convoy_actor) {
+            Some(convoy) => convoy,
+            None => {
+                game_client.get_game_ui_manager_mut().add_objective_event_log("The convoy is destroyed");
+                game_client.get_game_controller_mut().end_match(MatchResult::Defeat);
+                return;
+            },
+        };
+
+        let waypoints = &self._game_mode_data._convoy_waypoints;
+        if let Some(waypoint) = waypoints.get(self._convoy_waypoint_index) {
+            let to_waypoint: Vector3<f32> = waypoint - convoy.get_transform().get_position();
+            let distance_xz = (to_waypoint.x * to_waypoint.x + to_waypoint.z * to_waypoint.z).sqrt();
+            if distance_xz <= self._game_mode_data._convoy_arrival_radius || false == convoy.has_command() {
+                self._convoy_waypoint_index += 1;
+                if let Some(next_waypoint) = waypoints.get(self._convoy_waypoint_index) {
+                    convoy.set_command_actor_move(next_waypoint);
+                }
+            }
+        }
+
+        if waypoints.len() <= self._convoy_waypoint_index {
+            game_client.get_game_ui_manager_mut().add_objective_event_log("The convoy has arrived");
+            game_client.get_game_controller_mut().end_match(MatchResult::Victory);
+        }
+    }
+
+    fn update_match_result(&self, game_client: &GameClient) {
+        let game_mode_data = &self._game_mode_data;
+        let game_controller = game_client.get_game_controller_mut();
+        if game_mode_data.has_score_limit() {
+            if game_mode_data._score_limit <= self._player_team_score {
+                game_controller.end_match(MatchResult::Victory);
+                return;
+            } else if game_mode_data._score_limit <= self._enemy_team_score {
+                game_controller.end_match(MatchResult::Defeat);
+                return;
+            }
+        }
+
+        if GameModeType::Survival == game_mode_data._game_mode_type {
+            let is_player_respawning = game_client.get_actor_manager().is_player_respawning();
+            if 0 == self.get_team_actor_count(game_client, true) && false == is_player_respawning {
+                game_controller.end_match(MatchResult::Defeat);
+                return;
+            }
+            let is_last_wave_spawned = 0 < game_mode_data._wave_count && game_mode_data._wave_count <= self._spawned_wave_count;
+            if is_last_wave_spawned && 0 == self.get_team_actor_count(game_client, false) {
+                game_controller.end_match(MatchResult::Victory);
+                return;
+            }
+        }
+
+        // the higher score wins at the time limit, the survivors win and the late convoy loses
+        if game_mode_data.has_time_limit() && game_mode_data._time_limit <= self._elapsed_time {
+            let match_result = match game_mode_data._game_mode_type {
+                GameModeType::TeamDeathmatch | GameModeType::KingOfTheHill if self._enemy_team_score < self._player_team_score => MatchResult::Victory,
+                GameModeType::Survival => MatchResult::Victory,
+                _ => MatchResult::Defeat,
+            };
+            game_controller.end_match(match_result);
+        }
+    }
+
+    pub fn update_game_mode_manager(&mut self, delta_time: f32) {
+        // the server runs the game mode for the clients
+        let game_client = ptr_as_ref(self._game_client);
+        if game_client.get_network_manager().is_client() || game_client.get_game_controller().is_match_ended() {
+            return;
+        }
+        self._elapsed_time += delta_time;
+        match self._game_mode_data._game_mode_type {
+            GameModeType::KingOfTheHill => self.update_capture_points(game_client, delta_time),
+            GameModeType::Survival => self.update_survival_waves(game_client, delta_time),
+            GameModeType::Escort => self.update_convoy(game_client),
+            _ => (),
+        }
+        if false == game_client.get_game_controller().is_match_ended() {
+            self.update_match_result(game_client);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::game_module::game_constants::CAPTURE_MAX_ACTOR_COUNT;
+
+pub struct CapturePoint {
+    pub _zone_name: String,
+    // -1.0 is captured by the enemy team, 1.0 is captured by the player team
+    pub _capture_progress: f32,
+    // the team is is_player_team, None is neutral
+    pub _owner_team: Option<bool>,
+    pub _is_contested: bool,
+}
+
+impl CapturePoint {
+    pub fn create_capture_point(zone_name: &str) -> CapturePoint {
+        CapturePoint {
+            _zone_name: zone_name.to_string(),
+            _capture_progress: 0.0,
+            _owner_team: None,
+            _is_contested: false,
+        }
+    }
+
+    pub fn get_zone_name(&self) -> &str { &self._zone_name }
+    pub fn get_capture_progress(&self) -> f32 { self._capture_progress }
+    pub fn get_owner_team(&self) -> Option<bool> { self._owner_team }
+    pub fn is_contested(&self) -> bool { self._is_contested }
+
+    // the point does not move while both teams are inside or nobody is inside.
+    // the owner keeps the point until the progress is pushed back over the neutral.
+    // returns true when the owner is changed.
+    pub fn update_capture_point(&mut self, player_team_count: usize, enemy_team_count: usize, capture_time: f32, delta_time: f32) -> bool {
+        self._is_contested = 0 < player_team_count && 0 < enemy_team_count;
+        if self._is_contested || (0 == player_team_count && 0 == enemy_team_count) {
+            return false;
+        }
+
+        let (direction, actor_count) = if 0 < player_team_count { (1.0, player_team_count) } else { (-1.0, enemy_team_count) };
+        let capture_speed = actor_count.min(CAPTURE_MAX_ACTOR_COUNT) as f32 / capture_time.max(f32::EPSILON);
+        self._capture_progress = (self._capture_progress + direction * capture_speed * delta_time).clamp(-1.0, 1.0);
+
+        let prev_owner_team = self._owner_team;
+        if 1.0 <= self._capture_progress {
+            self._owner_team = Some(true);
+        } else if self._capture_progress <= -1.0 {
+            self._owner_team = Some(false);
+        } else {
+            match self._owner_team {
+                Some(true) if self._capture_progress <= 0.0 => self._owner_team = None,
+                Some(false) if 0.0 <= self._capture_progress => self._owner_team = None,
+                _ => (),
+            }
+        }
+        prev_owner_team != self._owner_team
+    }
+}
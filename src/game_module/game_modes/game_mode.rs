@@ -0,0 +1,102 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+use crate::game_module::level_datas::spawn_point::SpawnPointType;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum GameModeType {
+    // every ship of the level is spawned, the scripts and the objectives decide the match
+    Skirmish,
+    // the kills of the opposing team are scored
+    TeamDeathmatch,
+    // the owned capture points give the score over time
+    KingOfTheHill,
+    // the enemy waves come until the time limit or the last wave is destroyed
+    Survival,
+    // the convoy of the player team has to reach the last waypoint
+    Escort,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GameModeData {
+    pub _game_mode_type: GameModeType,
+    // zero is no limit
+    pub _score_limit: f32,
+    pub _time_limit: f32,
+    // king of the hill, the zone names of the level
+    pub _capture_zone_names: Vec<String>,
+    // the time for a single ship to capture the neutral point
+    pub _capture_time: f32,
+    pub _capture_score_per_second: f32,
+    // survival, zero wave count is endless
+    pub _wave_interval: f32,
+    pub _wave_count: i32,
+    pub _wave_spawn_point_datas: Vec<SpawnPointType>,
+    // escort
+    pub _convoy_ship_data_name: String,
+    pub _convoy_start_position: Vector3<f32>,
+    pub _convoy_waypoints: Vec<Vector3<f32>>,
+    pub _convoy_arrival_radius: f32,
+    // the player ship, zero lives is the endless respawn
+    pub _player_lives: i32,
+    pub _player_respawn_time: f32,
+    pub _player_invulnerable_time: f32,
+    pub _player_respawn_effects: Vec<String>,
+}
+
+impl Default for GameModeType {
+    fn default() -> GameModeType {
+        GameModeType::Skirmish
+    }
+}
+
+impl Default for GameModeData {
+    fn default() -> GameModeData {
+        GameModeData {
+            _game_mode_type: GameModeType::default(),
+            _score_limit: 0.0,
+            _time_limit: 0.0,
+            _capture_zone_names: Vec::new(),
+            _capture_time: 10.0,
+            _capture_score_per_second: 1.0,
+            _wave_interval: 30.0,
+            _wave_count: 0,
+            _wave_spawn_point_datas: Vec::new(),
+            _convoy_ship_data_name: "".to_string(),
+            _convoy_start_position: Vector3::zeros(),
+            _convoy_waypoints: Vec::new(),
+            _convoy_arrival_radius: 10.0,
+            _player_lives: 0,
+            _player_respawn_time: 5.0,
+            _player_invulnerable_time: 3.0,
+            _player_respawn_effects: Vec::new(),
+        }
+    }
+}
+
+impl GameModeType {
+    pub fn get_display_name(&self) -> &'static str {
+        match self {
+            GameModeType::Skirmish => "Skirmish",
+            GameModeType::TeamDeathmatch => "Team Deathmatch",
+            GameModeType::KingOfTheHill => "King of the Hill",
+            GameModeType::Survival => "Survival",
+            GameModeType::Escort => "Escort",
+        }
+    }
+}
+
+impl GameModeData {
+    pub fn has_score_limit(&self) -> bool {
+        0.0 < self._score_limit
+    }
+
+    pub fn has_time_limit(&self) -> bool {
+        0.0 < self._time_limit
+    }
+
+    pub fn has_player_lives(&self) -> bool {
+        0 < self._player_lives
+    }
+}
@@ -0,0 +1,2 @@
+pub mod capture_point;
+pub mod game_mode;
\ No newline at end of file
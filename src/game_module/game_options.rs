@@ -0,0 +1,20 @@
+use serde::{ Serialize, Deserialize };
+
+// the options of the player, they are read from game_datas/game_options.data at startup.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct GameOptions {
+    // 0.0 disables the camera shake
+    pub _camera_shake_intensity: f32,
+    // the game mode data name instead of the game mode of the level, empty is the game mode of the level
+    pub _game_mode_data_name: String,
+}
+
+impl Default for GameOptions {
+    fn default() -> GameOptions {
+        GameOptions {
+            _camera_shake_intensity: 1.0,
+            _game_mode_data_name: String::new(),
+        }
+    }
+}
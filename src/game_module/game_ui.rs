@@ -1,19 +1,36 @@
-use nalgebra::{ Vector2 };
+use std::collections::HashMap;
+
+use nalgebra::{ Vector2, Vector3 };
 
 use rust_engine_3d::application::scene_manager::ProjectSceneManagerBase;
 use rust_engine_3d::renderer::ui::{ProjectUIManagerBase, Widget, WidgetDefault};
 use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
+use crate::game_module::actors::actor::ActorHandle;
 use crate::game_module::game_client::GameClient;
+use crate::game_module::ui_widgets::damage_number_widgets::{DamageNumber, DamageNumberType};
+use crate::game_module::ui_widgets::event_log_widget::{EventLogWidget, EventLogType};
 use crate::game_module::ui_widgets::hud::{CrossHair, TargetHud, PlayerHud, SelectionArea};
+use crate::game_module::ui_widgets::minimap::Minimap;
+use crate::game_module::ui_widgets::production_widget::ProductionWidget;
+use crate::game_module::ui_widgets::respawn_widget::RespawnWidget;
+use crate::game_module::ui_widgets::scoreboard_widget::ScoreboardWidget;
 use crate::renderer::project_ui::ProjectUIManager;
 
 pub struct GameUIManager {
     pub _game_client: *const GameClient,
     pub _project_ui_manager: *const ProjectUIManager,
     pub _crosshair: Option<CrossHair>,
-    pub _target_hud: Option<TargetHud>,
+    pub _target_huds: Vec<TargetHud>,
+    pub _target_hud_map: HashMap<ActorHandle, usize>,
+    pub _free_target_huds: Vec<usize>,
     pub _player_hud: Option<PlayerHud>,
-    pub _selection_area: Option<Box<SelectionArea>>
+    pub _selection_area: Option<Box<SelectionArea>>,
+    pub _minimap: Option<Box<Minimap>>,
+    pub _damage_numbers: Vec<DamageNumber>,
+    pub _event_log_widget: Option<EventLogWidget>,
+    pub _production_widget: Option<ProductionWidget>,
+    pub _scoreboard_widget: Option<ScoreboardWidget>,
+    pub _respawn_widget: Option<RespawnWidget>,
 }
 
 impl GameUIManager {
@@ -22,9 +39,17 @@ impl GameUIManager {
             _game_client: std::ptr::null(),
             _project_ui_manager: std::ptr::null(),
             _crosshair: None,
-            _target_hud: None,
+            _target_huds: Vec::new(),
+            _target_hud_map: HashMap::new(),
+            _free_target_huds: Vec::new(),
             _player_hud: None,
             _selection_area: None,
+            _minimap: None,
+            _damage_numbers: Vec::new(),
+            _event_log_widget: None,
+            _production_widget: None,
+            _scoreboard_widget: None,
+            _respawn_widget: None,
         })
     }
 
@@ -36,15 +61,66 @@ impl GameUIManager {
         self._game_client = game_client;
         self._project_ui_manager = game_client.get_project_ui_manager();
 
+        // the dedicated server has no window, the widgets are not created
+        if game_client.is_dedicated_server() {
+            return;
+        }
+
         let project_resources = game_client.get_project_resources();
         let root_widget = game_client.get_project_ui_manager().get_root_widget_mut();
         let window_size = &game_client.get_project_application().get_engine_application()._window_size;
         let window_center = Vector2::<f32>::new(window_size.x as f32 * 0.5, window_size.y as f32 * 0.5,);
 
         self._crosshair = Some(CrossHair::create_crosshair(project_resources, root_widget, &window_center));
-        self._target_hud = Some(TargetHud::create_target_hud(root_widget, &window_center));
         self._player_hud = Some(PlayerHud::create_player_hud(root_widget, &Vector2::new(window_size.x as f32 - 200.0, window_center.y as f32)));
         self._selection_area = Some(SelectionArea::create_selection_area(root_widget, window_size));
+        self._event_log_widget = Some(EventLogWidget::create_event_log_widget(root_widget, &Vector2::new(10.0, 10.0)));
+        self._minimap = Some(Minimap::create_minimap(game_client, root_widget, &Vector2::new(window_size.x as f32 - 190.0, window_size.y as f32 - 190.0), 180.0));
+        self._production_widget = Some(ProductionWidget::create_production_widget(game_client, root_widget, &Vector2::new(10.0, window_size.y as f32 - 280.0)));
+        self._scoreboard_widget = Some(ScoreboardWidget::create_scoreboard_widget(root_widget, &Vector2::new(window_center.x - 130.0, 10.0)));
+        self._respawn_widget = Some(RespawnWidget::create_respawn_widget(root_widget, &window_center));
+    }
+
+    // the widgets which take the mouse clicks, the game controller ignores the clicks on them
+    pub fn is_ui_hit(&self, mouse_pos: &Vector2<i32>) -> bool {
+        let pos = Vector2::new(mouse_pos.x as f32, mouse_pos.y as f32);
+        self._minimap.as_ref().map_or(false, |minimap| minimap.contains_point(&pos)) ||
+            self._production_widget.as_ref().map_or(false, |production_widget| production_widget.contains_point(&pos))
+    }
+
+    pub fn build_minimap(&mut self) {
+        let height_map_data = self.get_game_client().get_project_scene_manager().get_height_map_data();
+        if let Some(minimap) = self._minimap.as_mut() {
+            minimap.build_minimap_background(height_map_data);
+        }
+    }
+
+    pub fn add_minimap_objective_marker(&mut self, position: &Vector3<f32>) {
+        if let Some(minimap) = self._minimap.as_mut() {
+            minimap.add_objective_marker(position);
+        }
+    }
+
+    pub fn clear_minimap_objective_markers(&mut self) {
+        if let Some(minimap) = self._minimap.as_mut() {
+            minimap.clear_objective_markers();
+        }
+    }
+
+    // the objective buildings and the objective zones which are not reached yet
+    pub fn update_objective_markers(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        self.clear_minimap_objective_markers();
+        for building in game_client.get_building_manager().get_buildings().values() {
+            if building.is_objective() && false == building.is_destroyed() {
+                self.add_minimap_objective_marker(&building.get_center());
+            }
+        }
+        for zone in game_client.get_zone_manager().get_zones().iter() {
+            if zone.is_active_objective() {
+                self.add_minimap_objective_marker(&zone.get_zone_data()._position);
+            }
+        }
     }
 
     pub fn destroy_game_ui_manager(&mut self) {
@@ -55,9 +131,10 @@ impl GameUIManager {
     }
 
     pub fn show_selection_area(&mut self, show: bool) {
-        let selection_area_widget = self._selection_area.as_ref().unwrap()._selection_area_layout.as_ref();
-        let ui_component = ptr_as_mut(selection_area_widget.get_ui_component());
-        ui_component.set_visible(show);
+        if let Some(selection_area) = self._selection_area.as_ref() {
+            let ui_component = ptr_as_mut(selection_area._selection_area_layout.get_ui_component());
+            ui_component.set_visible(show);
+        }
     }
 
     pub fn show_crosshair(&mut self, show: bool) {
@@ -66,14 +143,150 @@ impl GameUIManager {
     }
 
     pub fn set_crosshair_tracking_mouse(&mut self, tracking: bool) {
-        self._crosshair.as_mut().unwrap()._tracking_mouse = tracking;
+        if let Some(crosshair) = self._crosshair.as_mut() {
+            crosshair._tracking_mouse = tracking;
+        }
+    }
+
+    pub fn get_crosshair_screen_pos(&self) -> Vector2<f32> {
+        let crosshair = self._crosshair.as_ref().unwrap();
+        if crosshair._tracking_mouse {
+            Vector2::new(crosshair._pos.x as f32, crosshair._pos.y as f32)
+        } else {
+            let window_size = &self.get_game_client().get_project_application().get_engine_application()._window_size;
+            Vector2::new(window_size.x as f32 * 0.5, window_size.y as f32 * 0.5)
+        }
     }
 
     pub fn set_crosshair_pos(&mut self, pos: &Vector2<i32>) {
         self._crosshair.as_mut().unwrap()._pos.clone_from(pos);
     }
 
-    pub fn update_game_ui(&mut self, _delta_time: f32) {
+    pub fn show_damage_number(&mut self, world_position: &Vector3<f32>, damage: f32, damage_number_type: DamageNumberType) {
+        if damage <= 0.0 || self._project_ui_manager.is_null() {
+            return;
+        }
+
+        let index = match self._damage_numbers.iter().position(|damage_number| false == damage_number._is_alive) {
+            Some(index) => index,
+            None => {
+                let root_widget = self.get_project_ui_manager().get_root_widget_mut();
+                self._damage_numbers.push(DamageNumber::create_damage_number(root_widget));
+                self._damage_numbers.len() - 1
+            }
+        };
+        self._damage_numbers[index].show_damage_number(world_position, damage, damage_number_type);
+    }
+
+    pub fn add_event_log(&mut self, message: &str, event_log_type: EventLogType) {
+        match self._event_log_widget.as_mut() {
+            Some(event_log_widget) => event_log_widget.add_event_log(message, event_log_type),
+            // the dedicated server writes the events to the log
+            None => log::info!("{:?}: {}", event_log_type, message),
+        }
+    }
+
+    pub fn add_kill_event_log(&mut self, attacker_name: &str, weapon_name: &str, victim_name: &str) {
+        self.add_event_log(&format!("{} [{}] {}", attacker_name, weapon_name, victim_name), EventLogType::Kill);
+    }
+
+    pub fn add_objective_event_log(&mut self, message: &str) {
+        self.add_event_log(message, EventLogType::Objective);
+    }
+
+    pub fn add_reinforcement_event_log(&mut self, message: &str) {
+        self.add_event_log(message, EventLogType::Reinforcement);
+    }
+
+    pub fn show_hit_marker(&mut self, is_kill: bool) {
+        if let Some(crosshair) = self._crosshair.as_mut() {
+            crosshair.show_hit_marker(is_kill);
+        }
+    }
+
+    fn acquire_target_hud(&mut self, actor_handle: ActorHandle) -> usize {
+        if let Some(index) = self._target_hud_map.get(&actor_handle) {
+            return *index;
+        }
+
+        let index = match self._free_target_huds.pop() {
+            Some(index) => index,
+            None => {
+                let root_widget = self.get_project_ui_manager().get_root_widget_mut();
+                self._target_huds.push(TargetHud::create_target_hud(root_widget, &Vector2::zeros()));
+                self._target_huds.len() - 1
+            }
+        };
+        self._target_hud_map.insert(actor_handle, index);
+        index
+    }
+
+    fn release_target_hud(&mut self, actor_handle: ActorHandle) {
+        if let Some(index) = self._target_hud_map.remove(&actor_handle) {
+            self._target_huds[index].hide_target_hud();
+            self._free_target_huds.push(index);
+        }
+    }
+
+    pub fn update_target_huds(&mut self, game_client: &GameClient) {
+        let main_camera = game_client.get_project_scene_manager().get_main_camera();
+        let window_size = &main_camera._window_size;
+        let camera_position = main_camera.get_camera_position();
+        let camera_front = -main_camera.get_camera_front() as Vector3<f32>;
+        let camera_right = main_camera._transform_object.get_right();
+        let camera_up = main_camera._transform_object.get_up();
+        let actor_manager = game_client.get_actor_manager();
+        let player_actor_pos = actor_manager.get_player_focus_position();
+        let locked_target = game_client.get_game_controller().get_locked_target();
+
+        // recycle markers of the removed actors and the actors hidden by the fog of war
+        let fog_of_war_manager = game_client.get_fog_of_war_manager();
+        let removed_actors: Vec<ActorHandle> = self._target_hud_map.keys().filter(|actor_handle| match actor_manager.get_actor(**actor_handle) {
+            Some(actor) => false == fog_of_war_manager.is_visible_to_player(actor),
+            None => true,
+        }).cloned().collect();
+        for actor_handle in removed_actors.iter() {
+            self.release_target_hud(*actor_handle);
+        }
+
+        // the locked target is lost in the fog
+        let is_locked_target_hidden = match locked_target.and_then(|actor_handle| actor_manager.get_actor(actor_handle)) {
+            Some(actor) => false == fog_of_war_manager.is_visible_to_player(actor),
+            None => false,
+        };
+        if is_locked_target_hidden {
+            game_client.get_game_controller_mut().clear_locked_target();
+        }
+
+        for (actor_handle, actor) in actor_manager._actors.iter() {
+            if actor.is_player_actor() || false == fog_of_war_manager.is_visible_to_player(actor) {
+                continue;
+            }
+
+            let target_hud_index = self.acquire_target_hud(actor_handle);
+            let target_hud = &self._target_huds[target_hud_index];
+            let actor_pos = actor.get_transform().get_position();
+            let distance = (actor_pos - &player_actor_pos).norm();
+            target_hud.set_locked_target(Some(actor_handle) == locked_target);
+
+            let to_actor: Vector3<f32> = actor_pos - camera_position;
+            let screen_pos: Vector2<f32> = main_camera.convert_world_to_screen(actor_pos, false);
+            let is_on_screen = 0.0 < to_actor.dot(&camera_front) &&
+                0.0 <= screen_pos.x && screen_pos.x < window_size.x as f32 &&
+                0.0 <= screen_pos.y && screen_pos.y < window_size.y as f32;
+            if is_on_screen {
+                target_hud.update_target_hud(&screen_pos, distance, actor.get_ship());
+            } else {
+                let mut screen_dir: Vector2<f32> = Vector2::new(to_actor.dot(camera_right), -to_actor.dot(camera_up));
+                if false == screen_dir.try_normalize_mut(0.0).is_some() {
+                    screen_dir = Vector2::new(0.0, 1.0);
+                }
+                target_hud.update_offscreen_indicator(window_size, &screen_dir, distance);
+            }
+        }
+    }
+
+    pub fn update_game_ui(&mut self, delta_time: f32) {
         let game_client = ptr_as_ref(self._game_client);
         let main_camera = game_client.get_project_scene_manager().get_main_camera();
         let window_size = &game_client.get_project_application().get_engine_application()._window_size;
@@ -96,34 +309,55 @@ impl GameUIManager {
             ui_component.set_center(crosshair_pos_x as f32, crosshair_pos_y as f32);
         }
 
-        // Player Hud
+        self._crosshair.as_mut().unwrap().update_hit_marker(delta_time);
+
+        // Event Log
+        self._event_log_widget.as_mut().unwrap().update_event_log_widget(delta_time);
+
+        // Damage Numbers
+        for damage_number in self._damage_numbers.iter_mut() {
+            damage_number.update_damage_number(main_camera, delta_time);
+        }
+
+        // Player Hud, the gauges keep the last values while the player ship is destroyed
         let actor_manager = game_client.get_actor_manager();
-        let player_actor = actor_manager.get_player_actor();
-        let player_ship = player_actor.get_ship();
         let player_hud = self._player_hud.as_mut().unwrap();
-        player_hud._hull_point_widget.update_hull_point_widget(player_ship.get_hull_point() / 2.0, player_ship.get_max_hull_point());
-        player_hud._shield_point_widget.update_shield_point_widget(player_ship.get_shield_point() / 2.0, player_ship.get_max_shield_point());
+        if let Some(player_actor) = actor_manager.get_player_actor() {
+            let player_ship = player_actor.get_ship();
+            player_hud.update_hit_point_widgets(player_ship);
+            player_hud.update_power_widgets(player_ship);
+            player_hud.update_boost_fuel_widget(player_ship);
+            player_hud.update_weapon_gauge_widgets(player_ship);
+        }
+        let economy_manager = game_client.get_economy_manager();
+        player_hud.update_resource_widget(economy_manager.get_team_resources(true));
 
         // Target Hud
-        let target_hud = self._target_hud.as_mut().unwrap();
-        let player_actor_pos = player_actor.get_transform().get_position();
-        for (_id, actor) in actor_manager._actors.iter() {
-            if false == actor.is_player_actor() {
-                let actor_pos = actor.get_transform().get_position();
-                let distance = (actor_pos - player_actor_pos).norm();
-                let ship = actor.get_ship();
-                let clamp: bool = true;
-                let screen_pos: Vector2<f32> = main_camera.convert_world_to_screen(actor_pos, clamp);
-                let target_widget = ptr_as_mut(target_hud._widget).get_ui_component_mut();
-                target_widget.set_center(screen_pos.x, screen_pos.y);
-
-                let target_distance = ptr_as_mut(target_hud._distance).get_ui_component_mut();
-                target_distance.set_text(&format!("{}m", distance as i32));
-
-                target_hud._hull_point_widget.update_hull_point_widget(ship.get_hull_point() / 2.0, ship.get_max_hull_point());
-                target_hud._shield_point_widget.update_shield_point_widget(ship.get_shield_point() / 2.0, ship.get_max_shield_point());
-                break;
-            }
+        self.update_target_huds(game_client);
+
+        // Minimap
+        self._minimap.as_mut().unwrap().update_minimap(game_client);
+
+        // Scoreboard
+        self._scoreboard_widget.as_ref().unwrap().update_scoreboard_widget(game_client);
+
+        // Respawn
+        self._respawn_widget.as_ref().unwrap().update_respawn_widget(game_client);
+
+        // Production, the selection is released when the factory is destroyed
+        let production_widget = self._production_widget.as_mut().unwrap();
+        let selected_factory = match game_client.get_game_controller().get_selected_factory() {
+            Some(factory_handle) => game_client.get_building_manager().get_building(factory_handle),
+            None => None,
+        };
+        match selected_factory {
+            Some(factory) if factory.is_factory() && false == factory.is_destroyed() => {
+                production_widget.update_production_widget(game_client, factory);
+            },
+            _ => {
+                game_client.get_game_controller_mut().clear_selected_factory();
+                production_widget.hide_production_widget();
+            },
         }
     }
 }
\ No newline at end of file
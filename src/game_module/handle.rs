@@ -0,0 +1,217 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+// the index of the slot and the generation of it, the handle of a removed object never finds the new object of the same slot.
+pub struct Handle<T: ?Sized> {
+    pub _index: u32,
+    pub _generation: u32,
+    pub _phantom: PhantomData<*const T>,
+}
+
+struct HandleSlot<T: ?Sized> {
+    _generation: u32,
+    _value: Option<Box<T>>,
+}
+
+// the objects are boxed, so the references stay valid until the object is removed.
+pub struct HandleMap<T: ?Sized> {
+    _slots: Vec<HandleSlot<T>>,
+    _free_indices: Vec<u32>,
+    _count: usize,
+}
+
+// Implementation
+impl<T: ?Sized> Clone for Handle<T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T: ?Sized> Copy for Handle<T> {}
+
+impl<T: ?Sized> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self._index == other._index && self._generation == other._generation
+    }
+}
+
+impl<T: ?Sized> Eq for Handle<T> {}
+
+impl<T: ?Sized> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self._index.hash(state);
+        self._generation.hash(state);
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({}:{})", self._index, self._generation)
+    }
+}
+
+impl<T: ?Sized> Handle<T> {
+    pub fn new(index: u32, generation: u32) -> Handle<T> {
+        Handle {
+            _index: index,
+            _generation: generation,
+            _phantom: PhantomData,
+        }
+    }
+    pub fn get_index(&self) -> u32 { self._index }
+    pub fn get_generation(&self) -> u32 { self._generation }
+    // the id for the scripts, the network and the logs
+    pub fn to_id(&self) -> u64 {
+        ((self._generation as u64) << 32) | (self._index as u64)
+    }
+    pub fn from_id(id: u64) -> Handle<T> {
+        Handle::new(id as u32, (id >> 32) as u32)
+    }
+}
+
+impl<T: ?Sized> Default for HandleMap<T> {
+    fn default() -> HandleMap<T> {
+        HandleMap::new()
+    }
+}
+
+impl<T: ?Sized> HandleMap<T> {
+    pub fn new() -> HandleMap<T> {
+        HandleMap {
+            _slots: Vec::new(),
+            _free_indices: Vec::new(),
+            _count: 0,
+        }
+    }
+    pub fn len(&self) -> usize { self._count }
+    pub fn is_empty(&self) -> bool { 0 == self._count }
+
+    pub fn insert(&mut self, value: Box<T>) -> Handle<T> {
+        self._count += 1;
+        match self._free_indices.pop() {
+            Some(index) => {
+                let slot = &mut self._slots[index as usize];
+                slot._value = Some(value);
+                Handle::new(index, slot._generation)
+            },
+            None => {
+                let index = self._slots.len() as u32;
+                self._slots.push(HandleSlot { _generation: 0, _value: Some(value) });
+                Handle::new(index, 0)
+            },
+        }
+    }
+
+    // the generation of the slot is increased, the handles of the removed object become stale.
+    pub fn remove(&mut self, handle: Handle<T>) -> Option<Box<T>> {
+        let slot = self._slots.get_mut(handle._index as usize)?;
+        if slot._generation != handle._generation || slot._value.is_none() {
+            return None;
+        }
+        slot._generation = slot._generation.wrapping_add(1);
+        self._free_indices.push(handle._index);
+        self._count -= 1;
+        slot._value.take()
+    }
+
+    pub fn clear(&mut self) {
+        self._free_indices.clear();
+        for (index, slot) in self._slots.iter_mut().enumerate() {
+            if slot._value.take().is_some() {
+                slot._generation = slot._generation.wrapping_add(1);
+            }
+            self._free_indices.push(index as u32);
+        }
+        self._count = 0;
+    }
+
+    pub fn contains(&self, handle: Handle<T>) -> bool {
+        self.get(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle<T>) -> Option<&T> {
+        match self._slots.get(handle._index as usize) {
+            Some(slot) if slot._generation == handle._generation => slot._value.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        match self._slots.get_mut(handle._index as usize) {
+            Some(slot) if slot._generation == handle._generation => slot._value.as_deref_mut(),
+            _ => None,
+        }
+    }
+
+    pub fn handles(&self) -> Vec<Handle<T>> {
+        self.iter().map(|(handle, _)| handle).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self._slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot._value.as_deref().map(|value| (Handle::new(index as u32, slot._generation), value))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Handle<T>, &mut T)> {
+        self._slots.iter_mut().enumerate().filter_map(|(index, slot)| {
+            let generation = slot._generation;
+            slot._value.as_deref_mut().map(|value| (Handle::new(index as u32, generation), value))
+        })
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self._slots.iter().filter_map(|slot| slot._value.as_deref())
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self._slots.iter_mut().filter_map(|slot| slot._value.as_deref_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_handle_after_reuse() {
+        let mut handle_map: HandleMap<i32> = HandleMap::new();
+        let handle_a = handle_map.insert(Box::new(1));
+        assert_eq!(Some(1), handle_map.remove(handle_a).map(|value| *value));
+
+        // the new object takes the slot of the removed object
+        let handle_b = handle_map.insert(Box::new(2));
+        assert_eq!(handle_a.get_index(), handle_b.get_index());
+        assert_ne!(handle_a, handle_b);
+        assert!(handle_map.get(handle_a).is_none());
+        assert!(handle_map.get_mut(handle_a).is_none());
+        assert!(handle_map.remove(handle_a).is_none());
+        assert_eq!(Some(&2), handle_map.get(handle_b));
+        assert_eq!(1, handle_map.len());
+    }
+
+    #[test]
+    fn test_clear_invalidates_handles() {
+        let mut handle_map: HandleMap<i32> = HandleMap::new();
+        let handles: Vec<Handle<i32>> = (0..3).map(|value| handle_map.insert(Box::new(value))).collect();
+        handle_map.clear();
+        assert!(handle_map.is_empty());
+        for handle in handles.iter() {
+            assert!(handle_map.get(*handle).is_none());
+        }
+
+        // the cleared slots are reused with the new generations
+        let handle = handle_map.insert(Box::new(3));
+        assert!(handles.iter().all(|old_handle| *old_handle != handle));
+        assert_eq!(Some(&3), handle_map.get(handle));
+    }
+
+    #[test]
+    fn test_id_round_trip() {
+        let handle: Handle<i32> = Handle::new(7, 3);
+        assert_eq!(handle, Handle::from_id(handle.to_id()));
+
+        let handle: Handle<i32> = Handle::new(u32::MAX, u32::MAX);
+        assert_eq!(handle, Handle::from_id(handle.to_id()));
+        assert_eq!(u64::MAX, handle.to_id());
+    }
+}
@@ -3,6 +3,8 @@ use nalgebra::{Vector2, Vector3};
 use rust_engine_3d::utilities::math::lerp;
 use rust_engine_3d::utilities::bounding_box::BoundingBox;
 
+const LINE_OF_SIGHT_TOLERANCE: f32 = 1.0;
+
 #[derive(Clone)]
 pub struct HeightMapData {
     _sea_height: f32,
@@ -11,6 +13,8 @@ pub struct HeightMapData {
     _width: Vec<i32>,
     _height: Vec<i32>,
     _min_height_map_data: Vec<Vec<f32>>,
+    _texture_pixels: Vec<u8>,
+    _dirty_region: Option<(Vector2<i32>, Vector2<i32>)>,
 }
 
 impl Default for HeightMapData {
@@ -22,6 +26,8 @@ impl Default for HeightMapData {
             _width: Vec::new(),
             _height: Vec::new(),
             _min_height_map_data: Vec::new(),
+            _texture_pixels: Vec::new(),
+            _dirty_region: None,
         }
     }
 }
@@ -45,9 +51,14 @@ impl HeightMapData {
             }
         }
         self._min_height_map_data.push(lod_height_map_data);
+        self._texture_pixels = height_map_data;
+        self._dirty_region = None;
         self.generate_hiz_max();
     }
 
+    pub fn get_sea_height(&self) -> f32 { self._sea_height }
+    pub fn get_bounding_box(&self) -> &BoundingBox { &self._bounding_box }
+
     pub fn generate_hiz_max(&mut self) {
         for _ in 1..self._lod_count {
             let width = *self._width.last().unwrap() as i32;
@@ -72,6 +83,101 @@ impl HeightMapData {
         }
     }
 
+    // lower the heights in the radius with a smooth falloff, the hiz lods of the dirty region are rebuilt.
+    pub fn add_crater(&mut self, pos: &Vector3<f32>, radius: f32, depth: f32) {
+        if self._lod_count <= 0 || radius <= 0.0 || depth <= 0.0 {
+            return;
+        }
+
+        let width = self._width[0];
+        let height = self._height[0];
+        let texel_size: Vector2<f32> = self.get_texel_size(0);
+        let center: Vector2<f32> = Vector2::new(
+            (pos.x - self._bounding_box._min.x) / texel_size.x,
+            (pos.z - self._bounding_box._min.z) / texel_size.y
+        );
+        let region_min: Vector2<i32> = Vector2::new(
+            0.max((center.x - radius / texel_size.x).floor() as i32),
+            0.max((center.y - radius / texel_size.y).floor() as i32)
+        );
+        let region_max: Vector2<i32> = Vector2::new(
+            (width - 1).min((center.x + radius / texel_size.x).ceil() as i32),
+            (height - 1).min((center.y + radius / texel_size.y).ceil() as i32)
+        );
+        if region_max.x < region_min.x || region_max.y < region_min.y {
+            return;
+        }
+
+        for y in region_min.y..=region_max.y {
+            for x in region_min.x..=region_max.x {
+                let offset_x = (x as f32 - center.x) * texel_size.x;
+                let offset_z = (y as f32 - center.y) * texel_size.y;
+                let distance_ratio = (offset_x * offset_x + offset_z * offset_z).sqrt() / radius;
+                if 1.0 <= distance_ratio {
+                    continue;
+                }
+                let falloff = 1.0 - distance_ratio * distance_ratio;
+                let pixel_index = (y * width + x) as usize;
+                let crater_height = 0f32.max(self._min_height_map_data[0][pixel_index] - depth * falloff);
+                self._min_height_map_data[0][pixel_index] = crater_height;
+                self.update_texel_height(x, y);
+            }
+        }
+
+        self.add_dirty_region(&region_min, &region_max);
+        self.update_hiz_max_region(&region_min, &region_max);
+    }
+
+    fn add_dirty_region(&mut self, region_min: &Vector2<i32>, region_max: &Vector2<i32>) {
+        self._dirty_region = match self._dirty_region {
+            Some((dirty_min, dirty_max)) => Some((dirty_min.inf(region_min), dirty_max.sup(region_max))),
+            None => Some((region_min.clone_owned(), region_max.clone_owned())),
+        };
+    }
+
+    // the texture keeps the heights only, it is sampled by the stage material.
+    fn update_texel_height(&mut self, x: i32, y: i32) {
+        let pixel_index = (y * self._width[0] + x) as usize;
+        let height_ratio = self._min_height_map_data[0][pixel_index] / self._bounding_box._size.y;
+        let texel_height = (height_ratio * 255.0).round().clamp(0.0, 255.0) as u8;
+        self._texture_pixels[pixel_index * 4] = texel_height;
+        self._texture_pixels[pixel_index * 4 + 1] = texel_height;
+        self._texture_pixels[pixel_index * 4 + 2] = texel_height;
+    }
+
+    // same reduction as generate_hiz_max, only for the texels covering the region of lod 0.
+    pub fn update_hiz_max_region(&mut self, region_min: &Vector2<i32>, region_max: &Vector2<i32>) {
+        let mut lod_region_min: Vector2<i32> = region_min.clone_owned();
+        let mut lod_region_max: Vector2<i32> = region_max.clone_owned();
+        for lod in 1..self._lod_count as usize {
+            let prev_width = self._width[lod - 1];
+            let width = self._width[lod];
+            let height = self._height[lod];
+            lod_region_min = lod_region_min / 2;
+            lod_region_max = Vector2::new((width - 1).min(lod_region_max.x / 2), (height - 1).min(lod_region_max.y / 2));
+            for y in lod_region_min.y..=lod_region_max.y {
+                for x in lod_region_min.x..=lod_region_max.x {
+                    let tex_coord_0 = (y * 2 * prev_width + x * 2) as usize;
+                    let tex_coord_1 = ((y * 2 + 1) * prev_width + x * 2) as usize;
+                    let last_height_map_data = &self._min_height_map_data[lod - 1];
+                    let height_00 = last_height_map_data[tex_coord_0];
+                    let height_01 = last_height_map_data[tex_coord_0 + 1];
+                    let height_10 = last_height_map_data[tex_coord_1];
+                    let height_11 = last_height_map_data[tex_coord_1 + 1];
+                    let min_height = height_00.max(height_01.max(height_10.max(height_11)));
+                    self._min_height_map_data[lod][(y * width + x) as usize] = min_height;
+                }
+            }
+        }
+    }
+
+    pub fn get_texture_pixels(&self) -> &Vec<u8> { &self._texture_pixels }
+
+    // returns the modified texel region of lod 0 since the last call, (min, max) inclusive.
+    pub fn take_dirty_region(&mut self) -> Option<(Vector2<i32>, Vector2<i32>)> {
+        self._dirty_region.take()
+    }
+
     pub fn get_height_bilinear_by_texcoord(&self, texcoord: &Vector2<f32>, lod: usize) -> f32 {
         let lod = lod.min(self._lod_count as usize - 1);
         let width = self._width[lod];
@@ -95,6 +201,15 @@ impl HeightMapData {
         self._sea_height.max(height as f32)
     }
 
+    // the world size of one texel, the first and the last texels are on the edges of the bounding box.
+    pub fn get_texel_size(&self, lod: usize) -> Vector2<f32> {
+        let lod = lod.min(self._lod_count as usize - 1);
+        Vector2::new(
+            self._bounding_box._size.x / 1.max(self._width[lod] - 1) as f32,
+            self._bounding_box._size.z / 1.max(self._height[lod] - 1) as f32
+        )
+    }
+
     pub fn get_height_bilinear(&self, pos: &Vector3<f32>, lod: usize) -> f32 {
         let texcoord: Vector2<f32> = Vector2::new(
             (pos.x - &self._bounding_box._min.x) / self._bounding_box._size.x,
@@ -103,6 +218,23 @@ impl HeightMapData {
         self.get_height_bilinear_by_texcoord(&texcoord, lod)
     }
 
+    // central difference of the bilinear height with a step of one texel
+    pub fn get_normal_bilinear(&self, pos: &Vector3<f32>, lod: usize) -> Vector3<f32> {
+        let lod = lod.min(self._lod_count as usize - 1);
+        let texel_size: Vector2<f32> = self.get_texel_size(lod);
+        let step_x = texel_size.x;
+        let step_z = texel_size.y;
+        let height_l = self.get_height_bilinear(&Vector3::new(pos.x - step_x, pos.y, pos.z), lod);
+        let height_r = self.get_height_bilinear(&Vector3::new(pos.x + step_x, pos.y, pos.z), lod);
+        let height_b = self.get_height_bilinear(&Vector3::new(pos.x, pos.y, pos.z - step_z), lod);
+        let height_f = self.get_height_bilinear(&Vector3::new(pos.x, pos.y, pos.z + step_z), lod);
+        Vector3::new(
+            (height_l - height_r) / (2.0 * step_x),
+            1.0,
+            (height_b - height_f) / (2.0 * step_z)
+        ).normalize()
+    }
+
     pub fn get_height_point_by_texcoord(&self, texcoord: &Vector2<f32>, lod: usize) -> f32 {
         let lod = lod.min(self._lod_count as usize - 1);
         let width = self._width[lod];
@@ -122,6 +254,35 @@ impl HeightMapData {
         self.get_height_point_by_texcoord(&texcoord, lod)
     }
 
+    // marches the segment one texel at a time along the major axis of the xz plane, it is blocked when it passes under the terrain.
+    pub fn is_line_of_sight_blocked(&self, start_pos: &Vector3<f32>, end_pos: &Vector3<f32>) -> bool {
+        let to_end: Vector3<f32> = end_pos - start_pos;
+        let distance = to_end.norm();
+        if self._lod_count <= 0 || distance <= LINE_OF_SIGHT_TOLERANCE {
+            return false;
+        }
+
+        // the vertical line has no terrain between the points
+        let dir: Vector3<f32> = to_end / distance;
+        let max_dir: f32 = dir.x.abs().max(dir.z.abs());
+        if dir.x.abs() + dir.z.abs() <= f32::EPSILON {
+            return false;
+        }
+
+        let texel_size: Vector2<f32> = self.get_texel_size(0);
+        let step_distance: f32 = texel_size.x.min(texel_size.y) / max_dir;
+        let end_distance: f32 = distance - LINE_OF_SIGHT_TOLERANCE;
+        let mut marched_distance: f32 = step_distance;
+        while marched_distance < end_distance {
+            let point: Vector3<f32> = start_pos + dir * marched_distance;
+            if point.y < self.get_height_bilinear(&point, 0) {
+                return true;
+            }
+            marched_distance += step_distance;
+        }
+        false
+    }
+
     pub fn get_collision_point(&self, start_pos: &Vector3<f32>, dir: &Vector3<f32>, mut limit_dist: f32, collision_point: &mut Vector3<f32>) -> bool {
         let max_size: f32 = self._bounding_box._size.x.max(self._bounding_box._size.z);
         if limit_dist < 0.0 {
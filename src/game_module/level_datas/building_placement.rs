@@ -0,0 +1,24 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BuildingPlacementData {
+    pub _building_data_name: String,
+    pub _position: Vector3<f32>,
+    pub _rotation: Vector3<f32>,
+    pub _scale: Vector3<f32>,
+    pub _is_player_building: bool,
+}
+
+impl Default for BuildingPlacementData {
+    fn default() -> BuildingPlacementData {
+        BuildingPlacementData {
+            _building_data_name: "".to_string(),
+            _position: Vector3::zeros(),
+            _rotation: Vector3::zeros(),
+            _scale: Vector3::new(1.0, 1.0, 1.0),
+            _is_player_building: false,
+        }
+    }
+}
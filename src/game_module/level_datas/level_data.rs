@@ -1,11 +1,24 @@
 use serde::{ Serialize, Deserialize };
 
+use crate::game_module::level_datas::building_placement::BuildingPlacementData;
+use crate::game_module::level_datas::pickup_placement::PickupPlacementData;
+use crate::game_module::level_datas::resource_placement::{ResourceNodePlacementData, ResourceDepotPlacementData};
 use crate::game_module::level_datas::spawn_point::{ SpawnPointType, ShipSpawnPointData };
+use crate::game_module::level_datas::zone_data::ZoneData;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(default)]
 pub struct LevelData {
     pub _spawn_point_datas: Vec<SpawnPointType>,
+    pub _building_placement_datas: Vec<BuildingPlacementData>,
+    pub _pickup_placement_datas: Vec<PickupPlacementData>,
+    pub _resource_node_placement_datas: Vec<ResourceNodePlacementData>,
+    pub _resource_depot_placement_datas: Vec<ResourceDepotPlacementData>,
+    pub _zone_datas: Vec<ZoneData>,
+    // the script names in resources/scripts, the hooks of every script are called
+    pub _script_names: Vec<String>,
+    // the game mode data in game_datas/game_modes, empty is the skirmish
+    pub _game_mode_data_name: String,
 }
 
 impl LevelData {
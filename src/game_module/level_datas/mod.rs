@@ -1,2 +1,6 @@
+pub mod building_placement;
+pub mod pickup_placement;
+pub mod resource_placement;
 pub mod spawn_point;
-pub mod level_data;
\ No newline at end of file
+pub mod level_data;
+pub mod zone_data;
\ No newline at end of file
@@ -0,0 +1,18 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct PickupPlacementData {
+    pub _pickup_data_name: String,
+    pub _position: Vector3<f32>,
+}
+
+impl Default for PickupPlacementData {
+    fn default() -> PickupPlacementData {
+        PickupPlacementData {
+            _pickup_data_name: "".to_string(),
+            _position: Vector3::zeros(),
+        }
+    }
+}
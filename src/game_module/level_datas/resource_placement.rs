@@ -0,0 +1,50 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ResourceNodePlacementData {
+    pub _model_data_name: String,
+    pub _position: Vector3<f32>,
+    pub _rotation: Vector3<f32>,
+    pub _scale: Vector3<f32>,
+    pub _resource_amount: f32,
+    pub _harvest_radius: f32,
+}
+
+impl Default for ResourceNodePlacementData {
+    fn default() -> ResourceNodePlacementData {
+        ResourceNodePlacementData {
+            _model_data_name: "".to_string(),
+            _position: Vector3::zeros(),
+            _rotation: Vector3::zeros(),
+            _scale: Vector3::new(1.0, 1.0, 1.0),
+            _resource_amount: 500.0,
+            _harvest_radius: 5.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ResourceDepotPlacementData {
+    pub _model_data_name: String,
+    pub _position: Vector3<f32>,
+    pub _rotation: Vector3<f32>,
+    pub _scale: Vector3<f32>,
+    pub _is_player_depot: bool,
+    pub _unload_radius: f32,
+}
+
+impl Default for ResourceDepotPlacementData {
+    fn default() -> ResourceDepotPlacementData {
+        ResourceDepotPlacementData {
+            _model_data_name: "".to_string(),
+            _position: Vector3::zeros(),
+            _rotation: Vector3::zeros(),
+            _scale: Vector3::new(1.0, 1.0, 1.0),
+            _is_player_depot: true,
+            _unload_radius: 8.0,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+use crate::game_module::level_datas::spawn_point::SpawnPointType;
+use crate::game_module::ship::ship::ShipDataType;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ZoneShape {
+    // the full size of the axis aligned box around the position
+    Box { _size: Vector3<f32> },
+    Sphere { _radius: f32 },
+    // the vertical cylinder from the position up to the height
+    Cylinder { _radius: f32, _height: f32 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ZoneTeamFilter {
+    Any,
+    Player,
+    Enemy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ZoneData {
+    pub _zone_name: String,
+    pub _position: Vector3<f32>,
+    pub _shape: ZoneShape,
+    pub _team_filter: ZoneTeamFilter,
+    // empty is every ship type
+    pub _ship_types: Vec<ShipDataType>,
+    // the hazard damages the actors inside
+    pub _hull_damage_per_second: f32,
+    pub _shield_damage_per_second: f32,
+    // the objective is marked on the minimap until the player enters
+    pub _objective_message: String,
+    // the wave is spawned once when the first actor enters
+    pub _wave_spawn_point_datas: Vec<SpawnPointType>,
+}
+
+impl Default for ZoneShape {
+    fn default() -> ZoneShape {
+        ZoneShape::Sphere { _radius: 10.0 }
+    }
+}
+
+impl Default for ZoneTeamFilter {
+    fn default() -> ZoneTeamFilter {
+        ZoneTeamFilter::Any
+    }
+}
+
+impl Default for ZoneData {
+    fn default() -> ZoneData {
+        ZoneData {
+            _zone_name: "".to_string(),
+            _position: Vector3::zeros(),
+            _shape: ZoneShape::default(),
+            _team_filter: ZoneTeamFilter::default(),
+            _ship_types: Vec::new(),
+            _hull_damage_per_second: 0.0,
+            _shield_damage_per_second: 0.0,
+            _objective_message: "".to_string(),
+            _wave_spawn_point_datas: Vec::new(),
+        }
+    }
+}
+
+impl ZoneData {
+    pub fn contains_point(&self, point: &Vector3<f32>) -> bool {
+        let offset: Vector3<f32> = point - &self._position;
+        match &self._shape {
+            ZoneShape::Box { _size } => offset.x.abs() <= _size.x * 0.5 && offset.y.abs() <= _size.y * 0.5 && offset.z.abs() <= _size.z * 0.5,
+            ZoneShape::Sphere { _radius } => offset.norm() <= *_radius,
+            ZoneShape::Cylinder { _radius, _height } => {
+                0.0 <= offset.y && offset.y <= *_height && (offset.x * offset.x + offset.z * offset.z) <= _radius * _radius
+            },
+        }
+    }
+
+    pub fn is_accepted(&self, is_player_team: bool, ship_type: ShipDataType) -> bool {
+        let is_team_accepted = match self._team_filter {
+            ZoneTeamFilter::Any => true,
+            ZoneTeamFilter::Player => is_player_team,
+            ZoneTeamFilter::Enemy => false == is_player_team,
+        };
+        is_team_accepted && (self._ship_types.is_empty() || self._ship_types.contains(&ship_type))
+    }
+
+    pub fn is_hazard(&self) -> bool {
+        0.0 < self._hull_damage_per_second || 0.0 < self._shield_damage_per_second
+    }
+
+    pub fn is_objective(&self) -> bool {
+        false == self._objective_message.is_empty()
+    }
+}
@@ -1,13 +1,31 @@
+pub mod camera_shake;
 pub mod game_constants;
 pub mod game_controller;
 pub mod game_client;
+pub mod game_mode_manager;
+pub mod game_modes;
+pub mod game_options;
 pub mod game_states;
 pub mod level_datas;
 pub mod game_ui;
+pub mod handle;
 pub mod height_map_data;
 pub mod actors;
 pub mod ship;
 pub mod weapons;
 pub mod actor_manager;
+pub mod building_manager;
+pub mod buildings;
+pub mod economy;
+pub mod economy_manager;
+pub mod fog_of_war_manager;
+pub mod network;
+pub mod network_manager;
+pub mod pickup_manager;
+pub mod pickups;
+pub mod script_manager;
+pub mod scripts;
 pub mod weapon_manager;
+pub mod zone_manager;
+pub mod zones;
 pub mod ui_widgets;
@@ -0,0 +1,2 @@
+pub mod packet;
+pub mod snapshot;
\ No newline at end of file
@@ -0,0 +1,74 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+use serde::de::DeserializeOwned;
+
+use crate::game_module::network::snapshot::{ActorSnapshotDelta, BulletSnapshot, TeamScoreSnapshot};
+use crate::game_module::ship::ship_controller::ShipControllerInput;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct FireCommand {
+    pub _fire_start: Vector3<f32>,
+    pub _fire_dir: Vector3<f32>,
+    pub _target_position: Vector3<f32>,
+}
+
+// the input of one client frame, the server applies it with the same delta time.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct InputCommand {
+    pub _sequence: u32,
+    pub _delta_time: f32,
+    pub _controller_input: ShipControllerInput,
+    pub _fire_commands: Vec<FireCommand>,
+    pub _reload: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ClientPacket {
+    Connect { _protocol_version: u32 },
+    // the last inputs are sent again against the packet loss, the acked sequence is the baseline of the next snapshot.
+    Input { _acked_sequence: Option<u32>, _inputs: Vec<InputCommand> },
+    Disconnect,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ServerPacket {
+    // the team of the client, the player team of the server or the other team
+    Accept { _client_id: u32, _actor_id: u64, _is_server_team: bool },
+    Reject { _reason: String },
+    Snapshot {
+        _sequence: u32,
+        _baseline_sequence: Option<u32>,
+        _server_time: f32,
+        _last_processed_input: u32,
+        // the ship of the client, None while it waits for the respawn
+        _actor_id: Option<u64>,
+        _respawn_time: Option<f32>,
+        _actor_deltas: Vec<ActorSnapshotDelta>,
+        _removed_actor_ids: Vec<u64>,
+        _bullets: Vec<BulletSnapshot>,
+        _team_scores: TeamScoreSnapshot,
+    },
+}
+
+// Implementation
+pub fn encode_packet<T: Serialize>(packet: &T) -> Option<Vec<u8>> {
+    match serde_json::to_vec(packet) {
+        Ok(bytes) => Some(bytes),
+        Err(error) => {
+            log::error!("encode_packet failed: {:?}", error);
+            None
+        },
+    }
+}
+
+pub fn decode_packet<T: DeserializeOwned>(bytes: &[u8]) -> Option<T> {
+    match serde_json::from_slice(bytes) {
+        Ok(packet) => Some(packet),
+        Err(error) => {
+            log::error!("decode_packet failed: {:?}", error);
+            None
+        },
+    }
+}
@@ -0,0 +1,334 @@
+use nalgebra::{Vector2, Vector3};
+use serde::{ Serialize, Deserialize };
+
+use rust_engine_3d::utilities::math;
+use crate::game_module::game_constants::NETWORK_DELTA_EPSILON;
+use crate::game_module::ship::ship_controller::ShipControllerState;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ActorSnapshot {
+    pub _actor_id: u64,
+    pub _ship_data_name: String,
+    pub _is_server_team: bool,
+    pub _controller_state: ShipControllerState,
+    pub _hull: f32,
+    pub _shields: f32,
+}
+
+// the changed fields against the baseline snapshot, a new actor has every field.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ActorSnapshotDelta {
+    pub _actor_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _ship_data_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _is_server_team: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _controller_state: Option<ShipControllerState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _hull: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub _shields: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct BulletSnapshot {
+    pub _bullet_id: u64,
+    pub _model_data_name: String,
+    pub _position: Vector3<f32>,
+    pub _rotation: Vector3<f32>,
+}
+
+// the scores of the game mode, the server team is the player team of the server.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct TeamScoreSnapshot {
+    pub _server_team_score: f32,
+    pub _other_team_score: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorldSnapshot {
+    pub _sequence: u32,
+    pub _server_time: f32,
+    pub _actors: Vec<ActorSnapshot>,
+    pub _bullets: Vec<BulletSnapshot>,
+    pub _team_scores: TeamScoreSnapshot,
+}
+
+// Implementation
+fn is_vector2_changed(a: &Vector2<f32>, b: &Vector2<f32>) -> bool {
+    NETWORK_DELTA_EPSILON < (a - b).abs().max()
+}
+
+fn is_vector3_changed(a: &Vector3<f32>, b: &Vector3<f32>) -> bool {
+    NETWORK_DELTA_EPSILON < (a - b).abs().max()
+}
+
+fn is_value_changed(a: f32, b: f32) -> bool {
+    NETWORK_DELTA_EPSILON < (a - b).abs()
+}
+
+fn is_controller_state_changed(a: &ShipControllerState, b: &ShipControllerState) -> bool {
+    is_vector3_changed(&a._position, &b._position) ||
+        is_vector3_changed(&a._rotation, &b._rotation) ||
+        is_vector3_changed(&a._velocity, &b._velocity) ||
+        is_vector2_changed(&a._rotation_velocity, &b._rotation_velocity) ||
+        is_vector2_changed(&a._terrain_rotation, &b._terrain_rotation) ||
+        is_vector2_changed(&a._terrain_rotation_velocity, &b._terrain_rotation_velocity) ||
+        is_value_changed(a._boost_fuel, b._boost_fuel) ||
+        is_value_changed(a._boost_fuel_regen_delay, b._boost_fuel_regen_delay) ||
+        a._on_ground != b._on_ground
+}
+
+// the shortest way around the circle
+pub fn lerp_angle(a: f32, b: f32, t: f32) -> f32 {
+    let mut diff = (b - a) % math::TWO_PI;
+    if std::f32::consts::PI < diff {
+        diff -= math::TWO_PI;
+    } else if diff < -std::f32::consts::PI {
+        diff += math::TWO_PI;
+    }
+    a + diff * t
+}
+
+pub fn lerp_controller_state(a: &ShipControllerState, b: &ShipControllerState, t: f32) -> ShipControllerState {
+    ShipControllerState {
+        _position: a._position.lerp(&b._position, t),
+        _rotation: Vector3::new(
+            lerp_angle(a._rotation.x, b._rotation.x, t),
+            lerp_angle(a._rotation.y, b._rotation.y, t),
+            lerp_angle(a._rotation.z, b._rotation.z, t)
+        ),
+        _velocity: a._velocity.lerp(&b._velocity, t),
+        _terrain_rotation: a._terrain_rotation.lerp(&b._terrain_rotation, t),
+        ..b.clone()
+    }
+}
+
+impl ActorSnapshotDelta {
+    // returns None when nothing is changed
+    pub fn make_actor_snapshot_delta(actor_snapshot: &ActorSnapshot, baseline: Option<&ActorSnapshot>) -> Option<ActorSnapshotDelta> {
+        let baseline = match baseline {
+            Some(baseline) => baseline,
+            None => return Some(ActorSnapshotDelta {
+                _actor_id: actor_snapshot._actor_id,
+                _ship_data_name: Some(actor_snapshot._ship_data_name.clone()),
+                _is_server_team: Some(actor_snapshot._is_server_team),
+                _controller_state: Some(actor_snapshot._controller_state.clone()),
+                _hull: Some(actor_snapshot._hull),
+                _shields: Some(actor_snapshot._shields),
+            }),
+        };
+
+        let mut is_changed = false;
+        let mut delta = ActorSnapshotDelta {
+            _actor_id: actor_snapshot._actor_id,
+            ..Default::default()
+        };
+        if actor_snapshot._ship_data_name != baseline._ship_data_name {
+            delta._ship_data_name = Some(actor_snapshot._ship_data_name.clone());
+            is_changed = true;
+        }
+        if actor_snapshot._is_server_team != baseline._is_server_team {
+            delta._is_server_team = Some(actor_snapshot._is_server_team);
+            is_changed = true;
+        }
+        if is_controller_state_changed(&actor_snapshot._controller_state, &baseline._controller_state) {
+            delta._controller_state = Some(actor_snapshot._controller_state.clone());
+            is_changed = true;
+        }
+        if is_value_changed(actor_snapshot._hull, baseline._hull) {
+            delta._hull = Some(actor_snapshot._hull);
+            is_changed = true;
+        }
+        if is_value_changed(actor_snapshot._shields, baseline._shields) {
+            delta._shields = Some(actor_snapshot._shields);
+            is_changed = true;
+        }
+        if is_changed { Some(delta) } else { None }
+    }
+
+    pub fn apply_actor_snapshot_delta(&self, actor_snapshot: &mut ActorSnapshot) {
+        actor_snapshot._actor_id = self._actor_id;
+        if let Some(ship_data_name) = &self._ship_data_name {
+            actor_snapshot._ship_data_name.clone_from(ship_data_name);
+        }
+        if let Some(is_server_team) = self._is_server_team {
+            actor_snapshot._is_server_team = is_server_team;
+        }
+        if let Some(controller_state) = &self._controller_state {
+            actor_snapshot._controller_state.clone_from(controller_state);
+        }
+        if let Some(hull) = self._hull {
+            actor_snapshot._hull = hull;
+        }
+        if let Some(shields) = self._shields {
+            actor_snapshot._shields = shields;
+        }
+    }
+}
+
+impl WorldSnapshot {
+    pub fn get_actor_snapshot(&self, actor_id: u64) -> Option<&ActorSnapshot> {
+        self._actors.iter().find(|actor_snapshot| actor_id == actor_snapshot._actor_id)
+    }
+
+    pub fn get_bullet_snapshot(&self, bullet_id: u64) -> Option<&BulletSnapshot> {
+        self._bullets.iter().find(|bullet_snapshot| bullet_id == bullet_snapshot._bullet_id)
+    }
+
+    // returns the changed actors and the removed actor ids against the baseline
+    pub fn make_delta(&self, baseline: Option<&WorldSnapshot>) -> (Vec<ActorSnapshotDelta>, Vec<u64>) {
+        let actor_deltas: Vec<ActorSnapshotDelta> = self._actors.iter().filter_map(|actor_snapshot| {
+            let baseline_actor = baseline.and_then(|baseline| baseline.get_actor_snapshot(actor_snapshot._actor_id));
+            ActorSnapshotDelta::make_actor_snapshot_delta(actor_snapshot, baseline_actor)
+        }).collect();
+
+        let removed_actor_ids: Vec<u64> = match baseline {
+            Some(baseline) => baseline._actors.iter()
+                .filter(|baseline_actor| self.get_actor_snapshot(baseline_actor._actor_id).is_none())
+                .map(|baseline_actor| baseline_actor._actor_id)
+                .collect(),
+            None => Vec::new(),
+        };
+        (actor_deltas, removed_actor_ids)
+    }
+
+    pub fn apply_delta(
+        sequence: u32,
+        server_time: f32,
+        baseline: Option<&WorldSnapshot>,
+        actor_deltas: &[ActorSnapshotDelta],
+        removed_actor_ids: &[u64],
+        bullets: &[BulletSnapshot],
+        team_scores: &TeamScoreSnapshot
+    ) -> WorldSnapshot {
+        let mut actors: Vec<ActorSnapshot> = match baseline {
+            Some(baseline) => baseline._actors.iter().filter(|actor_snapshot| false == removed_actor_ids.contains(&actor_snapshot._actor_id)).cloned().collect(),
+            None => Vec::new(),
+        };
+        for actor_delta in actor_deltas.iter() {
+            match actors.iter_mut().find(|actor_snapshot| actor_delta._actor_id == actor_snapshot._actor_id) {
+                Some(actor_snapshot) => actor_delta.apply_actor_snapshot_delta(actor_snapshot),
+                None => {
+                    let mut actor_snapshot = ActorSnapshot::default();
+                    actor_delta.apply_actor_snapshot_delta(&mut actor_snapshot);
+                    actors.push(actor_snapshot);
+                },
+            }
+        }
+
+        WorldSnapshot {
+            _sequence: sequence,
+            _server_time: server_time,
+            _actors: actors,
+            _bullets: bullets.to_vec(),
+            _team_scores: team_scores.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_actor_snapshot(actor_id: u64, position: Vector3<f32>, hull: f32) -> ActorSnapshot {
+        ActorSnapshot {
+            _actor_id: actor_id,
+            _ship_data_name: "default".to_string(),
+            _is_server_team: true,
+            _controller_state: ShipControllerState {
+                _position: position,
+                _rotation: Vector3::new(0.0, 1.0, 0.0),
+                ..Default::default()
+            },
+            _hull: hull,
+            _shields: 50.0,
+        }
+    }
+
+    fn make_world_snapshot(sequence: u32, actors: Vec<ActorSnapshot>) -> WorldSnapshot {
+        WorldSnapshot {
+            _sequence: sequence,
+            _server_time: sequence as f32 * 0.05,
+            _actors: actors,
+            _bullets: Vec::new(),
+            _team_scores: TeamScoreSnapshot {
+                _server_team_score: sequence as f32,
+                _other_team_score: 0.0,
+            },
+        }
+    }
+
+    fn rebuild(snapshot: &WorldSnapshot, baseline: Option<&WorldSnapshot>) -> WorldSnapshot {
+        let (actor_deltas, removed_actor_ids) = snapshot.make_delta(baseline);
+        WorldSnapshot::apply_delta(snapshot._sequence, snapshot._server_time, baseline, &actor_deltas, &removed_actor_ids, &snapshot._bullets, &snapshot._team_scores)
+    }
+
+    fn assert_snapshot_near(a: &WorldSnapshot, b: &WorldSnapshot) {
+        assert_eq!(a._team_scores, b._team_scores);
+        assert_eq!(a._actors.len(), b._actors.len());
+        for actor_b in b._actors.iter() {
+            let actor_a = a.get_actor_snapshot(actor_b._actor_id).expect("the actor is missing");
+            assert!((actor_a._controller_state._position - actor_b._controller_state._position).abs().max() <= NETWORK_DELTA_EPSILON);
+            assert!((actor_a._hull - actor_b._hull).abs() <= NETWORK_DELTA_EPSILON);
+            assert!((actor_a._shields - actor_b._shields).abs() <= NETWORK_DELTA_EPSILON);
+            assert_eq!(actor_a._ship_data_name, actor_b._ship_data_name);
+            assert_eq!(actor_a._is_server_team, actor_b._is_server_team);
+        }
+    }
+
+    #[test]
+    fn test_apply_delta_round_trip() {
+        let a = make_world_snapshot(1, vec![
+            make_actor_snapshot(1, Vector3::new(0.0, 0.0, 0.0), 100.0),
+            make_actor_snapshot(2, Vector3::new(10.0, 0.0, 0.0), 100.0),
+        ]);
+        let b = make_world_snapshot(2, vec![
+            make_actor_snapshot(1, Vector3::new(1.0, 0.0, 2.0), 80.0),
+            make_actor_snapshot(3, Vector3::new(5.0, 0.0, 5.0), 100.0),
+        ]);
+
+        // the full snapshot without the baseline
+        assert_snapshot_near(&rebuild(&a, None), &a);
+
+        // the changed, the removed and the new actors against the baseline
+        let rebuilt_b = rebuild(&b, Some(&a));
+        assert_snapshot_near(&rebuilt_b, &b);
+        assert!(rebuilt_b.get_actor_snapshot(2).is_none());
+    }
+
+    #[test]
+    fn test_apply_delta_slow_drift() {
+        // each step is under the epsilon, the rebuilt baseline keeps the drift from being lost.
+        let step = NETWORK_DELTA_EPSILON * 0.5;
+        let mut baseline = make_world_snapshot(0, vec![make_actor_snapshot(1, Vector3::zeros(), 100.0)]);
+        for sequence in 1..100u32 {
+            let snapshot = make_world_snapshot(sequence, vec![make_actor_snapshot(1, Vector3::new(step * sequence as f32, 0.0, 0.0), 100.0)]);
+            baseline = rebuild(&snapshot, Some(&baseline));
+            assert_snapshot_near(&baseline, &snapshot);
+        }
+    }
+
+    #[test]
+    fn test_lerp_angle() {
+        let pi = std::f32::consts::PI;
+        assert!((lerp_angle(0.0, 1.0, 0.5) - 0.5).abs() < 1e-5);
+
+        // across +pi, the short way is through pi instead of through 0
+        let angle = lerp_angle(pi - 0.1, -pi + 0.1, 0.5);
+        assert!((angle - pi).abs() < 1e-5);
+
+        // across -pi
+        let angle = lerp_angle(-pi + 0.1, pi - 0.1, 0.5);
+        assert!((angle + pi).abs() < 1e-5);
+
+        // the end points
+        assert!((lerp_angle(pi - 0.1, -pi + 0.1, 0.0) - (pi - 0.1)).abs() < 1e-5);
+        assert!((lerp_angle(pi - 0.1, -pi + 0.1, 1.0) - (pi + 0.1)).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,1023 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::ErrorKind;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use nalgebra::Vector3;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use rust_engine_3d::renderer::render_object::{RenderObjectData, RenderObjectCreateInfo};
+use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_ref};
+use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::actors::actor::ActorHandle;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::{
+    NETWORK_PROTOCOL_VERSION,
+    NETWORK_DEFAULT_PORT,
+    NETWORK_MAX_PACKET_SIZE,
+    NETWORK_SNAPSHOT_INTERVAL,
+    NETWORK_SNAPSHOT_HISTORY,
+    NETWORK_INTERPOLATION_DELAY,
+    NETWORK_INPUT_REDUNDANCY,
+    NETWORK_MAX_PENDING_INPUTS,
+    NETWORK_MAX_INPUT_DELTA_TIME,
+    NETWORK_CONNECT_RETRY_INTERVAL,
+    NETWORK_CONNECTION_TIMEOUT,
+    NETWORK_SPAWN_SPACING,
+    NETWORK_MAX_FIRE_START_DISTANCE,
+    CHECK_TARGET_DISTANCE_MAX,
+};
+use crate::game_module::level_datas::spawn_point::{SpawnPointType, ShipSpawnPointData};
+use crate::game_module::network::packet::{ClientPacket, ServerPacket, InputCommand, FireCommand, encode_packet, decode_packet};
+use crate::game_module::network::snapshot::{ActorSnapshot, ActorSnapshotDelta, BulletSnapshot, TeamScoreSnapshot, WorldSnapshot, lerp_controller_state};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NetworkMode {
+    Offline,
+    Server,
+    Client,
+}
+
+// the connection of a client on the server
+pub struct RemoteClient {
+    pub _client_id: u32,
+    pub _address: SocketAddr,
+    pub _actor_handle: ActorHandle,
+    pub _last_processed_input: u32,
+    pub _acked_sequence: Option<u32>,
+    pub _sent_snapshots: VecDeque<WorldSnapshot>,
+    pub _idle_time: f32,
+    pub _respawn_time: Option<f32>,
+}
+
+// the server runs the simulation and sends the snapshots, the client sends the inputs,
+// predicts the player ship and interpolates the other ships.
+pub struct NetworkManager {
+    pub _game_client: *const GameClient,
+    pub _network_mode: NetworkMode,
+    pub _socket: Option<UdpSocket>,
+    pub _elapsed_time: f32,
+    // server
+    pub _client_id_generator: u32,
+    pub _remote_clients: HashMap<SocketAddr, RemoteClient>,
+    pub _snapshot_sequence: u32,
+    pub _snapshot_time: f32,
+    // client
+    pub _server_address: Option<SocketAddr>,
+    pub _is_connected: bool,
+    pub _connect_time: f32,
+    pub _idle_time: f32,
+    pub _server_actor_id: u64,
+    pub _is_server_team: bool,
+    // the server actor id to the local actor
+    pub _actor_id_map: HashMap<u64, ActorHandle>,
+    pub _input_sequence: u32,
+    pub _pending_inputs: VecDeque<InputCommand>,
+    pub _pending_fire_commands: Vec<FireCommand>,
+    pub _pending_reload: bool,
+    pub _last_processed_input: u32,
+    pub _received_snapshots: VecDeque<WorldSnapshot>,
+    pub _interpolation_time: f32,
+    pub _network_bullets: HashMap<u64, RcRefCell<RenderObjectData>>,
+}
+
+// Implementation
+fn resolve_server_address(address: &str) -> Option<SocketAddr> {
+    let address = if address.contains(':') { address.to_string() } else { format!("{}:{}", address, NETWORK_DEFAULT_PORT) };
+    match address.to_socket_addrs() {
+        Ok(mut addresses) => addresses.next(),
+        Err(error) => {
+            log::error!("resolve_server_address failed: {} {:?}", address, error);
+            None
+        },
+    }
+}
+
+// the fire command of the client starts around its ship on the server, the target is not farther than the local fire.
+fn validate_fire_command(fire_command: &FireCommand, ship_position: &Vector3<f32>) -> Option<FireCommand> {
+    let fire_dir_length = fire_command._fire_dir.norm();
+    if false == fire_dir_length.is_finite() || fire_dir_length <= f32::EPSILON {
+        return None;
+    }
+    let fire_start_distance = (&fire_command._fire_start - ship_position).norm();
+    if false == (fire_start_distance <= NETWORK_MAX_FIRE_START_DISTANCE) {
+        return None;
+    }
+    let fire_dir: Vector3<f32> = &fire_command._fire_dir / fire_dir_length;
+    let target_distance = (&fire_command._target_position - &fire_command._fire_start).norm();
+    if false == target_distance.is_finite() {
+        return None;
+    }
+    Some(FireCommand {
+        _fire_start: fire_command._fire_start.clone_owned(),
+        _target_position: &fire_command._fire_start + &fire_dir * target_distance.min(CHECK_TARGET_DISTANCE_MAX),
+        _fire_dir: fire_dir,
+    })
+}
+
+// the bullets of the server are only rendered on the client
+fn update_network_bullets(
+    network_bullets: &mut HashMap<u64, RcRefCell<RenderObjectData>>,
+    project_scene_manager: &mut ProjectSceneManager,
+    from_snapshot: &WorldSnapshot,
+    to_snapshot: &WorldSnapshot,
+    t: f32
+) {
+    network_bullets.retain(|bullet_id, render_object| {
+        let is_alive = to_snapshot.get_bullet_snapshot(*bullet_id).is_some();
+        if false == is_alive {
+            project_scene_manager.remove_static_render_object(&render_object.borrow()._render_object_name);
+        }
+        is_alive
+    });
+
+    for bullet_snapshot in to_snapshot._bullets.iter() {
+        let position: Vector3<f32> = match from_snapshot.get_bullet_snapshot(bullet_snapshot._bullet_id) {
+            Some(from_bullet) => from_bullet._position.lerp(&bullet_snapshot._position, t),
+            None => bullet_snapshot._position.clone_owned(),
+        };
+        match network_bullets.get(&bullet_snapshot._bullet_id) {
+            Some(render_object) => {
+                let transform = &mut render_object.borrow_mut()._transform_object;
+                transform.set_position(&position);
+                transform.set_rotation(&bullet_snapshot._rotation);
+                transform.update_transform_object();
+            },
+            None => {
+                let render_object_create_info = RenderObjectCreateInfo {
+                    _model_data_name: bullet_snapshot._model_data_name.clone(),
+                    _position: position,
+                    _rotation: bullet_snapshot._rotation.clone_owned(),
+                    ..Default::default()
+                };
+                if let Some(render_object) = project_scene_manager.add_static_render_object("bullet", &render_object_create_info) {
+                    network_bullets.insert(bullet_snapshot._bullet_id, render_object);
+                }
+            },
+        }
+    }
+}
+
+impl NetworkManager {
+    pub fn create_network_manager() -> Box<NetworkManager> {
+        Box::new(NetworkManager {
+            _game_client: std::ptr::null(),
+            _network_mode: NetworkMode::Offline,
+            _socket: None,
+            _elapsed_time: 0.0,
+            _client_id_generator: 0,
+            _remote_clients: HashMap::new(),
+            _snapshot_sequence: 0,
+            _snapshot_time: 0.0,
+            _server_address: None,
+            _is_connected: false,
+            _connect_time: 0.0,
+            _idle_time: 0.0,
+            _server_actor_id: 0,
+            _is_server_team: false,
+            _actor_id_map: HashMap::new(),
+            _input_sequence: 0,
+            _pending_inputs: VecDeque::new(),
+            _pending_fire_commands: Vec::new(),
+            _pending_reload: false,
+            _last_processed_input: 0,
+            _received_snapshots: VecDeque::new(),
+            _interpolation_time: 0.0,
+            _network_bullets: HashMap::new(),
+        })
+    }
+
+    // --server [port] hosts the game, --connect <address[:port]> joins the server.
+    pub fn initialize_network_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+
+        let args: Vec<String> = std::env::args().collect();
+        let mut args_iter = args.iter().skip(1);
+        while let Some(arg) = args_iter.next() {
+            if "--server" == arg {
+                let port = args_iter.next().and_then(|port| port.parse::<u16>().ok()).unwrap_or(NETWORK_DEFAULT_PORT);
+                self.start_server(port);
+                break;
+            } else if "--connect" == arg {
+                let address = args_iter.next().map(|address| address.as_str()).unwrap_or("127.0.0.1");
+                self.start_client(address);
+                break;
+            }
+        }
+    }
+    pub fn destroy_network_manager(&mut self) {
+        if self._is_connected {
+            self.send_to_server(&ClientPacket::Disconnect);
+        }
+        self._remote_clients.clear();
+        self._actor_id_map.clear();
+        self._pending_inputs.clear();
+        self._pending_fire_commands.clear();
+        self._received_snapshots.clear();
+        self._network_bullets.clear();
+        self._is_connected = false;
+        self._socket = None;
+        self._network_mode = NetworkMode::Offline;
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_network_mode(&self) -> NetworkMode { self._network_mode }
+    pub fn is_server(&self) -> bool { NetworkMode::Server == self._network_mode }
+    pub fn is_client(&self) -> bool { NetworkMode::Client == self._network_mode }
+    pub fn is_connected(&self) -> bool { self._is_connected }
+
+    pub fn start_server(&mut self, port: u16) -> bool {
+        match UdpSocket::bind(("0.0.0.0", port)) {
+            Ok(socket) => {
+                socket.set_nonblocking(true).expect("set_nonblocking failed");
+                log::info!("start_server: port {}", port);
+                self._socket = Some(socket);
+                self._network_mode = NetworkMode::Server;
+                true
+            },
+            Err(error) => {
+                log::error!("start_server failed: port {} {:?}", port, error);
+                false
+            },
+        }
+    }
+
+    pub fn start_client(&mut self, address: &str) -> bool {
+        let server_address = match resolve_server_address(address) {
+            Some(server_address) => server_address,
+            None => return false,
+        };
+        match UdpSocket::bind(("0.0.0.0", 0)) {
+            Ok(socket) => {
+                socket.set_nonblocking(true).expect("set_nonblocking failed");
+                log::info!("start_client: server {}", server_address);
+                self._socket = Some(socket);
+                self._server_address = Some(server_address);
+                self._network_mode = NetworkMode::Client;
+                self._connect_time = 0.0;
+                true
+            },
+            Err(error) => {
+                log::error!("start_client failed: {:?}", error);
+                false
+            },
+        }
+    }
+
+    fn send_packet<T: Serialize>(&self, packet: &T, address: &SocketAddr) {
+        let socket = match &self._socket {
+            Some(socket) => socket,
+            None => return,
+        };
+        if let Some(bytes) = encode_packet(packet) {
+            if NETWORK_MAX_PACKET_SIZE < bytes.len() {
+                log::error!("send_packet: too large packet {} bytes", bytes.len());
+                return;
+            }
+            if let Err(error) = socket.send_to(&bytes, address) {
+                if ErrorKind::WouldBlock != error.kind() {
+                    log::warn!("send_packet failed: {} {:?}", address, error);
+                }
+            }
+        }
+    }
+
+    fn send_to_server(&self, packet: &ClientPacket) {
+        if let Some(server_address) = &self._server_address {
+            self.send_packet(packet, server_address);
+        }
+    }
+
+    fn receive_packets<T: DeserializeOwned>(&self) -> Vec<(SocketAddr, T)> {
+        let mut packets: Vec<(SocketAddr, T)> = Vec::new();
+        let socket = match &self._socket {
+            Some(socket) => socket,
+            None => return packets,
+        };
+        let mut buffer = vec![0u8; NETWORK_MAX_PACKET_SIZE];
+        loop {
+            match socket.recv_from(&mut buffer) {
+                Ok((size, address)) => {
+                    if let Some(packet) = decode_packet::<T>(&buffer[..size]) {
+                        packets.push((address, packet));
+                    }
+                },
+                Err(error) => {
+                    if ErrorKind::WouldBlock != error.kind() {
+                        log::warn!("receive_packets failed: {:?}", error);
+                    }
+                    break;
+                },
+            }
+        }
+        packets
+    }
+
+    // the client commands are sent with the next input
+    pub fn add_fire_command(&mut self, fire_start: &Vector3<f32>, fire_dir: &Vector3<f32>, target_position: &Vector3<f32>) {
+        if self._is_connected {
+            self._pending_fire_commands.push(FireCommand {
+                _fire_start: fire_start.clone_owned(),
+                _fire_dir: fire_dir.clone_owned(),
+                _target_position: target_position.clone_owned(),
+            });
+        }
+    }
+    pub fn add_reload_command(&mut self) {
+        if self._is_connected {
+            self._pending_reload = true;
+        }
+    }
+
+    // Server
+    fn get_client_spawn_point(&self, client_id: u32) -> ShipSpawnPointData {
+        let level_data = self.get_game_client().get_project_scene_manager().get_level_data();
+        let mut spawn_point_data = level_data._spawn_point_datas.iter().find_map(|spawn_point_type| match spawn_point_type {
+            SpawnPointType::Player(spawn_point_data) => Some(spawn_point_data.clone()),
+            _ => None,
+        }).unwrap_or_else(|| ShipSpawnPointData {
+            _ship_data_name: "default".to_string(),
+            ..Default::default()
+        });
+        spawn_point_data._position.x += NETWORK_SPAWN_SPACING * (client_id + 1) as f32;
+        spawn_point_data
+    }
+
+    // the remote player joins the enemy team of the host, the dedicated server has no host so the players are the player team.
+    fn is_remote_client_server_team(&self) -> bool {
+        self.get_game_client().is_dedicated_server()
+    }
+
+    fn spawn_remote_client_actor(&mut self, client_id: u32) -> ActorHandle {
+        let game_client = ptr_as_ref(self._game_client);
+        let spawn_point_data = self.get_client_spawn_point(client_id);
+        game_client.get_actor_manager_mut().create_actor(game_client, &spawn_point_data, self.is_remote_client_server_team())
+    }
+
+    // the ships of the remote clients are driven by their inputs
+    pub fn is_remote_client_actor(&self, actor_handle: ActorHandle) -> bool {
+        self._remote_clients.values().any(|remote_client| actor_handle == remote_client._actor_handle)
+    }
+    pub fn get_remote_clients(&self) -> &HashMap<SocketAddr, RemoteClient> { &self._remote_clients }
+
+    pub fn accept_client(&mut self, address: &SocketAddr, protocol_version: u32) {
+        if NETWORK_PROTOCOL_VERSION != protocol_version {
+            let reason = format!("protocol version {} is not {}", protocol_version, NETWORK_PROTOCOL_VERSION);
+            self.send_packet(&ServerPacket::Reject { _reason: reason }, address);
+            return;
+        }
+
+        // the client sends the connect again until the accept arrives
+        if false == self._remote_clients.contains_key(address) {
+            let client_id = self._client_id_generator;
+            self._client_id_generator += 1;
+
+            let actor_handle = self.spawn_remote_client_actor(client_id);
+            log::info!("accept_client: {} client {} actor {}", address, client_id, actor_handle.to_id());
+            self._remote_clients.insert(*address, RemoteClient {
+                _client_id: client_id,
+                _address: *address,
+                _actor_handle: actor_handle,
+                _last_processed_input: 0,
+                _acked_sequence: None,
+                _sent_snapshots: VecDeque::new(),
+                _idle_time: 0.0,
+                _respawn_time: None,
+            });
+        }
+
+        let remote_client = self._remote_clients.get(address).unwrap();
+        let accept_packet = ServerPacket::Accept {
+            _client_id: remote_client._client_id,
+            _actor_id: remote_client._actor_handle.to_id(),
+            _is_server_team: self.is_remote_client_server_team(),
+        };
+        self.send_packet(&accept_packet, address);
+    }
+
+    fn remove_client(&mut self, address: &SocketAddr) {
+        if let Some(remote_client) = self._remote_clients.remove(address) {
+            log::info!("remove_client: {} client {}", address, remote_client._client_id);
+            let game_client = ptr_as_ref(self._game_client);
+            let actor_manager = game_client.get_actor_manager_mut();
+            actor_manager.remove_actor(game_client.get_project_scene_manager_mut(), remote_client._actor_handle);
+        }
+    }
+
+    pub fn kick_client(&mut self, client_id: u32, reason: &str) -> bool {
+        let address = self._remote_clients.values().find(|remote_client| client_id == remote_client._client_id).map(|remote_client| remote_client._address);
+        match address {
+            Some(address) => {
+                self.send_packet(&ServerPacket::Reject { _reason: reason.to_string() }, &address);
+                self.remove_client(&address);
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn kick_all_clients(&mut self, reason: &str) {
+        let addresses: Vec<SocketAddr> = self._remote_clients.keys().cloned().collect();
+        for address in addresses.iter() {
+            self.send_packet(&ServerPacket::Reject { _reason: reason.to_string() }, address);
+            self.remove_client(address);
+        }
+    }
+
+    // the match is restarted, the ships of the remote clients are respawned on the next update
+    pub fn respawn_remote_clients(&mut self) {
+        for remote_client in self._remote_clients.values_mut() {
+            remote_client._respawn_time = Some(0.0);
+        }
+    }
+
+    // the inputs are applied in order with the delta time of the client, the redundant inputs are skipped.
+    fn apply_client_inputs(&mut self, address: &SocketAddr, acked_sequence: Option<u32>, inputs: &[InputCommand]) {
+        let game_client = ptr_as_ref(self._game_client);
+        let remote_client = match self._remote_clients.get_mut(address) {
+            Some(remote_client) => remote_client,
+            None => return,
+        };
+        remote_client._idle_time = 0.0;
+        if let Some(acked_sequence) = acked_sequence {
+            if remote_client._acked_sequence.map_or(true, |sequence| sequence < acked_sequence) {
+                remote_client._acked_sequence = Some(acked_sequence);
+            }
+        }
+
+        let actor = match game_client.get_actor_manager_mut().get_actor_mut(remote_client._actor_handle) {
+            Some(actor) => actor,
+            None => return,
+        };
+        for input in inputs.iter() {
+            if input._sequence <= remote_client._last_processed_input {
+                continue;
+            }
+            remote_client._last_processed_input = input._sequence;
+
+            let ship = actor.get_ship_mut();
+            if input._reload {
+                ship.reload_weapons();
+            }
+            for fire_command in input._fire_commands.iter() {
+                match validate_fire_command(fire_command, ship.get_transform().get_position()) {
+                    Some(fire_command) => ship.ship_fire(game_client, &fire_command._fire_start, &fire_command._fire_dir, &fire_command._target_position),
+                    None => log::warn!("apply_client_inputs: invalid fire command of client {}", remote_client._client_id),
+                }
+            }
+            ship.get_controller_mut().set_controller_input(&input._controller_input);
+            ship.update_ship(game_client, 0f32.max(NETWORK_MAX_INPUT_DELTA_TIME.min(input._delta_time)));
+        }
+    }
+
+    fn make_world_snapshot(&self) -> WorldSnapshot {
+        let game_client = ptr_as_ref(self._game_client);
+        let actors: Vec<ActorSnapshot> = game_client.get_actor_manager()._actors.values().map(|actor| {
+            let ship = actor.get_ship();
+            ActorSnapshot {
+                _actor_id: actor.get_actor_id(),
+                _ship_data_name: ship.get_ship_data()._ship_name.clone(),
+                _is_server_team: actor.is_player_actor(),
+                _controller_state: ship.get_controller().get_controller_state(),
+                _hull: ship.get_hull_point(),
+                _shields: ship.get_shield_point(),
+            }
+        }).collect();
+        let bullets: Vec<BulletSnapshot> = game_client.get_weapon_manager().get_bullets().iter().map(|(bullet_handle, bullet)| {
+            let transform = bullet.get_transform_object();
+            BulletSnapshot {
+                _bullet_id: bullet_handle.to_id(),
+                _model_data_name: bullet.get_bullet_data()._model_data_name.clone(),
+                _position: transform.get_position().clone_owned(),
+                _rotation: transform.get_rotation().clone_owned(),
+            }
+        }).collect();
+        let game_mode_manager = game_client.get_game_mode_manager();
+        let team_scores = TeamScoreSnapshot {
+            _server_team_score: game_mode_manager.get_team_score(true),
+            _other_team_score: game_mode_manager.get_team_score(false),
+        };
+
+        WorldSnapshot {
+            _sequence: self._snapshot_sequence,
+            _server_time: self._elapsed_time,
+            _actors: actors,
+            _bullets: bullets,
+            _team_scores: team_scores,
+        }
+    }
+
+    // each client gets the delta against the last snapshot it has acknowledged
+    fn send_snapshots(&mut self) {
+        self._snapshot_sequence += 1;
+        let world_snapshot = self.make_world_snapshot();
+        let actor_manager = ptr_as_ref(self._game_client).get_actor_manager();
+        let mut packets: Vec<(SocketAddr, ServerPacket)> = Vec::new();
+        for remote_client in self._remote_clients.values_mut() {
+            let baseline = remote_client._acked_sequence.and_then(|acked_sequence| {
+                remote_client._sent_snapshots.iter().find(|sent_snapshot| acked_sequence == sent_snapshot._sequence)
+            });
+            let (actor_deltas, removed_actor_ids) = world_snapshot.make_delta(baseline);
+
+            // the small changes under the epsilon are not sent, so the next baseline is the snapshot the client rebuilds.
+            let sent_snapshot = WorldSnapshot::apply_delta(
+                world_snapshot._sequence,
+                world_snapshot._server_time,
+                baseline,
+                &actor_deltas,
+                &removed_actor_ids,
+                &world_snapshot._bullets,
+                &world_snapshot._team_scores
+            );
+            let actor_id = if actor_manager.has_actor(remote_client._actor_handle) { Some(remote_client._actor_handle.to_id()) } else { None };
+            packets.push((remote_client._address, ServerPacket::Snapshot {
+                _sequence: world_snapshot._sequence,
+                _baseline_sequence: baseline.map(|baseline| baseline._sequence),
+                _server_time: world_snapshot._server_time,
+                _last_processed_input: remote_client._last_processed_input,
+                _actor_id: actor_id,
+                _respawn_time: remote_client._respawn_time,
+                _actor_deltas: actor_deltas,
+                _removed_actor_ids: removed_actor_ids,
+                _bullets: world_snapshot._bullets.clone(),
+                _team_scores: world_snapshot._team_scores.clone(),
+            }));
+
+            remote_client._sent_snapshots.push_back(sent_snapshot);
+            while NETWORK_SNAPSHOT_HISTORY < remote_client._sent_snapshots.len() {
+                remote_client._sent_snapshots.pop_front();
+            }
+        }
+
+        for (address, packet) in packets.iter() {
+            self.send_packet(packet, address);
+        }
+    }
+
+    fn update_server(&mut self, delta_time: f32) {
+        for (address, packet) in self.receive_packets::<ClientPacket>() {
+            match packet {
+                ClientPacket::Connect { _protocol_version } => self.accept_client(&address, _protocol_version),
+                ClientPacket::Input { _acked_sequence, _inputs } => self.apply_client_inputs(&address, _acked_sequence, &_inputs),
+                ClientPacket::Disconnect => self.remove_client(&address),
+            }
+        }
+
+        let mut timeout_addresses: Vec<SocketAddr> = Vec::new();
+        for remote_client in self._remote_clients.values_mut() {
+            remote_client._idle_time += delta_time;
+            if NETWORK_CONNECTION_TIMEOUT < remote_client._idle_time {
+                timeout_addresses.push(remote_client._address);
+            }
+        }
+        for address in timeout_addresses.iter() {
+            log::info!("client timeout: {}", address);
+            self.remove_client(address);
+        }
+        self.update_remote_client_respawn(delta_time);
+
+        self._snapshot_time -= delta_time;
+        if self._snapshot_time <= 0.0 {
+            self._snapshot_time = NETWORK_SNAPSHOT_INTERVAL.max(self._snapshot_time + NETWORK_SNAPSHOT_INTERVAL);
+            self.send_snapshots();
+        }
+    }
+
+    // the destroyed ship of the remote client is respawned without the lives of the game mode,
+    // the client learns the death and the new ship from the snapshots.
+    fn update_remote_client_respawn(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        let actor_manager = game_client.get_actor_manager();
+        let game_mode_data = game_client.get_game_mode_manager().get_game_mode_data();
+        let mut respawn_addresses: Vec<SocketAddr> = Vec::new();
+        for remote_client in self._remote_clients.values_mut() {
+            match remote_client._respawn_time {
+                Some(respawn_time) => {
+                    let respawn_time = respawn_time - delta_time;
+                    if 0.0 < respawn_time {
+                        remote_client._respawn_time = Some(respawn_time);
+                    } else {
+                        remote_client._respawn_time = None;
+                        respawn_addresses.push(remote_client._address);
+                    }
+                },
+                None => {
+                    if false == actor_manager.has_actor(remote_client._actor_handle) {
+                        log::info!("remote client destroyed: client {}", remote_client._client_id);
+                        remote_client._respawn_time = Some(game_mode_data._player_respawn_time);
+                    }
+                },
+            }
+        }
+
+        for address in respawn_addresses.iter() {
+            let client_id = match self._remote_clients.get(address) {
+                Some(remote_client) => remote_client._client_id,
+                None => continue,
+            };
+            let actor_handle = self.spawn_remote_client_actor(client_id);
+            if let Some(actor) = game_client.get_actor_manager_mut().get_actor_mut(actor_handle) {
+                actor.get_ship_mut().set_invulnerable_time(game_mode_data._player_invulnerable_time);
+            }
+            log::info!("remote client respawned: client {} actor {}", client_id, actor_handle.to_id());
+            self._remote_clients.get_mut(address).unwrap()._actor_handle = actor_handle;
+        }
+    }
+
+    // Client
+    // the server simulates the world, the local actors except the player ship are replaced by the snapshots.
+    fn on_accepted(&mut self, client_id: u32, actor_id: u64, is_server_team: bool) {
+        if self._is_connected {
+            return;
+        }
+        log::info!("connected: client {} actor {}", client_id, actor_id);
+        self._is_server_team = is_server_team;
+
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let actor_manager = game_client.get_actor_manager_mut();
+        let local_actors: Vec<ActorHandle> = actor_manager._actors.handles().into_iter()
+            .filter(|actor_handle| Some(*actor_handle) != actor_manager._player_actor)
+            .collect();
+        for actor_handle in local_actors.iter() {
+            actor_manager.remove_actor(project_scene_manager, *actor_handle);
+        }
+
+        self._is_connected = true;
+        self._idle_time = 0.0;
+        self._server_actor_id = actor_id;
+        self._actor_id_map.clear();
+        if let Some(player_actor) = actor_manager._player_actor {
+            self._actor_id_map.insert(actor_id, player_actor);
+        }
+        self._input_sequence = 0;
+        self._last_processed_input = 0;
+        self._pending_inputs.clear();
+        self._pending_fire_commands.clear();
+        self._pending_reload = false;
+        self._received_snapshots.clear();
+    }
+
+    fn disconnect_from_server(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let actor_manager = game_client.get_actor_manager_mut();
+        for local_actor in self._actor_id_map.values() {
+            let is_remote_actor = match actor_manager.get_actor(*local_actor) {
+                Some(actor) => actor.is_remote_actor(),
+                None => false,
+            };
+            if is_remote_actor {
+                actor_manager.remove_actor(project_scene_manager, *local_actor);
+            }
+        }
+        for render_object in self._network_bullets.values() {
+            project_scene_manager.remove_static_render_object(&render_object.borrow()._render_object_name);
+        }
+        self._network_bullets.clear();
+        self._actor_id_map.clear();
+        self._received_snapshots.clear();
+        self._pending_inputs.clear();
+        self._is_connected = false;
+        self._connect_time = 0.0;
+    }
+
+    fn receive_snapshot(
+        &mut self,
+        sequence: u32,
+        baseline_sequence: Option<u32>,
+        server_time: f32,
+        last_processed_input: u32,
+        actor_deltas: &[ActorSnapshotDelta],
+        removed_actor_ids: &[u64],
+        bullets: &[BulletSnapshot],
+        team_scores: &TeamScoreSnapshot
+    ) -> bool {
+        // the old snapshot arrived out of order
+        if let Some(latest_snapshot) = self._received_snapshots.back() {
+            if sequence <= latest_snapshot._sequence {
+                return false;
+            }
+        }
+
+        let baseline = match baseline_sequence {
+            Some(baseline_sequence) => match self._received_snapshots.iter().find(|snapshot| baseline_sequence == snapshot._sequence) {
+                Some(baseline) => Some(baseline),
+                None => {
+                    log::warn!("receive_snapshot: not found baseline {}", baseline_sequence);
+                    return false;
+                },
+            },
+            None => None,
+        };
+        let world_snapshot = WorldSnapshot::apply_delta(sequence, server_time, baseline, actor_deltas, removed_actor_ids, bullets, team_scores);
+        self._received_snapshots.push_back(world_snapshot);
+        while NETWORK_SNAPSHOT_HISTORY < self._received_snapshots.len() {
+            self._received_snapshots.pop_front();
+        }
+        self._last_processed_input = last_processed_input;
+        true
+    }
+
+    // the server destroys and respawns the ship of the client, the local player ship follows it.
+    fn sync_player_actor(&mut self, actor_id: Option<u64>, respawn_time: Option<f32>) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let actor_manager = game_client.get_actor_manager_mut();
+        actor_manager.set_player_respawn_time(respawn_time);
+
+        let actor_id = match actor_id {
+            Some(actor_id) => actor_id,
+            None => {
+                // the death cam looks at the wreck until the respawn
+                if self._actor_id_map.remove(&self._server_actor_id).is_some() {
+                    log::info!("player actor destroyed: actor {}", self._server_actor_id);
+                    actor_manager.remove_player_actor(project_scene_manager);
+                }
+                return;
+            },
+        };
+        if actor_id == self._server_actor_id && actor_manager.get_player_actor().is_some() {
+            return;
+        }
+
+        let actor_snapshot = match self._received_snapshots.back().and_then(|snapshot| snapshot.get_actor_snapshot(actor_id)) {
+            Some(actor_snapshot) => actor_snapshot,
+            None => return,
+        };
+        if false == game_client.get_project_resources().has_ship_data(&actor_snapshot._ship_data_name) {
+            log::error!("sync_player_actor: not found ship data {}", actor_snapshot._ship_data_name);
+            return;
+        }
+        if let Some(local_actor) = self._actor_id_map.remove(&self._server_actor_id) {
+            actor_manager.remove_actor(project_scene_manager, local_actor);
+        }
+
+        let spawn_point_data = ShipSpawnPointData {
+            _ship_data_name: actor_snapshot._ship_data_name.clone(),
+            _position: actor_snapshot._controller_state._position.clone_owned(),
+            _rotation: actor_snapshot._controller_state._rotation.clone_owned(),
+        };
+        let player_actor = actor_manager.create_actor(game_client, &spawn_point_data, true);
+        actor_manager.set_player_actor(player_actor);
+        log::info!("player actor respawned: actor {}", actor_id);
+        self._server_actor_id = actor_id;
+        self._actor_id_map.insert(actor_id, player_actor);
+        self._pending_inputs.clear();
+        game_client.get_game_controller_mut().move_camera_to(&spawn_point_data._position);
+    }
+
+    // the scores of the server team are the scores of the player team when the client is on the server team
+    fn sync_team_scores(&self) {
+        let team_scores = match self._received_snapshots.back() {
+            Some(latest_snapshot) => &latest_snapshot._team_scores,
+            None => return,
+        };
+        let game_mode_manager = ptr_as_ref(self._game_client).get_game_mode_manager_mut();
+        if self._is_server_team {
+            game_mode_manager.set_team_scores(team_scores._server_team_score, team_scores._other_team_score);
+        } else {
+            game_mode_manager.set_team_scores(team_scores._other_team_score, team_scores._server_team_score);
+        }
+    }
+
+    // creates the new actors of the latest snapshot and removes the gone actors
+    fn sync_actors(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let project_resources = game_client.get_project_resources();
+        let actor_manager = game_client.get_actor_manager_mut();
+        let latest_snapshot = match self._received_snapshots.back() {
+            Some(latest_snapshot) => latest_snapshot,
+            None => return,
+        };
+
+        let removed_actor_ids: Vec<u64> = self._actor_id_map.keys()
+            .filter(|actor_id| self._server_actor_id != **actor_id && latest_snapshot.get_actor_snapshot(**actor_id).is_none())
+            .cloned()
+            .collect();
+        for actor_id in removed_actor_ids.iter() {
+            if let Some(local_actor) = self._actor_id_map.remove(actor_id) {
+                actor_manager.remove_actor(project_scene_manager, local_actor);
+            }
+        }
+
+        for actor_snapshot in latest_snapshot._actors.iter() {
+            let local_actor = match self._actor_id_map.get(&actor_snapshot._actor_id) {
+                Some(local_actor) => *local_actor,
+                // the ship of the client is created by sync_player_actor
+                None if self._server_actor_id == actor_snapshot._actor_id => continue,
+                None => {
+                    if false == project_resources.has_ship_data(&actor_snapshot._ship_data_name) {
+                        log::error!("sync_actors: not found ship data {}", actor_snapshot._ship_data_name);
+                        continue;
+                    }
+
+                    // the ships of the team of the client are the player team
+                    let spawn_point_data = ShipSpawnPointData {
+                        _ship_data_name: actor_snapshot._ship_data_name.clone(),
+                        _position: actor_snapshot._controller_state._position.clone_owned(),
+                        _rotation: actor_snapshot._controller_state._rotation.clone_owned(),
+                    };
+                    let local_actor = actor_manager.create_actor(game_client, &spawn_point_data, self._is_server_team == actor_snapshot._is_server_team);
+                    if let Some(actor) = actor_manager.get_actor_mut(local_actor) {
+                        actor.set_remote_actor(true);
+                    }
+                    self._actor_id_map.insert(actor_snapshot._actor_id, local_actor);
+                    local_actor
+                },
+            };
+
+            if let Some(actor) = actor_manager.get_actor_mut(local_actor) {
+                let ship = actor.get_ship_mut();
+                ship._hull = actor_snapshot._hull;
+                ship._shields = actor_snapshot._shields;
+            }
+        }
+    }
+
+    // the player ship restarts from the server state and replays the inputs the server has not processed yet
+    fn reconcile_player_actor(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let last_processed_input = self._last_processed_input;
+        self._pending_inputs.retain(|input| last_processed_input < input._sequence);
+
+        let player_actor = match game_client.get_actor_manager_mut().get_player_actor_mut() {
+            Some(player_actor) => player_actor,
+            None => return,
+        };
+        let actor_snapshot = match self._received_snapshots.back().and_then(|snapshot| snapshot.get_actor_snapshot(self._server_actor_id)) {
+            Some(actor_snapshot) => actor_snapshot,
+            None => return,
+        };
+
+        let ship = player_actor.get_ship_mut();
+        ship.get_controller_mut().set_controller_state(&actor_snapshot._controller_state);
+        for input in self._pending_inputs.iter() {
+            ship.predict_movement(game_client, &input._controller_input, input._delta_time);
+        }
+    }
+
+    fn record_input(&mut self, delta_time: f32) {
+        let controller_input = match self.get_game_client().get_actor_manager().get_player_actor() {
+            Some(player_actor) => player_actor.get_controller().get_last_input().clone(),
+            None => return,
+        };
+
+        self._input_sequence += 1;
+        let input = InputCommand {
+            _sequence: self._input_sequence,
+            _delta_time: delta_time,
+            _controller_input: controller_input,
+            _fire_commands: std::mem::take(&mut self._pending_fire_commands),
+            _reload: self._pending_reload,
+        };
+        self._pending_reload = false;
+        self._pending_inputs.push_back(input);
+        while NETWORK_MAX_PENDING_INPUTS < self._pending_inputs.len() {
+            self._pending_inputs.pop_front();
+        }
+    }
+
+    fn send_inputs(&self) {
+        let skip_count = self._pending_inputs.len().saturating_sub(NETWORK_INPUT_REDUNDANCY);
+        let input_packet = ClientPacket::Input {
+            _acked_sequence: self._received_snapshots.back().map(|snapshot| snapshot._sequence),
+            _inputs: self._pending_inputs.iter().skip(skip_count).cloned().collect(),
+        };
+        self.send_to_server(&input_packet);
+    }
+
+    // the other ships are rendered a little in the past between the two snapshots
+    fn update_interpolation(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        let latest_server_time = match self._received_snapshots.back() {
+            Some(latest_snapshot) => latest_snapshot._server_time,
+            None => return,
+        };
+        self._interpolation_time += delta_time;
+        if NETWORK_INTERPOLATION_DELAY < (latest_server_time - self._interpolation_time).abs() {
+            self._interpolation_time = latest_server_time;
+        }
+        let render_time = self._interpolation_time - NETWORK_INTERPOLATION_DELAY;
+
+        let to_index = self._received_snapshots.iter()
+            .position(|snapshot| render_time <= snapshot._server_time)
+            .unwrap_or(self._received_snapshots.len() - 1);
+        let from_index = if 0 < to_index { to_index - 1 } else { 0 };
+        let from_snapshot = &self._received_snapshots[from_index];
+        let to_snapshot = &self._received_snapshots[to_index];
+        let t = if from_snapshot._server_time < to_snapshot._server_time {
+            0f32.max(1f32.min((render_time - from_snapshot._server_time) / (to_snapshot._server_time - from_snapshot._server_time)))
+        } else {
+            1.0
+        };
+
+        let actor_manager = game_client.get_actor_manager_mut();
+        for actor_snapshot in to_snapshot._actors.iter() {
+            if self._server_actor_id == actor_snapshot._actor_id {
+                continue;
+            }
+            let local_actor = match self._actor_id_map.get(&actor_snapshot._actor_id) {
+                Some(local_actor) => *local_actor,
+                None => continue,
+            };
+            let actor = match actor_manager.get_actor_mut(local_actor) {
+                Some(actor) => actor,
+                None => continue,
+            };
+            let controller_state = match from_snapshot.get_actor_snapshot(actor_snapshot._actor_id) {
+                Some(from_actor) => lerp_controller_state(&from_actor._controller_state, &actor_snapshot._controller_state, t),
+                None => actor_snapshot._controller_state.clone(),
+            };
+            let ship = actor.get_ship_mut();
+            ship.get_controller_mut().set_controller_state(&controller_state);
+            ship.update_ship_transform(delta_time);
+        }
+
+        update_network_bullets(&mut self._network_bullets, game_client.get_project_scene_manager_mut(), from_snapshot, to_snapshot, t);
+    }
+
+    fn update_client(&mut self, delta_time: f32) {
+        if self._is_connected {
+            self.record_input(delta_time);
+        } else {
+            self._connect_time -= delta_time;
+            if self._connect_time <= 0.0 {
+                self._connect_time = NETWORK_CONNECT_RETRY_INTERVAL;
+                self.send_to_server(&ClientPacket::Connect { _protocol_version: NETWORK_PROTOCOL_VERSION });
+            }
+        }
+
+        let mut is_snapshot_received = false;
+        let mut player_actor_state: Option<(Option<u64>, Option<f32>)> = None;
+        for (address, packet) in self.receive_packets::<ServerPacket>() {
+            if Some(address) != self._server_address {
+                continue;
+            }
+            self._idle_time = 0.0;
+            match packet {
+                ServerPacket::Accept { _client_id, _actor_id, _is_server_team } => self.on_accepted(_client_id, _actor_id, _is_server_team),
+                ServerPacket::Reject { _reason } => {
+                    log::error!("connection rejected: {}", _reason);
+                    self.destroy_network_manager();
+                    return;
+                },
+                ServerPacket::Snapshot {
+                    _sequence,
+                    _baseline_sequence,
+                    _server_time,
+                    _last_processed_input,
+                    _actor_id,
+                    _respawn_time,
+                    _actor_deltas,
+                    _removed_actor_ids,
+                    _bullets,
+                    _team_scores,
+                } => {
+                    if self._is_connected && self.receive_snapshot(
+                        _sequence,
+                        _baseline_sequence,
+                        _server_time,
+                        _last_processed_input,
+                        &_actor_deltas,
+                        &_removed_actor_ids,
+                        &_bullets,
+                        &_team_scores
+                    ) {
+                        is_snapshot_received = true;
+                        player_actor_state = Some((_actor_id, _respawn_time));
+                    }
+                },
+            }
+        }
+
+        if self._is_connected {
+            self._idle_time += delta_time;
+            if NETWORK_CONNECTION_TIMEOUT < self._idle_time {
+                log::warn!("server timeout: {:?}", self._server_address);
+                self.disconnect_from_server();
+                return;
+            }
+
+            if is_snapshot_received {
+                if let Some((actor_id, respawn_time)) = player_actor_state {
+                    self.sync_player_actor(actor_id, respawn_time);
+                }
+                self.sync_actors();
+                self.sync_team_scores();
+                self.reconcile_player_actor();
+            }
+            self.update_interpolation(delta_time);
+            self.send_inputs();
+        }
+    }
+
+    pub fn update_network_manager(&mut self, delta_time: f32) {
+        self._elapsed_time += delta_time;
+        match self._network_mode {
+            NetworkMode::Offline => (),
+            NetworkMode::Server => self.update_server(delta_time),
+            NetworkMode::Client => self.update_client(delta_time),
+        }
+    }
+}
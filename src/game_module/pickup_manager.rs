@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use nalgebra::Vector3;
+
+use rust_engine_3d::effect::effect_data::EffectCreateInfo;
+use rust_engine_3d::renderer::render_object::RenderObjectCreateInfo;
+use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::{PICKUP_FLOATING_HEIGHT, PICKUP_ROTATION_SPEED};
+use crate::game_module::pickups::pickup::PickupInstance;
+
+
+pub struct PickupManager {
+    pub _game_client: *const GameClient,
+    pub _id_generator: u64,
+    pub _pickups: HashMap<u64, Box<PickupInstance>>,
+}
+
+impl PickupManager {
+    pub fn create_pickup_manager() -> Box<PickupManager> {
+        Box::new(PickupManager {
+            _game_client: std::ptr::null(),
+            _id_generator: 0,
+            _pickups: HashMap::new(),
+        })
+    }
+
+    pub fn initialize_pickup_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+    pub fn destroy_pickup_manager(&mut self) {
+        self._pickups.clear();
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn generate_id(&mut self) -> u64 {
+        let id = self._id_generator;
+        self._id_generator += 1;
+        id
+    }
+    pub fn get_pickups(&self) -> &HashMap<u64, Box<PickupInstance>> { &self._pickups }
+
+    pub fn create_pickup(&mut self, pickup_data_name: &str, position: &Vector3<f32>, is_dropped: bool) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_resources = game_client.get_project_resources();
+        if false == project_resources.has_pickup_data(pickup_data_name) {
+            log::error!("create_pickup: not found pickup data {}", pickup_data_name);
+            return;
+        }
+
+        // the pickup floats above the ground
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let mut position = position.clone_owned();
+        position.y = position.y.max(project_scene_manager.get_height_bilinear(&position, 0) + PICKUP_FLOATING_HEIGHT);
+
+        let id = self.generate_id();
+        let pickup_data = project_resources.get_pickup_data(pickup_data_name);
+        let mut pickup = PickupInstance::create_pickup_instance(id, pickup_data_name, pickup_data, &position, is_dropped);
+        PickupManager::spawn_pickup(&mut pickup, project_scene_manager);
+        self._pickups.insert(id, Box::new(pickup));
+    }
+
+    pub fn spawn_pickups(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let level_data = game_client.get_project_scene_manager().get_level_data();
+        for pickup_placement_data in level_data._pickup_placement_datas.iter() {
+            self.create_pickup(&pickup_placement_data._pickup_data_name, &pickup_placement_data._position, false);
+        }
+    }
+
+    // rolls the drop table of the destroyed ship
+    pub fn drop_pickup(&mut self, drop_table_data_name: &str, position: &Vector3<f32>) {
+        let project_resources = ptr_as_ref(self._game_client).get_project_resources();
+        if drop_table_data_name.is_empty() || false == project_resources.has_drop_table_data(drop_table_data_name) {
+            return;
+        }
+
+        let drop_table_data = project_resources.get_drop_table_data(drop_table_data_name).borrow();
+        if let Some(pickup_data_name) = drop_table_data.roll_drop() {
+            self.create_pickup(pickup_data_name, position, true);
+        }
+    }
+
+    fn spawn_pickup(pickup: &mut PickupInstance, project_scene_manager: &mut ProjectSceneManager) {
+        let pickup_data = ptr_as_ref(pickup._pickup_data.as_ptr());
+        let render_object_create_info = RenderObjectCreateInfo {
+            _model_data_name: pickup_data._model_data_name.clone(),
+            _position: pickup._position.clone_owned(),
+            ..Default::default()
+        };
+        pickup._render_object = project_scene_manager.add_static_render_object("Pickup", &render_object_create_info);
+        pickup._is_spawned = true;
+        for effect_data_name in pickup_data._spawn_effects.iter() {
+            let effect_create_info = EffectCreateInfo {
+                _effect_position: pickup._position.clone_owned(),
+                _effect_data_name: effect_data_name.clone(),
+                ..Default::default()
+            };
+            project_scene_manager.add_effect(effect_data_name, &effect_create_info);
+        }
+    }
+
+    fn despawn_pickup(pickup: &mut PickupInstance, project_scene_manager: &mut ProjectSceneManager) {
+        if let Some(render_object) = &pickup._render_object {
+            project_scene_manager.remove_static_render_object(&render_object.borrow()._render_object_name);
+        }
+        pickup._render_object = None;
+        pickup._is_spawned = false;
+        pickup._respawn_time = pickup.get_pickup_data()._respawn_time;
+    }
+
+    pub fn update_pickup_manager(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let actor_manager = game_client.get_actor_manager();
+
+        for pickup in self._pickups.values_mut() {
+            let pickup_data = ptr_as_ref(pickup._pickup_data.as_ptr());
+
+            // respawn
+            if false == pickup.is_spawned() {
+                if false == pickup._is_dropped && 0.0 < pickup_data._respawn_time {
+                    pickup._respawn_time -= delta_time;
+                    if pickup._respawn_time <= 0.0 {
+                        PickupManager::spawn_pickup(pickup, project_scene_manager);
+                    }
+                }
+                continue;
+            }
+
+            if let Some(render_object) = &pickup._render_object {
+                let transform = &mut render_object.borrow_mut()._transform_object;
+                transform.rotation_yaw(PICKUP_ROTATION_SPEED * delta_time);
+                transform.update_transform_object();
+            }
+
+            // collect by the ship bounding box
+            for actor in actor_manager._actors.values() {
+                let actor = ptr_as_mut(actor);
+                let is_collected = {
+                    let bound_box = actor.get_bound_box();
+                    pickup.is_overlapped(&bound_box._center, bound_box._radius)
+                };
+                if is_collected && false == actor.get_ship().is_destroyed() && actor.get_ship_mut().apply_pickup(pickup_data) {
+                    for effect_data_name in pickup_data._pickup_effects.iter() {
+                        let effect_create_info = EffectCreateInfo {
+                            _effect_position: pickup._position.clone_owned(),
+                            _effect_data_name: effect_data_name.clone(),
+                            ..Default::default()
+                        };
+                        project_scene_manager.add_effect(effect_data_name, &effect_create_info);
+                    }
+                    if actor.is_player_actor() && false == pickup_data._pickup_sound_bank.is_empty() {
+                        game_client.play_audio_bank(&pickup_data._pickup_sound_bank);
+                    }
+                    PickupManager::despawn_pickup(pickup, project_scene_manager);
+                    break;
+                }
+            }
+
+            // the dropped pickup expires
+            if pickup._is_dropped && pickup.is_spawned() {
+                pickup._life_time -= delta_time;
+                if pickup._life_time <= 0.0 {
+                    PickupManager::despawn_pickup(pickup, project_scene_manager);
+                }
+            }
+        }
+        self._pickups.retain(|_, pickup| false == pickup.is_expired());
+    }
+}
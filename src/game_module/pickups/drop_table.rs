@@ -0,0 +1,56 @@
+use serde::{ Serialize, Deserialize };
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DropTableEntry {
+    pub _pickup_data_name: String,
+    pub _weight: f32,
+}
+
+impl Default for DropTableEntry {
+    fn default() -> DropTableEntry {
+        DropTableEntry {
+            _pickup_data_name: "".to_string(),
+            _weight: 1.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DropTableData {
+    // probability of dropping anything, the entry is picked by the weights.
+    pub _drop_chance: f32,
+    pub _drop_entries: Vec<DropTableEntry>,
+}
+
+impl Default for DropTableData {
+    fn default() -> DropTableData {
+        DropTableData {
+            _drop_chance: 0.5,
+            _drop_entries: Vec::new(),
+        }
+    }
+}
+
+impl DropTableData {
+    pub fn roll_drop(&self) -> Option<&str> {
+        if self._drop_entries.is_empty() || self._drop_chance <= rand::random::<f32>() {
+            return None;
+        }
+
+        let total_weight: f32 = self._drop_entries.iter().map(|entry| 0f32.max(entry._weight)).sum();
+        if total_weight <= 0.0 {
+            return None;
+        }
+
+        let mut weight = rand::random::<f32>() * total_weight;
+        for entry in self._drop_entries.iter() {
+            weight -= 0f32.max(entry._weight);
+            if weight <= 0.0 {
+                return Some(&entry._pickup_data_name);
+            }
+        }
+        self._drop_entries.last().map(|entry| entry._pickup_data_name.as_str())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod drop_table;
+pub mod pickup;
\ No newline at end of file
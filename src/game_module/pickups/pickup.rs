@@ -0,0 +1,97 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+use rust_engine_3d::renderer::render_object::RenderObjectData;
+use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_ref};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PickupType {
+    HullRepair,
+    ShieldRecharge,
+    DamageBoost,
+    Ammo,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PickupData {
+    pub _pickup_type: PickupType,
+    pub _model_data_name: String,
+    pub _spawn_effects: Vec<String>,
+    pub _pickup_effects: Vec<String>,
+    pub _pickup_sound_bank: String,
+    // hull or shield points, the damage scale for the damage boost, the reserve ammo for the ammo
+    pub _amount: f32,
+    // duration of the damage boost
+    pub _duration: f32,
+    // zero does not respawn
+    pub _respawn_time: f32,
+    // life time of the dropped pickup
+    pub _drop_life_time: f32,
+    pub _pickup_radius: f32,
+}
+
+impl Default for PickupData {
+    fn default() -> PickupData {
+        PickupData {
+            _pickup_type: PickupType::HullRepair,
+            _model_data_name: "".to_string(),
+            _spawn_effects: Vec::new(),
+            _pickup_effects: Vec::new(),
+            _pickup_sound_bank: "".to_string(),
+            _amount: 30.0,
+            _duration: 10.0,
+            _respawn_time: 30.0,
+            _drop_life_time: 30.0,
+            _pickup_radius: 2.0,
+        }
+    }
+}
+
+pub struct PickupInstance {
+    pub _pickup_id: u64,
+    pub _pickup_data_name: String,
+    pub _pickup_data: RcRefCell<PickupData>,
+    pub _position: Vector3<f32>,
+    // the render object is None on the dedicated server even while spawned
+    pub _render_object: Option<RcRefCell<RenderObjectData>>,
+    pub _is_spawned: bool,
+    pub _is_dropped: bool,
+    pub _respawn_time: f32,
+    pub _life_time: f32,
+}
+
+impl PickupInstance {
+    pub fn create_pickup_instance(
+        pickup_id: u64,
+        pickup_data_name: &str,
+        pickup_data: &RcRefCell<PickupData>,
+        position: &Vector3<f32>,
+        is_dropped: bool
+    ) -> PickupInstance {
+        PickupInstance {
+            _pickup_id: pickup_id,
+            _pickup_data_name: pickup_data_name.to_string(),
+            _pickup_data: pickup_data.clone(),
+            _position: position.clone_owned(),
+            _render_object: None,
+            _is_spawned: false,
+            _is_dropped: is_dropped,
+            _respawn_time: 0.0,
+            _life_time: pickup_data.borrow()._drop_life_time,
+        }
+    }
+
+    pub fn get_pickup_id(&self) -> u64 { self._pickup_id }
+    pub fn get_pickup_data(&self) -> &PickupData { ptr_as_ref(self._pickup_data.as_ptr()) }
+    pub fn get_position(&self) -> &Vector3<f32> { &self._position }
+    pub fn is_spawned(&self) -> bool { self._is_spawned }
+    // the dropped pickup is removed once it is collected or expired
+    pub fn is_expired(&self) -> bool {
+        self._is_dropped && (false == self.is_spawned() || self._life_time <= 0.0)
+    }
+    pub fn is_overlapped(&self, center: &Vector3<f32>, radius: f32) -> bool {
+        let pickup_radius = self.get_pickup_data()._pickup_radius + radius;
+        (center - &self._position).norm_squared() <= (pickup_radius * pickup_radius)
+    }
+}
@@ -0,0 +1,199 @@
+use rhai::{AST, CallFnOptions, Dynamic, Engine, FuncArgs, Scope, INT};
+
+use rust_engine_3d::utilities::system::ptr_as_ref;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::{
+    SCRIPT_MAX_OPERATIONS,
+    SCRIPT_MAX_CALL_LEVELS,
+    SCRIPT_MAX_TIMERS,
+};
+use crate::game_module::scripts::script_api::{ScriptApi, register_script_api};
+use crate::game_module::scripts::script_event::ScriptEvent;
+
+pub struct ScriptTimer {
+    pub _timer_name: String,
+    pub _remaining_time: f32,
+    pub _interval: f32,
+    pub _is_repeat: bool,
+}
+
+pub struct ScriptInstance {
+    pub _script_name: String,
+    pub _ast: AST,
+    pub _scope: Scope<'static>,
+}
+
+pub struct ScriptManager {
+    pub _game_client: *const GameClient,
+    pub _engine: Engine,
+    pub _scripts: Vec<ScriptInstance>,
+    pub _script_events: Vec<ScriptEvent>,
+    pub _timers: Vec<ScriptTimer>,
+}
+
+// Implementation
+impl ScriptInstance {
+    pub fn has_hook(&self, hook_name: &str) -> bool {
+        self._ast.iter_functions().any(|function| hook_name == function.name)
+    }
+
+    // the top level statements are run once when the script is loaded, the hooks do not run them again
+    pub fn call_hook<A: FuncArgs>(&mut self, engine: &Engine, hook_name: &str, args: A) {
+        let options = CallFnOptions::new().eval_ast(false);
+        if let Err(error) = engine.call_fn_with_options::<Dynamic>(options, &mut self._scope, &self._ast, hook_name, args) {
+            log::error!("{}::{} failed: {}", self._script_name, hook_name, error);
+        }
+    }
+}
+
+impl ScriptManager {
+    pub fn create_script_manager() -> Box<ScriptManager> {
+        let mut engine = Engine::new();
+        // a broken script can not hang the game
+        engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+        engine.set_max_call_levels(SCRIPT_MAX_CALL_LEVELS);
+        engine.on_print(|text| log::info!("script: {}", text));
+        Box::new(ScriptManager {
+            _game_client: std::ptr::null(),
+            _engine: engine,
+            _scripts: Vec::new(),
+            _script_events: Vec::new(),
+            _timers: Vec::new(),
+        })
+    }
+
+    pub fn initialize_script_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+        register_script_api(&mut self._engine, ScriptApi { _game_client: game_client });
+    }
+    pub fn destroy_script_manager(&mut self) {
+        self.clear_scripts();
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+
+    pub fn clear_scripts(&mut self) {
+        self._scripts.clear();
+        self._script_events.clear();
+        self._timers.clear();
+    }
+
+    // the scripts of the level are compiled and on_start is called on the next update
+    pub fn start_scripts(&mut self) {
+        self.clear_scripts();
+        let game_client = ptr_as_ref(self._game_client);
+        let script_names = game_client.get_project_scene_manager().get_level_data()._script_names.clone();
+        for script_name in script_names.iter() {
+            self.load_script(script_name);
+        }
+        self.add_script_event(ScriptEvent::Start);
+    }
+
+    fn load_script(&mut self, script_name: &str) {
+        let project_resources = self.get_game_client().get_project_resources();
+        if false == project_resources.has_script_data(script_name) {
+            log::error!("load_script: {} does not exist", script_name);
+            return;
+        }
+
+        let ast = match self._engine.compile(project_resources.get_script_data(script_name).borrow().as_str()) {
+            Ok(ast) => ast,
+            Err(error) => {
+                log::error!("load_script: {} {}", script_name, error);
+                return;
+            },
+        };
+        let mut scope = Scope::new();
+        if let Err(error) = self._engine.run_ast_with_scope(&mut scope, &ast) {
+            log::error!("load_script: {} {}", script_name, error);
+            return;
+        }
+        log::info!("load_script: {}", script_name);
+        self._scripts.push(ScriptInstance {
+            _script_name: script_name.to_string(),
+            _ast: ast,
+            _scope: scope,
+        });
+    }
+
+    pub fn has_hook(&self, hook_name: &str) -> bool {
+        self._scripts.iter().any(|script| script.has_hook(hook_name))
+    }
+
+    pub fn add_script_event(&mut self, script_event: ScriptEvent) {
+        // nothing listens without the scripts
+        if false == self._scripts.is_empty() {
+            self._script_events.push(script_event);
+        }
+    }
+
+    pub fn set_timer(&mut self, timer_name: &str, seconds: f32, is_repeat: bool) {
+        self.clear_timer(timer_name);
+        if SCRIPT_MAX_TIMERS <= self._timers.len() {
+            log::error!("set_timer: too many timers {}", timer_name);
+            return;
+        }
+        let interval = 0f32.max(seconds);
+        self._timers.push(ScriptTimer {
+            _timer_name: timer_name.to_string(),
+            _remaining_time: interval,
+            _interval: interval,
+            _is_repeat: is_repeat,
+        });
+    }
+
+    pub fn clear_timer(&mut self, timer_name: &str) {
+        self._timers.retain(|timer| timer_name != timer._timer_name);
+    }
+
+    fn update_timers(&mut self, delta_time: f32) {
+        let mut script_events: Vec<ScriptEvent> = Vec::new();
+        self._timers.retain_mut(|timer| {
+            timer._remaining_time -= delta_time;
+            if 0.0 < timer._remaining_time {
+                return true;
+            }
+            script_events.push(ScriptEvent::Timer { _timer_name: timer._timer_name.clone() });
+            // the repeat timer with zero interval would fire every frame
+            if timer._is_repeat && 0.0 < timer._interval {
+                timer._remaining_time += timer._interval;
+                return true;
+            }
+            false
+        });
+        self._script_events.extend(script_events);
+    }
+
+    fn call_hooks<A: FuncArgs + Clone>(&mut self, hook_name: &str, args: A) {
+        for script in self._scripts.iter_mut() {
+            if script.has_hook(hook_name) {
+                script.call_hook(&self._engine, hook_name, args.clone());
+            }
+        }
+    }
+
+    fn dispatch_script_events(&mut self) {
+        // the hooks may queue the new events, they are dispatched on the next update
+        let script_events = std::mem::take(&mut self._script_events);
+        for script_event in script_events.iter() {
+            let hook_name = script_event.get_hook_name();
+            match script_event {
+                ScriptEvent::Start => self.call_hooks(hook_name, ()),
+                ScriptEvent::ActorDestroyed { _actor_id, _is_player_team } => self.call_hooks(hook_name, (*_actor_id as INT, *_is_player_team)),
+                ScriptEvent::EnterZone { _zone_name, _actor_id } |
+                ScriptEvent::ExitZone { _zone_name, _actor_id } |
+                ScriptEvent::StayZone { _zone_name, _actor_id } => self.call_hooks(hook_name, (_zone_name.clone(), *_actor_id as INT)),
+                ScriptEvent::Timer { _timer_name } => self.call_hooks(hook_name, (_timer_name.clone(),)),
+            }
+        }
+    }
+
+    pub fn update_script_manager(&mut self, delta_time: f32) {
+        // the server runs the level logic for the clients
+        let game_client = ptr_as_ref(self._game_client);
+        if game_client.get_network_manager().is_client() || game_client.get_game_controller().is_match_ended() {
+            return;
+        }
+        self.update_timers(delta_time);
+        self.dispatch_script_events();
+    }
+}
@@ -0,0 +1,2 @@
+pub mod script_api;
+pub mod script_event;
\ No newline at end of file
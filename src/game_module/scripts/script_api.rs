@@ -0,0 +1,174 @@
+use nalgebra::Vector3;
+use rhai::{Engine, FLOAT, INT};
+
+use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use crate::game_module::actors::actor::{ActorController, ActorHandle};
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_controller::MatchResult;
+use crate::game_module::level_datas::spawn_point::ShipSpawnPointData;
+use crate::game_module::level_datas::zone_data::{ZoneData, ZoneShape};
+
+// the functions for the scripts, the actors are referenced by the actor id and every call is validated
+// so a script can not touch a removed actor or the ships of the players.
+// the actor id is the actor handle, the id of a removed actor never finds the new actor.
+#[derive(Clone, Copy)]
+pub struct ScriptApi {
+    pub _game_client: *const GameClient,
+}
+
+// Implementation
+fn to_vector3(x: FLOAT, y: FLOAT, z: FLOAT) -> Vector3<f32> {
+    Vector3::new(x as f32, y as f32, z as f32)
+}
+
+impl ScriptApi {
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+
+    fn get_actor(&self, actor_id: INT) -> Option<&ActorController> {
+        if actor_id < 0 {
+            return None;
+        }
+        self.get_game_client().get_actor_manager().get_actor_by_id(actor_id as u64)
+    }
+
+    // the player ship and the remote ships are controlled by the players
+    fn get_commandable_actor(&self, actor_id: INT) -> Option<&mut ActorController> {
+        let player_actor = self.get_game_client().get_actor_manager()._player_actor;
+        match self.get_actor(actor_id) {
+            Some(actor) if false == actor.is_remote_actor() && Some(actor.get_actor_handle()) != player_actor => Some(ptr_as_mut(actor)),
+            _ => None,
+        }
+    }
+
+    // returns -1 when the ship data does not exist
+    pub fn spawn_ship(&self, ship_data_name: &str, position: &Vector3<f32>, is_player_team: bool) -> INT {
+        let game_client = self.get_game_client();
+        if false == game_client.get_project_resources().has_ship_data(ship_data_name) {
+            log::error!("spawn_ship: unknown ship data {}", ship_data_name);
+            return -1;
+        }
+        let spawn_point_data = ShipSpawnPointData {
+            _ship_data_name: ship_data_name.to_string(),
+            _position: position.clone_owned(),
+            ..Default::default()
+        };
+        let actor_handle = game_client.get_actor_manager_mut().create_actor(game_client, &spawn_point_data, is_player_team);
+        actor_handle.to_id() as INT
+    }
+
+    pub fn command_move(&self, actor_id: INT, target_position: &Vector3<f32>) -> bool {
+        match self.get_commandable_actor(actor_id) {
+            Some(actor) => {
+                actor.set_command_actor_move(target_position);
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn command_attack(&self, actor_id: INT, target_position: &Vector3<f32>) -> bool {
+        match self.get_commandable_actor(actor_id) {
+            Some(actor) => {
+                actor.set_command_actor_attack(target_position);
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn clear_command(&self, actor_id: INT) -> bool {
+        match self.get_commandable_actor(actor_id) {
+            Some(actor) => {
+                actor.clear_command_of_actor();
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn is_actor_alive(&self, actor_id: INT) -> bool {
+        self.get_actor(actor_id).is_some()
+    }
+
+    pub fn get_actor_count(&self, is_player_team: bool) -> INT {
+        self.get_game_client().get_actor_manager()._actors.values().filter(|actor| is_player_team == actor.is_player_actor()).count() as INT
+    }
+
+    // returns -1 when the player has no ship
+    pub fn get_player_actor_id(&self) -> INT {
+        match self.get_game_client().get_actor_manager().get_player_actor() {
+            Some(player_actor) => player_actor.get_actor_id() as INT,
+            None => -1,
+        }
+    }
+
+    pub fn is_player_respawning(&self) -> bool {
+        self.get_game_client().get_actor_manager().is_player_respawning()
+    }
+
+    pub fn show_message(&self, message: &str) {
+        self.get_game_client().get_game_ui_manager_mut().add_objective_event_log(message);
+    }
+
+    pub fn end_match(&self, is_victory: bool) {
+        let match_result = if is_victory { MatchResult::Victory } else { MatchResult::Defeat };
+        self.get_game_client().get_game_controller_mut().end_match(match_result);
+    }
+
+    pub fn set_timer(&self, timer_name: &str, seconds: FLOAT, is_repeat: bool) {
+        self.get_game_client().get_script_manager_mut().set_timer(timer_name, seconds as f32, is_repeat);
+    }
+
+    pub fn clear_timer(&self, timer_name: &str) {
+        self.get_game_client().get_script_manager_mut().clear_timer(timer_name);
+    }
+
+    pub fn add_zone(&self, zone_name: &str, position: &Vector3<f32>, radius: FLOAT) {
+        let zone_data = ZoneData {
+            _zone_name: zone_name.to_string(),
+            _position: position.clone_owned(),
+            _shape: ZoneShape::Sphere { _radius: radius as f32 },
+            ..Default::default()
+        };
+        self.get_game_client().get_zone_manager_mut().add_zone(&zone_data);
+    }
+
+    pub fn remove_zone(&self, zone_name: &str) -> bool {
+        self.get_game_client().get_zone_manager_mut().remove_zone(zone_name)
+    }
+
+    pub fn is_actor_in_zone(&self, zone_name: &str, actor_id: INT) -> bool {
+        match self.get_game_client().get_zone_manager().get_zone(zone_name) {
+            Some(zone) => 0 <= actor_id && zone.is_actor_inside(ActorHandle::from_id(actor_id as u64)),
+            None => false,
+        }
+    }
+}
+
+pub fn register_script_api(engine: &mut Engine, script_api: ScriptApi) {
+    engine.register_fn("spawn_ship", move |ship_data_name: &str, x: FLOAT, y: FLOAT, z: FLOAT, is_player_team: bool| {
+        script_api.spawn_ship(ship_data_name, &to_vector3(x, y, z), is_player_team)
+    });
+    engine.register_fn("command_move", move |actor_id: INT, x: FLOAT, y: FLOAT, z: FLOAT| {
+        script_api.command_move(actor_id, &to_vector3(x, y, z))
+    });
+    engine.register_fn("command_attack", move |actor_id: INT, x: FLOAT, y: FLOAT, z: FLOAT| {
+        script_api.command_attack(actor_id, &to_vector3(x, y, z))
+    });
+    engine.register_fn("clear_command", move |actor_id: INT| script_api.clear_command(actor_id));
+    engine.register_fn("is_actor_alive", move |actor_id: INT| script_api.is_actor_alive(actor_id));
+    engine.register_fn("get_actor_count", move |is_player_team: bool| script_api.get_actor_count(is_player_team));
+    engine.register_fn("get_player_actor_id", move || script_api.get_player_actor_id());
+    engine.register_fn("is_player_respawning", move || script_api.is_player_respawning());
+    engine.register_fn("show_message", move |message: &str| script_api.show_message(message));
+    engine.register_fn("end_match", move |is_victory: bool| script_api.end_match(is_victory));
+    engine.register_fn("set_timer", move |timer_name: &str, seconds: FLOAT, is_repeat: bool| {
+        script_api.set_timer(timer_name, seconds, is_repeat)
+    });
+    engine.register_fn("clear_timer", move |timer_name: &str| script_api.clear_timer(timer_name));
+    engine.register_fn("add_zone", move |zone_name: &str, x: FLOAT, y: FLOAT, z: FLOAT, radius: FLOAT| {
+        script_api.add_zone(zone_name, &to_vector3(x, y, z), radius)
+    });
+    engine.register_fn("remove_zone", move |zone_name: &str| script_api.remove_zone(zone_name));
+    engine.register_fn("is_actor_in_zone", move |zone_name: &str, actor_id: INT| script_api.is_actor_in_zone(zone_name, actor_id));
+}
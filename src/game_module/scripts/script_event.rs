@@ -0,0 +1,30 @@
+pub const HOOK_ON_START: &str = "on_start";
+pub const HOOK_ON_ACTOR_DESTROYED: &str = "on_actor_destroyed";
+pub const HOOK_ON_ENTER_ZONE: &str = "on_enter_zone";
+pub const HOOK_ON_EXIT_ZONE: &str = "on_exit_zone";
+pub const HOOK_ON_STAY_ZONE: &str = "on_stay_zone";
+pub const HOOK_ON_TIMER: &str = "on_timer";
+
+// the events are queued while the managers are updated and the hooks are called by the script manager
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptEvent {
+    Start,
+    ActorDestroyed { _actor_id: u64, _is_player_team: bool },
+    EnterZone { _zone_name: String, _actor_id: u64 },
+    ExitZone { _zone_name: String, _actor_id: u64 },
+    StayZone { _zone_name: String, _actor_id: u64 },
+    Timer { _timer_name: String },
+}
+
+impl ScriptEvent {
+    pub fn get_hook_name(&self) -> &'static str {
+        match self {
+            ScriptEvent::Start => HOOK_ON_START,
+            ScriptEvent::ActorDestroyed { .. } => HOOK_ON_ACTOR_DESTROYED,
+            ScriptEvent::EnterZone { .. } => HOOK_ON_ENTER_ZONE,
+            ScriptEvent::ExitZone { .. } => HOOK_ON_EXIT_ZONE,
+            ScriptEvent::StayZone { .. } => HOOK_ON_STAY_ZONE,
+            ScriptEvent::Timer { .. } => HOOK_ON_TIMER,
+        }
+    }
+}
@@ -6,16 +6,20 @@ use rust_engine_3d::renderer::transform_object::TransformObjectData;
 use rust_engine_3d::utilities::system::{RcRefCell, newRcRefCell, ptr_as_ref, ptr_as_mut};
 use crate::application::project_scene_manager::ProjectSceneManager;
 use crate::game_module::actor_manager::calc_floating_height;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actors::actor::ActorHandle;
 use crate::game_module::game_client::GameClient;
-use crate::game_module::ship::ship_controller::{ShipController, ShipControllerData};
-use crate::game_module::weapons::weapon::{WeaponTrait, WeaponData, BeamEmitter, WeaponSlotData};
+use crate::game_module::game_constants::SHIP_POWER_SCALE_MIN;
+use crate::game_module::handle::HandleMap;
+use crate::game_module::pickups::pickup::{PickupData, PickupType};
+use crate::game_module::ship::ship_controller::{ShipController, ShipControllerData, ShipControllerInput, ShipBoostEvent};
+use crate::game_module::weapons::weapon::{WeaponTrait, WeaponData, WeaponHandle, BeamEmitter, WeaponSlotData};
 use rust_engine_3d::utilities::bounding_box::BoundingBox;
 
 #[derive(Serialize, Deserialize,Clone, Copy, Debug, PartialEq)]
 pub enum ShipDataType {
     Scout,
     Tank,
+    Harvester,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -29,6 +33,33 @@ pub struct ShipDataCreateInfo {
     pub _max_shields: f32,
     pub _weapon_solts: Vec<WeaponSlotData>,
     pub _controller_data_name: String,
+    pub _max_energy: f32,
+    pub _energy_generation: f32,
+    pub _shield_regeneration: f32,
+    pub _shield_regeneration_energy_cost: f32,
+    pub _weapon_fire_energy_cost: f32,
+    pub _mass: f32,
+    // half extents of the collision box in the model space
+    pub _collision_half_extents: Vector3<f32>,
+    pub _ramming_damage: f32,
+    pub _destroy_explosion_data_name: String,
+    pub _destroy_effects: Vec<String>,
+    pub _wreck_model_data_name: String,
+    pub _wreck_effects: Vec<String>,
+    pub _wreck_effect_interval: f32,
+    pub _wreck_life_time: f32,
+    pub _debris_model_data_names: Vec<String>,
+    pub _debris_count: u32,
+    pub _debris_speed: f32,
+    pub _drop_table_data_name: String,
+    // zero is not a harvester
+    pub _harvest_capacity: f32,
+    pub _harvest_rate: f32,
+    // production cost and time at the factory
+    pub _cost: f32,
+    pub _build_time: f32,
+    // the fog of war is revealed in the radius
+    pub _sight_radius: f32,
 }
 
 impl Default for ShipDataCreateInfo {
@@ -42,6 +73,29 @@ impl Default for ShipDataCreateInfo {
             _max_shields: 10.0,
             _weapon_solts: vec![WeaponSlotData::default()],
             _controller_data_name: "".to_string(),
+            _max_energy: 100.0,
+            _energy_generation: 10.0,
+            _shield_regeneration: 1.0,
+            _shield_regeneration_energy_cost: 2.0,
+            _weapon_fire_energy_cost: 2.0,
+            _mass: 1.0,
+            _collision_half_extents: Vector3::new(1.0, 0.5, 1.5),
+            _ramming_damage: 0.0,
+            _destroy_explosion_data_name: "".to_string(),
+            _destroy_effects: Vec::new(),
+            _wreck_model_data_name: "".to_string(),
+            _wreck_effects: Vec::new(),
+            _wreck_effect_interval: 1.0,
+            _wreck_life_time: 10.0,
+            _debris_model_data_names: Vec::new(),
+            _debris_count: 4,
+            _debris_speed: 15.0,
+            _drop_table_data_name: "".to_string(),
+            _harvest_capacity: 0.0,
+            _harvest_rate: 10.0,
+            _cost: 100.0,
+            _build_time: 10.0,
+            _sight_radius: 100.0,
         }
     }
 }
@@ -57,17 +111,67 @@ pub struct ShipData {
     pub _max_shields: f32,
     pub _weapon_solts: Vec<WeaponSlotData>,
     pub _contoller_data: RcRefCell<ShipControllerData>,
+    pub _max_energy: f32,
+    pub _energy_generation: f32,
+    pub _shield_regeneration: f32,
+    pub _shield_regeneration_energy_cost: f32,
+    pub _weapon_fire_energy_cost: f32,
+    pub _mass: f32,
+    // half extents of the collision box in the model space
+    pub _collision_half_extents: Vector3<f32>,
+    pub _ramming_damage: f32,
+    pub _destroy_explosion_data_name: String,
+    pub _destroy_effects: Vec<String>,
+    pub _wreck_model_data_name: String,
+    pub _wreck_effects: Vec<String>,
+    pub _wreck_effect_interval: f32,
+    pub _wreck_life_time: f32,
+    pub _debris_model_data_names: Vec<String>,
+    pub _debris_count: u32,
+    pub _debris_speed: f32,
+    pub _drop_table_data_name: String,
+    // zero is not a harvester
+    pub _harvest_capacity: f32,
+    pub _harvest_rate: f32,
+    // production cost and time at the factory
+    pub _cost: f32,
+    pub _build_time: f32,
+    // the fog of war is revealed in the radius
+    pub _sight_radius: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShipPowerType {
+    Shields,
+    Weapons,
+    Engines,
+}
+
+// the allocations are normalized, the default distribution is a third each.
+#[derive(Clone, Debug)]
+pub struct ShipPowerDistribution {
+    pub _shields: f32,
+    pub _weapons: f32,
+    pub _engines: f32,
 }
 
 pub struct ShipInstance {
     pub _ship_data: RcRefCell<ShipData>,
     pub _hull: f32,
     pub _shields: f32,
-    pub _render_object: RcRefCell<RenderObjectData>,
-    pub _transform_object: *mut TransformObjectData,
+    pub _energy: f32,
+    pub _power_distribution: ShipPowerDistribution,
+    pub _damage_boost_scale: f32,
+    pub _damage_boost_time: f32,
+    pub _invulnerable_time: f32,
+    pub _cargo: f32,
+    pub _transform_object: TransformObjectData,
+    pub _bound_box: BoundingBox,
+    // None on the dedicated server
+    pub _render_object: Option<RcRefCell<RenderObjectData>>,
     pub _controller: ShipController,
-    pub _weapons: Vec<Box<dyn WeaponTrait>>,
-    pub _current_weapons: Vec<*const dyn WeaponTrait>,
+    pub _weapons: HandleMap<dyn WeaponTrait>,
+    pub _current_weapons: Vec<WeaponHandle>,
 }
 
 // Implementation
@@ -83,42 +187,135 @@ impl ShipData {
             _max_shields: ship_data_create_info._max_shields,
             _weapon_solts: ship_data_create_info._weapon_solts.clone(),
             _contoller_data: controller_data.clone(),
+            _max_energy: ship_data_create_info._max_energy,
+            _energy_generation: ship_data_create_info._energy_generation,
+            _shield_regeneration: ship_data_create_info._shield_regeneration,
+            _shield_regeneration_energy_cost: ship_data_create_info._shield_regeneration_energy_cost,
+            _weapon_fire_energy_cost: ship_data_create_info._weapon_fire_energy_cost,
+            _mass: ship_data_create_info._mass,
+            _collision_half_extents: ship_data_create_info._collision_half_extents.clone_owned(),
+            _ramming_damage: ship_data_create_info._ramming_damage,
+            _destroy_explosion_data_name: ship_data_create_info._destroy_explosion_data_name.clone(),
+            _destroy_effects: ship_data_create_info._destroy_effects.clone(),
+            _wreck_model_data_name: ship_data_create_info._wreck_model_data_name.clone(),
+            _wreck_effects: ship_data_create_info._wreck_effects.clone(),
+            _wreck_effect_interval: ship_data_create_info._wreck_effect_interval,
+            _wreck_life_time: ship_data_create_info._wreck_life_time,
+            _debris_model_data_names: ship_data_create_info._debris_model_data_names.clone(),
+            _debris_count: ship_data_create_info._debris_count,
+            _debris_speed: ship_data_create_info._debris_speed,
+            _drop_table_data_name: ship_data_create_info._drop_table_data_name.clone(),
+            _harvest_capacity: ship_data_create_info._harvest_capacity,
+            _harvest_rate: ship_data_create_info._harvest_rate,
+            _cost: ship_data_create_info._cost,
+            _build_time: ship_data_create_info._build_time,
+            _sight_radius: ship_data_create_info._sight_radius,
         })
     }
 }
 
+impl Default for ShipPowerDistribution {
+    fn default() -> ShipPowerDistribution {
+        ShipPowerDistribution {
+            _shields: 1.0 / 3.0,
+            _weapons: 1.0 / 3.0,
+            _engines: 1.0 / 3.0,
+        }
+    }
+}
+
+impl ShipPowerDistribution {
+    pub fn get_power(&self, power_type: ShipPowerType) -> f32 {
+        match power_type {
+            ShipPowerType::Shields => self._shields,
+            ShipPowerType::Weapons => self._weapons,
+            ShipPowerType::Engines => self._engines,
+        }
+    }
+
+    // the scale is 1.0 with the default distribution and 3.0 when all power goes to one system.
+    // a system without power still keeps the minimum scale so that the ship can move and fire.
+    pub fn get_power_scale(&self, power_type: ShipPowerType) -> f32 {
+        SHIP_POWER_SCALE_MIN.max(self.get_power(power_type) * 3.0)
+    }
+
+    // move the power from the other systems to the given system
+    pub fn add_power(&mut self, power_type: ShipPowerType, amount: f32) {
+        let current_power = self.get_power(power_type);
+        let amount = amount.min(1.0 - current_power);
+        let others = 1.0 - current_power;
+        let (mut shields, mut weapons, mut engines) = (self._shields, self._weapons, self._engines);
+        if 0.0 < others {
+            let ratio = (others - amount) / others;
+            shields *= ratio;
+            weapons *= ratio;
+            engines *= ratio;
+        }
+        match power_type {
+            ShipPowerType::Shields => shields = current_power + amount,
+            ShipPowerType::Weapons => weapons = current_power + amount,
+            ShipPowerType::Engines => engines = current_power + amount,
+        }
+        self._shields = shields;
+        self._weapons = weapons;
+        self._engines = engines;
+    }
+}
+
 impl ShipInstance {
     pub fn create_ship_instance(
         ship_data: &RcRefCell<ShipData>,
-        render_object: &RcRefCell<RenderObjectData>
+        position: &Vector3<f32>,
+        rotation: &Vector3<f32>,
+        render_object: Option<RcRefCell<RenderObjectData>>
     ) -> ShipInstance {
-        let transform_object: &TransformObjectData = &render_object.borrow()._transform_object;
-        let floating_height = calc_floating_height(&render_object.borrow());
-        ShipInstance {
+        let mut transform_object = TransformObjectData::new_transform_object_data();
+        transform_object.set_position(position);
+        transform_object.set_rotation(rotation);
+        transform_object.update_transform_object();
+        let floating_height = calc_floating_height(&ship_data.borrow());
+        let mut ship = ShipInstance {
             _ship_data: ship_data.clone(),
             _hull: 0.0,
             _shields: 0.0,
-            _render_object: render_object.clone(),
-            _transform_object: (transform_object as *const TransformObjectData as *mut TransformObjectData).clone(),
+            _energy: 0.0,
+            _power_distribution: ShipPowerDistribution::default(),
+            _damage_boost_scale: 1.0,
+            _damage_boost_time: 0.0,
+            _invulnerable_time: 0.0,
+            _cargo: 0.0,
+            _transform_object: transform_object,
+            _bound_box: BoundingBox::default(),
+            _render_object: render_object,
             _controller: ShipController::create_ship_controller(
                 &ship_data.borrow()._contoller_data,
-                transform_object.get_position(),
-                transform_object.get_rotation(),
+                position,
+                rotation,
                 floating_height
             ),
-            _weapons: Vec::new(),
+            _weapons: HandleMap::new(),
             _current_weapons: Vec::new(),
-        }
+        };
+        ship.update_bound_box();
+        ship
     }
 
-    pub fn initialize_ship_instance(&mut self, owner_actor: *const ActorController, project_scene_manager: &mut ProjectSceneManager) {
+    pub fn initialize_ship_instance(&mut self, owner_actor: ActorHandle, project_scene_manager: &mut ProjectSceneManager) {
         let ship_data = unsafe { &*self._ship_data.as_ptr() };
         self._hull = ship_data._max_hull;
         self._shields = ship_data._max_shields;
+        self._energy = ship_data._max_energy;
 
         // add weapons
         for weapon_slot in self._ship_data.borrow()._weapon_solts.iter() {
-            let weapon_data: RcRefCell<WeaponData> = project_scene_manager.get_project_resources().get_weapon_data("beam_emitter").clone();
+            let project_resources = project_scene_manager.get_project_resources();
+            let weapon_data_name = if project_resources.has_weapon_data(&weapon_slot._weapon_data_name) {
+                weapon_slot._weapon_data_name.as_str()
+            } else {
+                log::warn!("initialize_ship_instance: not found weapon data {}", weapon_slot._weapon_data_name);
+                "beam_emitter"
+            };
+            let weapon_data: RcRefCell<WeaponData> = project_resources.get_weapon_data(weapon_data_name).clone();
             let mut weapon_slot_transform = TransformObjectData::new_transform_object_data();
             weapon_slot_transform.set_position(&weapon_slot._position);
             weapon_slot_transform.set_rotation(&weapon_slot._rotation);
@@ -134,29 +331,65 @@ impl ShipInstance {
                 owner_actor,
                 &weapon_data,
                 &weapon_slot_transform,
-                &weapon_render_object,
+                weapon_render_object,
             );
-            self._current_weapons.push(weapon.as_ref());
-            self._weapons.push(weapon);
+            let weapon_handle = self._weapons.insert(weapon);
+            self._current_weapons.push(weapon_handle);
         }
     }
     pub fn remove_ship_instance(&mut self, project_scene_manager: &mut ProjectSceneManager) {
-        project_scene_manager.remove_skeletal_render_object(&self._render_object.borrow()._render_object_name);
-        for weapon in self._weapons.iter_mut() {
+        if let Some(render_object) = &self._render_object {
+            project_scene_manager.remove_skeletal_render_object(&render_object.borrow()._render_object_name);
+        }
+        for weapon in self._weapons.values_mut() {
             weapon.remove_weapon(project_scene_manager);
         }
         self._weapons.clear();
+        self._current_weapons.clear();
     }
     pub fn get_ship_data(&self) -> &ShipData { ptr_as_ref(self._ship_data.as_ptr()) }
     pub fn get_controller(&self) -> &ShipController {
         &self._controller
     }
     pub fn get_controller_mut(&mut self) -> &mut ShipController { &mut self._controller }
-    pub fn get_bound_box(&self) -> &BoundingBox { &ptr_as_ref(self._render_object.as_ptr())._bound_box }
-    pub fn get_transform(&self) -> &TransformObjectData { ptr_as_ref(self._transform_object) }
-    pub fn get_transform_mut(&self) -> &mut TransformObjectData { ptr_as_mut(self._transform_object) }
-    pub fn get_current_weapons(&self) -> &Vec<*const dyn WeaponTrait> {
-        &self._current_weapons
+    pub fn get_bound_box(&self) -> &BoundingBox { &self._bound_box }
+    pub fn get_mass(&self) -> f32 { f32::EPSILON.max(self.get_ship_data()._mass) }
+    // moves the controller and the transform together, used by the collision separation.
+    pub fn set_position(&mut self, position: &Vector3<f32>) {
+        self._controller.set_position(position);
+        self._transform_object.set_position(position);
+        self._transform_object.update_matrix();
+        self.update_bound_box();
+        self.update_render_object_transform();
+    }
+    pub fn get_transform(&self) -> &TransformObjectData { &self._transform_object }
+    pub fn get_transform_mut(&self) -> &mut TransformObjectData { ptr_as_mut(&self._transform_object) }
+    // the world bound box of the rotated collision box
+    fn update_bound_box(&mut self) {
+        let half_extents = &self.get_ship_data()._collision_half_extents;
+        let matrix = ptr_as_ref(self._transform_object.get_matrix());
+        let rotation = matrix.fixed_view::<3, 3>(0, 0).abs();
+        let world_half_extents: Vector3<f32> = rotation * half_extents;
+        let position = self._transform_object.get_position();
+        self._bound_box = BoundingBox::create_bounding_box(&(position - &world_half_extents), &(position + &world_half_extents));
+    }
+    // the render object follows the ship transform
+    fn update_render_object_transform(&self) {
+        if let Some(render_object) = &self._render_object {
+            let render_transform = &mut render_object.borrow_mut()._transform_object;
+            render_transform.set_position(self._transform_object.get_position());
+            render_transform.set_rotation(self._transform_object.get_rotation());
+            render_transform.update_matrix();
+        }
+    }
+    pub fn get_weapon(&self, weapon_handle: WeaponHandle) -> Option<&dyn WeaponTrait> {
+        self._weapons.get(weapon_handle)
+    }
+    pub fn get_weapon_mut(&mut self, weapon_handle: WeaponHandle) -> Option<&mut dyn WeaponTrait> {
+        self._weapons.get_mut(weapon_handle)
+    }
+    pub fn get_current_weapons(&self) -> Vec<&dyn WeaponTrait> {
+        self._current_weapons.iter().filter_map(|weapon_handle| self._weapons.get(*weapon_handle)).collect()
     }
     pub fn get_hull_point(&self) -> f32 {
         self._hull
@@ -170,23 +403,206 @@ impl ShipInstance {
     pub fn get_max_shield_point(&self) -> f32 {
         self.get_ship_data()._max_shields
     }
+    pub fn get_energy(&self) -> f32 {
+        self._energy
+    }
+    pub fn get_max_energy(&self) -> f32 {
+        self.get_ship_data()._max_energy
+    }
+    pub fn get_power_distribution(&self) -> &ShipPowerDistribution {
+        &self._power_distribution
+    }
+    pub fn add_power(&mut self, power_type: ShipPowerType, amount: f32) {
+        self._power_distribution.add_power(power_type, amount);
+    }
+    pub fn reset_power_distribution(&mut self) {
+        self._power_distribution = ShipPowerDistribution::default();
+    }
+    pub fn get_power_scale(&self, power_type: ShipPowerType) -> f32 {
+        self._power_distribution.get_power_scale(power_type)
+    }
+    pub fn is_destroyed(&self) -> bool {
+        self._hull <= 0.0
+    }
+    pub fn is_harvester(&self) -> bool {
+        0.0 < self.get_ship_data()._harvest_capacity
+    }
+    pub fn get_cargo(&self) -> f32 {
+        self._cargo
+    }
+    pub fn get_cargo_space(&self) -> f32 {
+        0f32.max(self.get_ship_data()._harvest_capacity - self._cargo)
+    }
+    pub fn is_cargo_full(&self) -> bool {
+        self.get_cargo_space() <= 0.0
+    }
+    pub fn add_cargo(&mut self, amount: f32) {
+        self._cargo = self.get_ship_data()._harvest_capacity.min(self._cargo + amount);
+    }
+    // returns the unloaded cargo
+    pub fn unload_cargo(&mut self) -> f32 {
+        let cargo = self._cargo;
+        self._cargo = 0.0;
+        cargo
+    }
+    pub fn get_damage_scale(&self) -> f32 {
+        if 0.0 < self._damage_boost_time { self._damage_boost_scale } else { 1.0 }
+    }
+    // returns false when the pickup has no effect on the ship, the pickup is not consumed then.
+    pub fn apply_pickup(&mut self, pickup_data: &PickupData) -> bool {
+        let ship_data = ptr_as_ref(self._ship_data.as_ptr());
+        match pickup_data._pickup_type {
+            PickupType::HullRepair => {
+                if ship_data._max_hull <= self._hull {
+                    return false;
+                }
+                self._hull = ship_data._max_hull.min(self._hull + pickup_data._amount);
+            },
+            PickupType::ShieldRecharge => {
+                if ship_data._max_shields <= self._shields {
+                    return false;
+                }
+                self._shields = ship_data._max_shields.min(self._shields + pickup_data._amount);
+            },
+            PickupType::DamageBoost => {
+                self._damage_boost_scale = pickup_data._amount;
+                self._damage_boost_time = pickup_data._duration;
+            },
+            PickupType::Ammo => {
+                let mut is_consumed = false;
+                for weapon in self._weapons.values_mut() {
+                    let weapon_data = ptr_as_ref(weapon.get_weapon_data());
+                    let weapon_supply = weapon.get_weapon_supply_mut();
+                    let reserve_ammo = weapon_supply._reserve_ammo;
+                    weapon_supply.add_reserve_ammo(weapon_data, pickup_data._amount as i32);
+                    is_consumed |= reserve_ammo != weapon_supply._reserve_ammo;
+                }
+                return is_consumed;
+            },
+        }
+        true
+    }
+    pub fn is_invulnerable(&self) -> bool {
+        0.0 < self._invulnerable_time
+    }
+    pub fn set_invulnerable_time(&mut self, invulnerable_time: f32) {
+        self._invulnerable_time = invulnerable_time;
+    }
+    // shields absorb the shield damage first, the hull takes damage once shields are depleted.
+    // returns the applied (shield_damage, hull_damage)
+    pub fn apply_damage(&mut self, shield_damage: f32, hull_damage: f32) -> (f32, f32) {
+        if self.is_invulnerable() {
+            return (0.0, 0.0);
+        }
+        let ship_data = self.get_ship_data();
+        let mut applied_shield_damage: f32 = 0.0;
+        let mut applied_hull_damage: f32 = 0.0;
+        if 0.0 < self._shields {
+            applied_shield_damage = self._shields.min(0f32.max(shield_damage - ship_data._shield_armor));
+            self._shields -= applied_shield_damage;
+        } else {
+            applied_hull_damage = self._hull.min(0f32.max(hull_damage - ship_data._hull_armor));
+            self._hull -= applied_hull_damage;
+        }
+        (applied_shield_damage, applied_hull_damage)
+    }
+    pub fn can_fire(&self) -> bool {
+        self._current_weapons.iter().filter_map(|weapon_handle| self._weapons.get(*weapon_handle)).any(|weapon| weapon.can_fire())
+    }
+    pub fn is_reloading(&self) -> bool {
+        self._current_weapons.iter().filter_map(|weapon_handle| self._weapons.get(*weapon_handle)).any(|weapon| weapon.get_weapon_supply().is_reloading())
+    }
+    pub fn reload_weapons(&mut self) {
+        for weapon_handle in self._current_weapons.iter() {
+            if let Some(weapon) = self._weapons.get_mut(*weapon_handle) {
+                weapon.reload_weapon();
+            }
+        }
+    }
     pub fn ship_fire(&mut self, game_client: &GameClient, fire_start: &Vector3<f32>, fire_dir: &Vector3<f32>, target_position: &Vector3<f32>) {
-        for weapon in self._current_weapons.iter() {
-            let weapon: &mut dyn WeaponTrait = unsafe { &mut *(*weapon as *mut dyn WeaponTrait) };
-            weapon.weapon_fire(game_client, fire_start, fire_dir, target_position);
+        // the server fires for the network client, the bullets come back with the snapshots.
+        let network_manager = game_client.get_network_manager_mut();
+        if network_manager.is_client() {
+            network_manager.add_fire_command(fire_start, fire_dir, target_position);
+            return;
+        }
+
+        let weapon_fire_energy_cost = self.get_ship_data()._weapon_fire_energy_cost;
+        for weapon_handle in self._current_weapons.iter() {
+            let weapon = match self._weapons.get_mut(*weapon_handle) {
+                Some(weapon) => weapon,
+                None => continue,
+            };
+            if weapon.can_fire() && weapon_fire_energy_cost <= self._energy {
+                weapon.weapon_fire(game_client, fire_start, fire_dir, target_position);
+                self._energy -= weapon_fire_energy_cost;
+            }
         }
     }
+    pub fn update_energy(&mut self, delta_time: f32) {
+        let ship_data = ptr_as_ref(self._ship_data.as_ptr());
+        self._energy = ship_data._max_energy.min(self._energy + ship_data._energy_generation * delta_time);
+
+        // shield regeneration
+        if self._shields < ship_data._max_shields && false == self.is_destroyed() {
+            let mut shield_regeneration = ship_data._shield_regeneration * self.get_power_scale(ShipPowerType::Shields) * delta_time;
+            shield_regeneration = shield_regeneration.min(ship_data._max_shields - self._shields);
+            if 0.0 < ship_data._shield_regeneration_energy_cost {
+                shield_regeneration = shield_regeneration.min(self._energy / ship_data._shield_regeneration_energy_cost);
+            }
+            self._shields += shield_regeneration;
+            self._energy -= shield_regeneration * ship_data._shield_regeneration_energy_cost;
+        }
+    }
+    // the energy, the shields and the timers of the ship, the weapons are updated with the transform.
+    pub fn update_ship_systems(&mut self, delta_time: f32) {
+        self.update_energy(delta_time);
+        self._damage_boost_time = 0f32.max(self._damage_boost_time - delta_time);
+        self._invulnerable_time = 0f32.max(self._invulnerable_time - delta_time);
+    }
     pub fn update_ship(&mut self, game_client: &GameClient, delta_time: f32) {
-        let ship_transform = ptr_as_mut(self._transform_object);
+        let ship_transform = ptr_as_mut(&self._transform_object);
+
+        self.update_ship_systems(delta_time);
 
+        self._controller.set_engine_power_scale(self.get_power_scale(ShipPowerType::Engines));
         self._controller.update_controller(game_client, ship_transform, delta_time);
 
-        ship_transform.set_rotation(self._controller.get_rotation());
+        if let Some(boost_event) = self._controller.get_boost_event() {
+            let controller_data = self._controller._controller_data.borrow();
+            let sound_bank = match boost_event {
+                ShipBoostEvent::BoostStart => &controller_data._boost_start_sound_bank,
+                ShipBoostEvent::BoostStop => &controller_data._boost_stop_sound_bank,
+            };
+            if false == sound_bank.is_empty() {
+                game_client.play_audio_bank(sound_bank);
+            }
+        }
+
+        self.update_ship_transform(delta_time);
+    }
+    // the transform and the weapons follow the controller, the network snapshots move the remote ships with this.
+    pub fn update_ship_transform(&mut self, delta_time: f32) {
+        let ship_transform = ptr_as_mut(&self._transform_object);
+        ship_transform.set_rotation(&self._controller.get_visual_rotation());
         ship_transform.set_position(self._controller.get_position());
         ship_transform.update_matrix();
+        self.update_bound_box();
+        self.update_render_object_transform();
 
-        for weapon in self._weapons.iter_mut() {
+        for weapon in self._weapons.values_mut() {
             weapon.update_weapon(ship_transform, delta_time);
         }
     }
+    // replays the movement of an input without the energy and the weapons, used by the client prediction.
+    pub fn predict_movement(&mut self, game_client: &GameClient, controller_input: &ShipControllerInput, delta_time: f32) {
+        let ship_transform = ptr_as_mut(&self._transform_object);
+        self._controller.set_controller_input(controller_input);
+        self._controller.update_controller(game_client, ship_transform, delta_time);
+        ship_transform.set_rotation(&self._controller.get_visual_rotation());
+        ship_transform.set_position(self._controller.get_position());
+        ship_transform.update_matrix();
+        self.update_bound_box();
+        self.update_render_object_transform();
+    }
 }
\ No newline at end of file
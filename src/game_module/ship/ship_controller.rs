@@ -14,6 +14,12 @@ pub enum ShipControllerDataType {
     TankController,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShipBoostEvent {
+    BoostStart,
+    BoostStop,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct ShipControllerData {
@@ -24,8 +30,18 @@ pub struct ShipControllerData {
     pub _side_step_roll: f32,
     pub _side_step_roll_speed: f32,
     pub _boost_acceleration: f32,
+    pub _boost_fuel_capacity: f32,
+    pub _boost_fuel_burn_rate: f32,
+    pub _boost_fuel_regen_delay: f32,
+    pub _boost_fuel_regen_rate: f32,
+    pub _boost_start_sound_bank: String,
+    pub _boost_stop_sound_bank: String,
     pub _max_rotation_speed: f32,
-    pub _rotation_acceleration: f32
+    pub _rotation_acceleration: f32,
+    pub _terrain_align_stiffness: f32,
+    pub _terrain_align_damping: f32,
+    pub _slope_slowdown_angle: f32,
+    pub _max_slope_angle: f32,
 }
 
 impl Default for ShipControllerData {
@@ -38,12 +54,48 @@ impl Default for ShipControllerData {
             _side_step_roll: 0.3,
             _side_step_roll_speed: 2.0,
             _boost_acceleration: 1.5,
+            _boost_fuel_capacity: 100.0,
+            _boost_fuel_burn_rate: 30.0,
+            _boost_fuel_regen_delay: 1.0,
+            _boost_fuel_regen_rate: 20.0,
+            _boost_start_sound_bank: "".to_string(),
+            _boost_stop_sound_bank: "".to_string(),
             _max_rotation_speed: 10.0,
-            _rotation_acceleration: 100.0
+            _rotation_acceleration: 100.0,
+            _terrain_align_stiffness: 60.0,
+            _terrain_align_damping: 12.0,
+            _slope_slowdown_angle: 0.5,
+            _max_slope_angle: 0.9,
         }
     }
 }
 
+// the inputs of a controller update, the network client sends them to the server.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ShipControllerInput {
+    pub _acceleration: Vector3<f32>,
+    pub _rotation_acceleration: Vector2<f32>,
+    pub _rotation_velocity: Vector2<f32>,
+    pub _yaw: f32,
+    pub _boost: bool,
+}
+
+// the simulated state of the controller, the network snapshots carry it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ShipControllerState {
+    pub _position: Vector3<f32>,
+    pub _rotation: Vector3<f32>,
+    pub _velocity: Vector3<f32>,
+    pub _rotation_velocity: Vector2<f32>,
+    pub _terrain_rotation: Vector2<f32>,
+    pub _terrain_rotation_velocity: Vector2<f32>,
+    pub _boost_fuel: f32,
+    pub _boost_fuel_regen_delay: f32,
+    pub _on_ground: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct ShipController {
     pub _controller_data: RcRefCell<ShipControllerData>,
@@ -56,8 +108,16 @@ pub struct ShipController {
     pub _rotation_acceleration: Vector2<f32>,
     pub _position: Vector3<f32>,
     pub _rotation: Vector3<f32>,
+    pub _terrain_rotation: Vector2<f32>,
+    pub _terrain_rotation_velocity: Vector2<f32>,
     pub _boost: bool,
+    pub _boosting: bool,
+    pub _boost_fuel: f32,
+    pub _boost_fuel_regen_delay: f32,
+    pub _boost_event: Option<ShipBoostEvent>,
     pub _on_ground: bool,
+    pub _engine_power_scale: f32,
+    pub _last_input: ShipControllerInput,
 }
 
 // implementation
@@ -79,12 +139,27 @@ impl ShipController {
             _rotation_velocity: Vector2::zeros(),
             _position: postion.clone_owned(),
             _rotation: rotation.clone_owned(),
+            _terrain_rotation: Vector2::zeros(),
+            _terrain_rotation_velocity: Vector2::zeros(),
             _boost: false,
+            _boosting: false,
+            _boost_fuel: controller_data.borrow()._boost_fuel_capacity,
+            _boost_fuel_regen_delay: 0.0,
+            _boost_event: None,
             _on_ground: false,
+            _engine_power_scale: 1.0,
+            _last_input: ShipControllerInput::default(),
         }
     }
 
     pub fn boost_on(&mut self) { self._boost = true; }
+    pub fn set_on_ground(&mut self, on_ground: bool) { self._on_ground = on_ground; }
+    pub fn is_boosting(&self) -> bool { self._boosting }
+    pub fn get_boost_fuel(&self) -> f32 { self._boost_fuel }
+    pub fn get_boost_fuel_capacity(&self) -> f32 { self._controller_data.borrow()._boost_fuel_capacity }
+    // the boost start or stop event of the last update, used for effects and audio.
+    pub fn get_boost_event(&self) -> Option<ShipBoostEvent> { self._boost_event }
+    pub fn set_engine_power_scale(&mut self, engine_power_scale: f32) { self._engine_power_scale = engine_power_scale; }
     pub fn acceleration_side(&mut self, acceleration: f32) { self._acceleration.x = acceleration; }
     pub fn acceleration_vertical(&mut self, acceleration: f32) { self._acceleration.y = acceleration; }
     pub fn acceleration_forward(&mut self, acceleration: f32) { self._acceleration.z = acceleration; }
@@ -106,14 +181,82 @@ impl ShipController {
     pub fn get_yaw(&self) -> f32 { self._rotation.y }
     pub fn get_roll(&self) -> f32 { self._rotation.z }
     pub fn set_rotation(&mut self, rotation: &Vector3<f32>) { self._rotation.clone_from(rotation); }
+    // the rotation of the ship model, the terrain pitch and roll are added under the side step roll.
+    pub fn get_visual_rotation(&self) -> Vector3<f32> {
+        Vector3::new(self._rotation.x + self._terrain_rotation.x, self._rotation.y, self._rotation.z + self._terrain_rotation.y)
+    }
     pub fn set_pitch(&mut self, pitch: f32) { self._rotation.x = pitch; }
     pub fn set_yaw(&mut self, yaw: f32) { self._rotation.y = yaw; }
     pub fn set_roll(&mut self, roll: f32) { self._rotation.z = roll; }
+    pub fn get_controller_input(&self) -> ShipControllerInput {
+        ShipControllerInput {
+            _acceleration: self._acceleration.clone_owned(),
+            _rotation_acceleration: self._rotation_acceleration.clone_owned(),
+            _rotation_velocity: self._rotation_velocity.clone_owned(),
+            _yaw: self._rotation.y,
+            _boost: self._boost,
+        }
+    }
+    pub fn set_controller_input(&mut self, controller_input: &ShipControllerInput) {
+        self._acceleration.clone_from(&controller_input._acceleration);
+        self._rotation_acceleration.clone_from(&controller_input._rotation_acceleration);
+        self._rotation_velocity.clone_from(&controller_input._rotation_velocity);
+        self._rotation.y = controller_input._yaw;
+        self._boost = controller_input._boost;
+    }
+    // the input of the last update_controller
+    pub fn get_last_input(&self) -> &ShipControllerInput { &self._last_input }
+    pub fn get_controller_state(&self) -> ShipControllerState {
+        ShipControllerState {
+            _position: self._position.clone_owned(),
+            _rotation: self._rotation.clone_owned(),
+            _velocity: self._velocity.clone_owned(),
+            _rotation_velocity: self._rotation_velocity.clone_owned(),
+            _terrain_rotation: self._terrain_rotation.clone_owned(),
+            _terrain_rotation_velocity: self._terrain_rotation_velocity.clone_owned(),
+            _boost_fuel: self._boost_fuel,
+            _boost_fuel_regen_delay: self._boost_fuel_regen_delay,
+            _on_ground: self._on_ground,
+        }
+    }
+    pub fn set_controller_state(&mut self, controller_state: &ShipControllerState) {
+        self._position.clone_from(&controller_state._position);
+        self._rotation.clone_from(&controller_state._rotation);
+        self._velocity.clone_from(&controller_state._velocity);
+        self._prev_velocity.clone_from(&controller_state._velocity);
+        self._rotation_velocity.clone_from(&controller_state._rotation_velocity);
+        self._terrain_rotation.clone_from(&controller_state._terrain_rotation);
+        self._terrain_rotation_velocity.clone_from(&controller_state._terrain_rotation_velocity);
+        self._boost_fuel = controller_state._boost_fuel;
+        self._boost_fuel_regen_delay = controller_state._boost_fuel_regen_delay;
+        self._on_ground = controller_state._on_ground;
+    }
     pub fn update_controller(&mut self, game_client: &GameClient, transform: &TransformObjectData, delta_time: f32) {
+        self._last_input = self.get_controller_input();
         let mut goal_roll = 0.0;
 
         let controller_data = self._controller_data.borrow();
-        let boost_acceleration = if self._boost { controller_data._boost_acceleration } else { 1.0 };
+
+        // boost fuel
+        let boosting = self._boost && 0.0 < self._boost_fuel;
+        if self._boost {
+            self._boost_fuel = 0f32.max(self._boost_fuel - controller_data._boost_fuel_burn_rate * delta_time);
+            self._boost_fuel_regen_delay = controller_data._boost_fuel_regen_delay;
+        } else if 0.0 < self._boost_fuel_regen_delay {
+            self._boost_fuel_regen_delay = 0f32.max(self._boost_fuel_regen_delay - delta_time);
+        } else {
+            self._boost_fuel = controller_data._boost_fuel_capacity.min(self._boost_fuel + controller_data._boost_fuel_regen_rate * delta_time);
+        }
+        self._boost_event = if boosting == self._boosting {
+            None
+        } else if boosting {
+            Some(ShipBoostEvent::BoostStart)
+        } else {
+            Some(ShipBoostEvent::BoostStop)
+        };
+        self._boosting = boosting;
+
+        let boost_acceleration = self._engine_power_scale * if boosting { controller_data._boost_acceleration } else { 1.0 };
         let dir_forward = make_normalize_xz(transform.get_front());
         let dir_side = make_normalize_xz(transform.get_right());
 
@@ -153,6 +296,21 @@ impl ShipController {
             self._ground_speed = ground_speed;
         }
 
+        // slope limit
+        let project_scene_manager = game_client.get_project_scene_manager();
+        let terrain_normal = project_scene_manager.get_height_normal(&self._position, 0);
+        if self._on_ground && (0.0 != self._velocity.x || 0.0 != self._velocity.z) {
+            let (uphill_dir, slope) = make_normalize_xz_with_norm(&Vector3::new(-terrain_normal.x, 0.0, -terrain_normal.z));
+            let slope_angle = (slope / terrain_normal.y).atan();
+            let uphill_speed = uphill_dir.dot(&self._velocity);
+            if 0.0 < uphill_speed && controller_data._slope_slowdown_angle < slope_angle {
+                let slope_range = (controller_data._max_slope_angle - controller_data._slope_slowdown_angle).max(f32::EPSILON);
+                let block_ratio = 1.0f32.min((slope_angle - controller_data._slope_slowdown_angle) / slope_range);
+                self._velocity.x -= uphill_dir.x * uphill_speed * block_ratio;
+                self._velocity.z -= uphill_dir.z * uphill_speed * block_ratio;
+            }
+        }
+
         // apply gravity
         if 0.0 == self._acceleration.y && false == self._on_ground {
             self._velocity.y -= GRAVITY * delta_time;
@@ -162,7 +320,6 @@ impl ShipController {
         let mut position = &self._position + &self._velocity * delta_time;
         if position != self._position || false == self._on_ground {
             self._on_ground = false;
-            let project_scene_manager = game_client.get_project_scene_manager();
             let floating_height = project_scene_manager.get_height_bilinear(&position, 0) + self._floating_height;
             if position.y < floating_height {
                 position.y = floating_height;
@@ -200,6 +357,18 @@ impl ShipController {
             self._rotation.z = roll % TWO_PI;
         }
 
+        // terrain alignment, spring damper toward the terrain normal
+        let goal_terrain_rotation: Vector2<f32> = if self._on_ground {
+            let terrain_pitch = (terrain_normal.dot(&dir_forward) / terrain_normal.y).atan();
+            let terrain_roll = (-terrain_normal.dot(&dir_side) / terrain_normal.y).atan();
+            Vector2::new(terrain_pitch, terrain_roll)
+        } else {
+            Vector2::zeros()
+        };
+        let terrain_rotation_acceleration: Vector2<f32> = (&goal_terrain_rotation - &self._terrain_rotation) * controller_data._terrain_align_stiffness - &self._terrain_rotation_velocity * controller_data._terrain_align_damping;
+        self._terrain_rotation_velocity += terrain_rotation_acceleration * delta_time;
+        self._terrain_rotation += &self._terrain_rotation_velocity * delta_time;
+
         // pitch, yaw
         self._rotation.x = (self._rotation.x + self.get_velocity_pitch() * delta_time) % TWO_PI;
         self._rotation.y = (self._rotation.y + self.get_velocity_yaw() * delta_time) % TWO_PI;
@@ -0,0 +1,86 @@
+use nalgebra::{Vector2, Vector3};
+
+use rust_engine_3d::renderer::camera::CameraObjectData;
+use rust_engine_3d::renderer::ui::{UIManager, Widget, UIWidgetTypes, HorizontalAlign, VerticalAlign, WidgetDefault};
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use rust_engine_3d::utilities::system::ptr_as_mut;
+
+const DAMAGE_NUMBER_LIFE_TIME: f32 = 1.0;
+const DAMAGE_NUMBER_RISE_SPEED: f32 = 3.0;
+const DAMAGE_NUMBER_WIDTH: f32 = 60.0;
+const DAMAGE_NUMBER_HEIGHT: f32 = 20.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DamageNumberType {
+    Shield,
+    Hull,
+}
+
+pub struct DamageNumber {
+    pub _widget: *const WidgetDefault,
+    pub _world_position: Vector3<f32>,
+    pub _elapsed_time: f32,
+    pub _is_alive: bool,
+}
+
+// Implementation
+impl DamageNumber {
+    pub fn create_damage_number(root_widget: &mut dyn Widget) -> DamageNumber {
+        let damage_number_widget = UIManager::create_widget("damage_number", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(damage_number_widget.as_ref()).get_ui_component_mut();
+        ui_component.set_size(DAMAGE_NUMBER_WIDTH, DAMAGE_NUMBER_HEIGHT);
+        ui_component.set_font_size(20.0);
+        ui_component.set_halign(HorizontalAlign::CENTER);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_color(get_color32(0, 0, 0, 0));
+        ui_component.set_visible(false);
+        root_widget.add_widget(&damage_number_widget);
+
+        DamageNumber {
+            _widget: damage_number_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _world_position: Vector3::zeros(),
+            _elapsed_time: 0.0,
+            _is_alive: false,
+        }
+    }
+
+    pub fn show_damage_number(&mut self, world_position: &Vector3<f32>, damage: f32, damage_number_type: DamageNumberType) {
+        let font_color = match damage_number_type {
+            DamageNumberType::Shield => get_color32(100, 150, 255, 255),
+            DamageNumberType::Hull => get_color32(255, 150, 0, 255),
+        };
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        ui_component.set_text(&format!("{}", damage.ceil() as i32));
+        ui_component.set_font_color(font_color);
+        ui_component.set_opacity(1.0);
+        self._world_position.clone_from(world_position);
+        self._elapsed_time = 0.0;
+        self._is_alive = true;
+    }
+
+    pub fn update_damage_number(&mut self, main_camera: &CameraObjectData, delta_time: f32) {
+        if false == self._is_alive {
+            return;
+        }
+
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        self._elapsed_time += delta_time;
+        if DAMAGE_NUMBER_LIFE_TIME <= self._elapsed_time {
+            self._is_alive = false;
+            ui_component.set_visible(false);
+            return;
+        }
+
+        let position: Vector3<f32> = &self._world_position + Vector3::new(0.0, self._elapsed_time * DAMAGE_NUMBER_RISE_SPEED, 0.0);
+        let camera_front = -main_camera.get_camera_front() as Vector3<f32>;
+        if (&position - main_camera.get_camera_position()).dot(&camera_front) <= 0.0 {
+            ui_component.set_visible(false);
+            return;
+        }
+
+        let screen_pos: Vector2<f32> = main_camera.convert_world_to_screen(&position, false);
+        ui_component.set_center(screen_pos.x, screen_pos.y);
+        ui_component.set_opacity(1.0 - self._elapsed_time / DAMAGE_NUMBER_LIFE_TIME);
+        ui_component.set_visible(true);
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+
+use nalgebra::Vector2;
+
+use rust_engine_3d::renderer::ui::{UIManager, Widget, UIWidgetTypes, HorizontalAlign, VerticalAlign, UILayoutType, Orientation, WidgetDefault};
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use rust_engine_3d::utilities::system::ptr_as_mut;
+
+const EVENT_LOG_MAX_LINES: usize = 6;
+const EVENT_LOG_LIFE_TIME: f32 = 6.0;
+const EVENT_LOG_FADE_OUT_TIME: f32 = 1.0;
+const EVENT_LOG_LINE_WIDTH: f32 = 400.0;
+const EVENT_LOG_LINE_HEIGHT: f32 = 20.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventLogType {
+    Kill,
+    Objective,
+    Reinforcement,
+}
+
+pub struct EventLogMessage {
+    pub _message: String,
+    pub _event_log_type: EventLogType,
+    pub _elapsed_time: f32,
+}
+
+pub struct EventLogWidget {
+    pub _widget: *const WidgetDefault,
+    pub _lines: Vec<*const WidgetDefault>,
+    pub _messages: VecDeque<EventLogMessage>,
+}
+
+// Implementation
+fn get_event_log_color(event_log_type: EventLogType) -> u32 {
+    match event_log_type {
+        EventLogType::Kill => get_color32(255, 100, 100, 255),
+        EventLogType::Objective => get_color32(255, 255, 0, 255),
+        EventLogType::Reinforcement => get_color32(100, 200, 255, 255),
+    }
+}
+
+impl EventLogWidget {
+    pub fn create_event_log_widget(root_widget: &mut dyn Widget, pos: &Vector2<f32>) -> EventLogWidget {
+        let event_log_widget = UIManager::create_widget("event_log_widget", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(event_log_widget.as_ref()).get_ui_component_mut();
+        ui_component.set_pos(pos.x, pos.y);
+        ui_component.set_size(EVENT_LOG_LINE_WIDTH, EVENT_LOG_LINE_HEIGHT * EVENT_LOG_MAX_LINES as f32);
+        ui_component.set_layout_type(UILayoutType::BoxLayout);
+        ui_component.set_layout_orientation(Orientation::VERTICAL);
+        ui_component.set_halign(HorizontalAlign::LEFT);
+        ui_component.set_valign(VerticalAlign::TOP);
+        ui_component.set_color(get_color32(0, 0, 0, 0));
+        root_widget.add_widget(&event_log_widget);
+
+        let mut lines: Vec<*const WidgetDefault> = Vec::new();
+        for _ in 0..EVENT_LOG_MAX_LINES {
+            let line_widget = UIManager::create_widget("event_log_line", UIWidgetTypes::Default);
+            let ui_component = ptr_as_mut(line_widget.as_ref()).get_ui_component_mut();
+            ui_component.set_size(EVENT_LOG_LINE_WIDTH, EVENT_LOG_LINE_HEIGHT);
+            ui_component.set_font_size(16.0);
+            ui_component.set_halign(HorizontalAlign::LEFT);
+            ui_component.set_valign(VerticalAlign::CENTER);
+            ui_component.set_color(get_color32(0, 0, 0, 64));
+            ui_component.set_visible(false);
+            ptr_as_mut(event_log_widget.as_ref()).add_widget(&line_widget);
+            lines.push(line_widget.as_ref() as *const dyn Widget as *const WidgetDefault);
+        }
+
+        EventLogWidget {
+            _widget: event_log_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _lines: lines,
+            _messages: VecDeque::new(),
+        }
+    }
+
+    pub fn add_event_log(&mut self, message: &str, event_log_type: EventLogType) {
+        log::info!("[{:?}] {}", event_log_type, message);
+        if EVENT_LOG_MAX_LINES <= self._messages.len() {
+            self._messages.pop_front();
+        }
+        self._messages.push_back(EventLogMessage {
+            _message: message.to_string(),
+            _event_log_type: event_log_type,
+            _elapsed_time: 0.0,
+        });
+    }
+
+    pub fn update_event_log_widget(&mut self, delta_time: f32) {
+        for message in self._messages.iter_mut() {
+            message._elapsed_time += delta_time;
+        }
+
+        while let Some(message) = self._messages.front() {
+            if message._elapsed_time < EVENT_LOG_LIFE_TIME {
+                break;
+            }
+            self._messages.pop_front();
+        }
+
+        for (index, line) in self._lines.iter().enumerate() {
+            let ui_component = ptr_as_mut(*line).get_ui_component_mut();
+            match self._messages.get(index) {
+                Some(message) => {
+                    let remain_time = EVENT_LOG_LIFE_TIME - message._elapsed_time;
+                    ui_component.set_text(&message._message);
+                    ui_component.set_font_color(get_event_log_color(message._event_log_type));
+                    ui_component.set_opacity(1.0f32.min(remain_time / EVENT_LOG_FADE_OUT_TIME));
+                    ui_component.set_visible(true);
+                },
+                None => ui_component.set_visible(false),
+            }
+        }
+    }
+}
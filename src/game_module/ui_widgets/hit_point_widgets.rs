@@ -17,6 +17,27 @@ pub struct ShieldPointWidget {
     pub _shield_point_bar: *const WidgetDefault,
 }
 
+pub struct WeaponGaugeWidget {
+    pub _weapon_gauge_layer: *const WidgetDefault,
+    pub _weapon_gauge_bar: *const WidgetDefault,
+}
+
+pub struct EnergyWidget {
+    pub _energy_layer: *const WidgetDefault,
+    pub _energy_bar: *const WidgetDefault,
+}
+
+pub struct BoostFuelWidget {
+    pub _boost_fuel_layer: *const WidgetDefault,
+    pub _boost_fuel_bar: *const WidgetDefault,
+}
+
+pub struct PowerWidget {
+    pub _power_name: String,
+    pub _power_layer: *const WidgetDefault,
+    pub _power_bar: *const WidgetDefault,
+}
+
 
 // Implementation
 fn create_hit_point_layer_widget(parent_widget: &mut dyn Widget) -> *const WidgetDefault {
@@ -88,3 +109,79 @@ impl ShieldPointWidget {
         shield_point_bar.set_size_hint_x(Some(shield_point_ratio));
     }
 }
+
+impl WeaponGaugeWidget {
+    pub fn create_weapon_gauge_widget(parent_widget: &mut dyn Widget) -> WeaponGaugeWidget {
+        let weapon_gauge_layer = create_hit_point_layer_widget(parent_widget);
+        let weapon_gauge_bar = create_hit_point_bar_widget(ptr_as_mut(weapon_gauge_layer), get_color32(255, 255, 0, 75));
+        WeaponGaugeWidget {
+            _weapon_gauge_layer: weapon_gauge_layer,
+            _weapon_gauge_bar: weapon_gauge_bar
+        }
+    }
+
+    pub fn update_weapon_gauge_widget(&self, gauge_ratio: f32, gauge_text: &str) {
+        let weapon_gauge_ui = ptr_as_mut(self._weapon_gauge_layer).get_ui_component_mut();
+        weapon_gauge_ui.set_text(gauge_text);
+        let weapon_gauge_bar = ptr_as_mut(self._weapon_gauge_bar).get_ui_component_mut();
+        weapon_gauge_bar.set_size_hint_x(Some(0f32.max(1f32.min(gauge_ratio))));
+    }
+}
+
+impl EnergyWidget {
+    pub fn create_energy_widget(parent_widget: &mut dyn Widget) -> EnergyWidget {
+        let energy_layer = create_hit_point_layer_widget(parent_widget);
+        let energy_bar = create_hit_point_bar_widget(ptr_as_mut(energy_layer), get_color32(0, 255, 255, 75));
+        EnergyWidget {
+            _energy_layer: energy_layer,
+            _energy_bar: energy_bar
+        }
+    }
+
+    pub fn update_energy_widget(&self, energy: f32, max_energy: f32) {
+        let energy_ratio = if 0.0 < max_energy { 1.0f32.min(energy / max_energy) } else { 0.0 };
+        let energy_ui = ptr_as_mut(self._energy_layer).get_ui_component_mut();
+        energy_ui.set_text(&format!("Energy: {}", energy as i32));
+        let energy_bar = ptr_as_mut(self._energy_bar).get_ui_component_mut();
+        energy_bar.set_size_hint_x(Some(energy_ratio));
+    }
+}
+
+impl PowerWidget {
+    pub fn create_power_widget(parent_widget: &mut dyn Widget, power_name: &str) -> PowerWidget {
+        let power_layer = create_hit_point_layer_widget(parent_widget);
+        let power_bar = create_hit_point_bar_widget(ptr_as_mut(power_layer), get_color32(0, 255, 100, 75));
+        PowerWidget {
+            _power_name: power_name.to_string(),
+            _power_layer: power_layer,
+            _power_bar: power_bar
+        }
+    }
+
+    pub fn update_power_widget(&self, power: f32) {
+        let power_ui = ptr_as_mut(self._power_layer).get_ui_component_mut();
+        power_ui.set_text(&format!("{}: {}%", self._power_name, (power * 100.0).round() as i32));
+        let power_bar = ptr_as_mut(self._power_bar).get_ui_component_mut();
+        power_bar.set_size_hint_x(Some(0f32.max(1f32.min(power))));
+    }
+}
+
+impl BoostFuelWidget {
+    pub fn create_boost_fuel_widget(parent_widget: &mut dyn Widget) -> BoostFuelWidget {
+        let boost_fuel_layer = create_hit_point_layer_widget(parent_widget);
+        let boost_fuel_bar = create_hit_point_bar_widget(ptr_as_mut(boost_fuel_layer), get_color32(255, 150, 255, 75));
+        BoostFuelWidget {
+            _boost_fuel_layer: boost_fuel_layer,
+            _boost_fuel_bar: boost_fuel_bar
+        }
+    }
+
+    pub fn update_boost_fuel_widget(&self, boost_fuel: f32, boost_fuel_capacity: f32, boosting: bool) {
+        let boost_fuel_ratio = if 0.0 < boost_fuel_capacity { 1.0f32.min(boost_fuel / boost_fuel_capacity) } else { 0.0 };
+        let boost_fuel_ui = ptr_as_mut(self._boost_fuel_layer).get_ui_component_mut();
+        boost_fuel_ui.set_text(&format!("Boost: {}", boost_fuel as i32));
+        boost_fuel_ui.set_border_color(if boosting { get_color32(255, 255, 255, 255) } else { get_color32(0, 0, 0, 255) });
+        let boost_fuel_bar = ptr_as_mut(self._boost_fuel_bar).get_ui_component_mut();
+        boost_fuel_bar.set_size_hint_x(Some(boost_fuel_ratio));
+    }
+}
@@ -5,15 +5,20 @@ use nalgebra::Vector2;
 use rust_engine_3d::renderer::ui::*;
 use rust_engine_3d::resource::resource::ProjectResourcesBase;
 use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
-use crate::game_module::ui_widgets::hit_point_widgets::{ShieldPointWidget, HullPointWidget};
+use crate::game_module::ui_widgets::hit_point_widgets::{ShieldPointWidget, HullPointWidget, WeaponGaugeWidget, EnergyWidget, PowerWidget, BoostFuelWidget};
+use crate::game_module::ship::ship::{ShipInstance, ShipPowerType};
 use crate::resource::project_resource::ProjectResources;
 use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
 use std::rc::Rc;
 
+const CROSSHAIR_HIT_MARKER_TIME: f32 = 0.2;
+const CROSSHAIR_KILL_MARKER_TIME: f32 = 0.6;
+
 
 pub struct TargetHud {
     pub _widget: *const WidgetDefault,
     pub _distance: *const WidgetDefault,
+    pub _offscreen_indicator: *const WidgetDefault,
     pub _hull_point_widget: HullPointWidget,
     pub _shield_point_widget: ShieldPointWidget,
 }
@@ -22,10 +27,17 @@ pub struct PlayerHud {
     pub _widget: *const WidgetDefault,
     pub _hull_point_widget: HullPointWidget,
     pub _shield_point_widget: ShieldPointWidget,
+    pub _energy_widget: EnergyWidget,
+    pub _boost_fuel_widget: BoostFuelWidget,
+    pub _power_widgets: Vec<(ShipPowerType, PowerWidget)>,
+    pub _weapon_gauge_widgets: Vec<WeaponGaugeWidget>,
+    pub _resource_widget: *const WidgetDefault,
 }
 
 pub struct CrossHair {
     pub _widget: *const WidgetDefault,
+    pub _hit_marker: *const WidgetDefault,
+    pub _hit_marker_time: f32,
     pub _pos: Vector2<i32>,
     pub _tracking_mouse: bool,
 }
@@ -36,6 +48,12 @@ pub struct SelectionArea {
     pub _drag_mouse: bool,
 }
 
+// the player and the target huds show the same hull and shield bars
+fn update_ship_hit_point_widgets(hull_point_widget: &HullPointWidget, shield_point_widget: &ShieldPointWidget, ship: &ShipInstance) {
+    hull_point_widget.update_hull_point_widget(ship.get_hull_point() / 2.0, ship.get_max_hull_point());
+    shield_point_widget.update_shield_point_widget(ship.get_shield_point() / 2.0, ship.get_max_shield_point());
+}
+
 // CrossHair
 impl CrossHair {
     pub fn create_crosshair(project_resources: &ProjectResources, root_widget: &mut dyn Widget, window_center: &Vector2<f32>) -> CrossHair {
@@ -47,12 +65,55 @@ impl CrossHair {
         ui_component.set_material_instance(&project_resources.get_material_instance_data("ui/crosshair"));
         root_widget.add_widget(&crosshair_widget);
 
+        let hit_marker_widget = UIManager::create_widget("hit_marker", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(hit_marker_widget.as_ref()).get_ui_component_mut();
+        ui_component.set_size_hint_x(Some(1.0));
+        ui_component.set_size_hint_y(Some(1.0));
+        ui_component.set_text("X");
+        ui_component.set_font_size(30.0);
+        ui_component.set_halign(HorizontalAlign::CENTER);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_color(get_color32(0, 0, 0, 0));
+        ui_component.set_visible(false);
+        ptr_as_mut(crosshair_widget.as_ref()).add_widget(&hit_marker_widget);
+
         CrossHair {
             _widget: crosshair_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _hit_marker: hit_marker_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _hit_marker_time: 0.0,
             _pos: Vector2::zeros(),
             _tracking_mouse: true,
         }
     }
+
+    // a kill confirmation flashes longer with a distinct color
+    pub fn show_hit_marker(&mut self, is_kill: bool) {
+        let ui_component = ptr_as_mut(self._hit_marker).get_ui_component_mut();
+        if is_kill {
+            ui_component.set_font_color(get_color32(255, 0, 0, 255));
+            ui_component.set_font_size(40.0);
+            self._hit_marker_time = CROSSHAIR_KILL_MARKER_TIME;
+        } else {
+            ui_component.set_font_color(get_color32(255, 255, 255, 255));
+            ui_component.set_font_size(30.0);
+            self._hit_marker_time = self._hit_marker_time.max(CROSSHAIR_HIT_MARKER_TIME);
+        }
+        ui_component.set_opacity(1.0);
+        ui_component.set_visible(true);
+    }
+
+    pub fn update_hit_marker(&mut self, delta_time: f32) {
+        if 0.0 < self._hit_marker_time {
+            self._hit_marker_time -= delta_time;
+            let ui_component = ptr_as_mut(self._hit_marker).get_ui_component_mut();
+            if self._hit_marker_time <= 0.0 {
+                self._hit_marker_time = 0.0;
+                ui_component.set_visible(false);
+            } else {
+                ui_component.set_opacity(1.0f32.min(self._hit_marker_time / CROSSHAIR_HIT_MARKER_TIME));
+            }
+        }
+    }
 }
 
 // TargetHud
@@ -93,17 +154,87 @@ impl TargetHud {
         ui_component.set_expandable(true);
         ptr_as_mut(target_widget.as_ref()).add_widget(&target_distance);
 
+        let offscreen_indicator = UIManager::create_widget("target_offscreen_indicator", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(offscreen_indicator.as_ref()).get_ui_component_mut();
+        ui_component.set_text(">");
+        ui_component.set_size(hud_ui_width * 0.6, hud_ui_height);
+        ui_component.set_font_size(20.0);
+        ui_component.set_halign(HorizontalAlign::CENTER);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_color(get_color32(255, 0, 0, 128));
+        ui_component.set_font_color(get_color32(255, 255, 255, 255));
+        ui_component.set_round(5.0);
+        ui_component.set_visible(false);
+        root_widget.add_widget(&offscreen_indicator);
+
         TargetHud {
             _widget: target_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
             _distance: target_distance.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _offscreen_indicator: offscreen_indicator.as_ref() as *const dyn Widget as *const WidgetDefault,
             _hull_point_widget: HullPointWidget::create_hull_point_widget(ptr_as_mut(target_widget.as_ref())),
             _shield_point_widget: ShieldPointWidget::create_shield_point_widget(ptr_as_mut(target_widget.as_ref()))
         }
     }
+
+    pub fn hide_target_hud(&self) {
+        ptr_as_mut(self._widget).get_ui_component_mut().set_visible(false);
+        ptr_as_mut(self._offscreen_indicator).get_ui_component_mut().set_visible(false);
+    }
+
+    pub fn set_locked_target(&self, locked: bool) {
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        if locked {
+            ui_component.set_border_color(get_color32(255, 255, 0, 255));
+            ui_component.set_border(2.0);
+            ui_component.set_opacity(1.0);
+        } else {
+            ui_component.set_border(0.0);
+            ui_component.set_opacity(0.5);
+        }
+        let indicator_color = if locked { get_color32(255, 255, 0, 200) } else { get_color32(255, 0, 0, 128) };
+        ptr_as_mut(self._offscreen_indicator).get_ui_component_mut().set_color(indicator_color);
+    }
+
+    pub fn update_target_hud(&self, screen_pos: &Vector2<f32>, distance: f32, ship: &ShipInstance) {
+        let target_widget = ptr_as_mut(self._widget).get_ui_component_mut();
+        target_widget.set_center(screen_pos.x, screen_pos.y);
+        target_widget.set_visible(true);
+        ptr_as_mut(self._offscreen_indicator).get_ui_component_mut().set_visible(false);
+
+        let target_distance = ptr_as_mut(self._distance).get_ui_component_mut();
+        target_distance.set_text(&format!("{}m", distance as i32));
+
+        update_ship_hit_point_widgets(&self._hull_point_widget, &self._shield_point_widget, ship);
+    }
+
+    // screen_dir is the direction from the screen center toward the off-screen target
+    pub fn update_offscreen_indicator(&self, window_size: &Vector2<i32>, screen_dir: &Vector2<f32>, distance: f32) {
+        ptr_as_mut(self._widget).get_ui_component_mut().set_visible(false);
+
+        const INDICATOR_BORDER: f32 = 30.0;
+        let half_size: Vector2<f32> = Vector2::new(window_size.x as f32 * 0.5 - INDICATOR_BORDER, window_size.y as f32 * 0.5 - INDICATOR_BORDER);
+        let scale_x = if 0.0 != screen_dir.x { half_size.x / screen_dir.x.abs() } else { f32::MAX };
+        let scale_y = if 0.0 != screen_dir.y { half_size.y / screen_dir.y.abs() } else { f32::MAX };
+        let edge_pos: Vector2<f32> = screen_dir * scale_x.min(scale_y) + Vector2::new(window_size.x as f32 * 0.5, window_size.y as f32 * 0.5);
+        let arrow = if screen_dir.x.abs() < screen_dir.y.abs() {
+            if screen_dir.y < 0.0 { "^" } else { "v" }
+        } else {
+            if screen_dir.x < 0.0 { "<" } else { ">" }
+        };
+
+        let indicator = ptr_as_mut(self._offscreen_indicator).get_ui_component_mut();
+        indicator.set_text(&format!("{}{}m", arrow, distance as i32));
+        indicator.set_center(edge_pos.x, edge_pos.y);
+        indicator.set_visible(true);
+    }
 }
 
 // PlayerHud
 impl PlayerHud {
+    pub fn update_hit_point_widgets(&self, player_ship: &ShipInstance) {
+        update_ship_hit_point_widgets(&self._hull_point_widget, &self._shield_point_widget, player_ship);
+    }
+
     pub fn create_player_hud(root_widget: &mut dyn Widget, pos: &Vector2<f32>) -> PlayerHud {
         let hud_layer_width: f32 = 100.0;
         let hud_layer_height: f32 = 100.0;
@@ -122,10 +253,69 @@ impl PlayerHud {
         ui_component.set_color(get_color32(255, 255, 255, 10));
         root_widget.add_widget(&player_widget);
 
+        let resource_widget = UIManager::create_widget("resource_widget", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(resource_widget.as_ref()).get_ui_component_mut();
+        ui_component.set_text("Resources: 0");
+        ui_component.set_size(hud_layer_width, 25.0);
+        ui_component.set_halign(HorizontalAlign::LEFT);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_color(get_color32(255, 200, 0, 20));
+        ui_component.set_font_color(get_color32(255, 255, 255, 255));
+        ui_component.set_margine(2.0);
+        ui_component.set_padding(4.0);
+        ui_component.set_expandable(true);
+        ptr_as_mut(player_widget.as_ref()).add_widget(&resource_widget);
+
         PlayerHud {
             _widget: player_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
             _hull_point_widget: HullPointWidget::create_hull_point_widget(ptr_as_mut(player_widget.as_ref())),
             _shield_point_widget: ShieldPointWidget::create_shield_point_widget(ptr_as_mut(player_widget.as_ref())),
+            _energy_widget: EnergyWidget::create_energy_widget(ptr_as_mut(player_widget.as_ref())),
+            _boost_fuel_widget: BoostFuelWidget::create_boost_fuel_widget(ptr_as_mut(player_widget.as_ref())),
+            _power_widgets: vec![
+                (ShipPowerType::Shields, PowerWidget::create_power_widget(ptr_as_mut(player_widget.as_ref()), "Shields")),
+                (ShipPowerType::Weapons, PowerWidget::create_power_widget(ptr_as_mut(player_widget.as_ref()), "Weapons")),
+                (ShipPowerType::Engines, PowerWidget::create_power_widget(ptr_as_mut(player_widget.as_ref()), "Engines")),
+            ],
+            _weapon_gauge_widgets: Vec::new(),
+            _resource_widget: resource_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+        }
+    }
+
+    pub fn update_power_widgets(&self, ship: &ShipInstance) {
+        self._energy_widget.update_energy_widget(ship.get_energy(), ship.get_max_energy());
+        let power_distribution = ship.get_power_distribution();
+        for (power_type, power_widget) in self._power_widgets.iter() {
+            power_widget.update_power_widget(power_distribution.get_power(*power_type));
+        }
+    }
+
+    pub fn update_resource_widget(&self, player_resources: f32) {
+        let ui_component = ptr_as_mut(self._resource_widget).get_ui_component_mut();
+        ui_component.set_text(&format!("Resources: {}", player_resources as i32));
+    }
+
+    pub fn update_boost_fuel_widget(&self, ship: &ShipInstance) {
+        let controller = ship.get_controller();
+        self._boost_fuel_widget.update_boost_fuel_widget(controller.get_boost_fuel(), controller.get_boost_fuel_capacity(), controller.is_boosting());
+    }
+
+    pub fn update_weapon_gauge_widgets(&mut self, ship: &ShipInstance) {
+        let current_weapons = ship.get_current_weapons();
+        while self._weapon_gauge_widgets.len() < current_weapons.len() {
+            self._weapon_gauge_widgets.push(WeaponGaugeWidget::create_weapon_gauge_widget(ptr_as_mut(self._widget)));
+        }
+
+        for (index, weapon_gauge_widget) in self._weapon_gauge_widgets.iter().enumerate() {
+            let ui_component = ptr_as_mut(weapon_gauge_widget._weapon_gauge_layer).get_ui_component_mut();
+            if index < current_weapons.len() {
+                let weapon = current_weapons[index];
+                let (gauge_ratio, gauge_text) = weapon.get_weapon_supply().get_supply_gauge(weapon.get_weapon_data());
+                weapon_gauge_widget.update_weapon_gauge_widget(gauge_ratio, &gauge_text);
+                ui_component.set_visible(true);
+            } else {
+                ui_component.set_visible(false);
+            }
         }
     }
 }
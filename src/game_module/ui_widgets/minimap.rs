@@ -0,0 +1,304 @@
+use std::os::raw::c_void;
+
+use nalgebra::{Vector2, Vector3};
+
+use rust_engine_3d::application::scene_manager::ProjectSceneManagerBase;
+use rust_engine_3d::renderer::ui::*;
+use rust_engine_3d::resource::resource::ProjectResourcesBase;
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use crate::game_module::game_client::GameClient;
+use crate::game_module::actors::actor::ActorController;
+use crate::game_module::game_constants::{FOG_OF_WAR_MASK_TEXTURE_NAME, MINIMAP_TEXTURE_NAME, MINIMAP_TEXTURE_SIZE};
+use crate::game_module::game_controller::GameViewMode;
+use crate::game_module::height_map_data::HeightMapData;
+
+const MINIMAP_BLIP_SIZE: f32 = 6.0;
+const MINIMAP_OBJECTIVE_SIZE: f32 = 10.0;
+const MINIMAP_FRUSTUM_EDGE_SAMPLES: i32 = 4;
+const MINIMAP_FRUSTUM_DOT_SIZE: f32 = 2.0;
+const MINIMAP_FRUSTUM_DOT_SPACING: f32 = 4.0;
+
+pub struct Minimap {
+    pub _game_client: *const GameClient,
+    pub _minimap_layout: *const WidgetDefault,
+    pub _pos: Vector2<f32>,
+    pub _size: Vector2<f32>,
+    pub _actor_blips: Vec<*const WidgetDefault>,
+    pub _objective_markers: Vec<*const WidgetDefault>,
+    pub _objective_positions: Vec<Vector3<f32>>,
+    pub _frustum_dots: Vec<*const WidgetDefault>,
+    pub _background_widget: *const WidgetDefault,
+    pub _fog_of_war_widget: *const WidgetDefault,
+    pub _background_pixels: Vec<u8>,
+}
+
+// Implementation
+fn create_minimap_marker_widget(parent_widget: &mut dyn Widget, widget_name: &str, size: f32, color: u32) -> *const WidgetDefault {
+    let marker_widget = UIManager::create_widget(widget_name, UIWidgetTypes::Default);
+    let ui_component = ptr_as_mut(marker_widget.as_ref()).get_ui_component_mut();
+    ui_component.set_size(size, size);
+    ui_component.set_color(color);
+    ui_component.set_round(size * 0.5);
+    ui_component.set_visible(false);
+    parent_widget.add_widget(&marker_widget);
+    marker_widget.as_ref() as *const dyn Widget as *const WidgetDefault
+}
+
+fn create_minimap_texture_widget(parent_widget: &mut dyn Widget, widget_name: &str, size: &Vector2<f32>, material_instance_name: &str, game_client: &GameClient) -> *const WidgetDefault {
+    let project_resources = game_client.get_project_resources();
+    let texture_widget = UIManager::create_widget(widget_name, UIWidgetTypes::Default);
+    let ui_component = ptr_as_mut(texture_widget.as_ref()).get_ui_component_mut();
+    ui_component.set_size(size.x, size.y);
+    ui_component.set_color(get_color32(255, 255, 255, 255));
+    ui_component.set_material_instance(&project_resources.get_material_instance_data(material_instance_name));
+    parent_widget.add_widget(&texture_widget);
+    texture_widget.as_ref() as *const dyn Widget as *const WidgetDefault
+}
+
+// rgba8 pixel of the minimap texture
+fn get_height_color(height_map_data: &HeightMapData, texcoord: &Vector2<f32>) -> [u8; 4] {
+    let bounding_box = height_map_data.get_bounding_box();
+    let height = height_map_data.get_height_bilinear_by_texcoord(texcoord, 0);
+    if height <= height_map_data.get_sea_height() {
+        return [30, 60, 140, 200];
+    }
+    let height_ratio = 0.0f32.max(1.0f32.min((height - bounding_box._min.y) / bounding_box._size.y.max(1.0)));
+    let shade = 60.0 + height_ratio * 195.0;
+    [(shade * 0.6) as u8, shade as u8, (shade * 0.4) as u8, 200]
+}
+
+// the own ship, the allies and the visible enemies
+fn get_blip_color(actor: &ActorController, player_actor: Option<&ActorController>) -> u32 {
+    if player_actor.map_or(false, |player_actor| player_actor.get_actor_handle() == actor.get_actor_handle()) {
+        get_color32(255, 255, 255, 255)
+    } else if actor.is_player_actor() {
+        get_color32(0, 255, 0, 255)
+    } else {
+        get_color32(255, 0, 0, 255)
+    }
+}
+
+impl Minimap {
+    pub fn create_minimap(game_client: &GameClient, root_widget: &mut dyn Widget, pos: &Vector2<f32>, size: f32) -> Box<Minimap> {
+        let minimap_layout = UIManager::create_widget("minimap_layout", UIWidgetTypes::Default);
+        let layout_ui_component = ptr_as_mut(minimap_layout.as_ref()).get_ui_component_mut();
+        layout_ui_component.set_pos(pos.x, pos.y);
+        layout_ui_component.set_size(size, size);
+        layout_ui_component.set_color(get_color32(0, 0, 0, 128));
+        layout_ui_component.set_border_color(get_color32(255, 255, 255, 255));
+        layout_ui_component.set_border(2.0);
+        layout_ui_component.set_touchable(true);
+        static TOUCH_DOWN: CallbackTouchEvent = Minimap::touch_down;
+        static TOUCH_MOVE: CallbackTouchEvent = Minimap::touch_move;
+        layout_ui_component.set_callback_touch_down(&TOUCH_DOWN);
+        layout_ui_component.set_callback_touch_move(&TOUCH_MOVE);
+        root_widget.add_widget(&minimap_layout);
+
+        let minimap = Box::new(Minimap {
+            _game_client: game_client,
+            _minimap_layout: minimap_layout.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _pos: pos.clone_owned(),
+            _size: Vector2::new(size, size),
+            _actor_blips: Vec::new(),
+            _objective_markers: Vec::new(),
+            _objective_positions: Vec::new(),
+            _frustum_dots: Vec::new(),
+            _background_widget: std::ptr::null(),
+            _fog_of_war_widget: std::ptr::null(),
+            _background_pixels: vec![0; MINIMAP_TEXTURE_SIZE * MINIMAP_TEXTURE_SIZE * 4],
+        });
+
+        // set user data
+        layout_ui_component.set_user_data(minimap.as_ref() as *const Minimap as *const c_void);
+
+        minimap
+    }
+
+    // the height map is only ready after the scene is opened, the widgets are created once and the texture is uploaded for each scene.
+    pub fn build_minimap_background(&mut self, height_map_data: &HeightMapData) {
+        let game_client = ptr_as_ref(self._game_client);
+        if self._background_widget.is_null() {
+            // the fog overlay covers the background, the blips are added later and stay on top.
+            // the fog of war manager uploads the fog mask texture, the widget only draws it.
+            let minimap_layout = ptr_as_mut(self._minimap_layout);
+            self._background_widget = create_minimap_texture_widget(minimap_layout, "minimap_background", &self._size, MINIMAP_TEXTURE_NAME, game_client);
+            self._fog_of_war_widget = create_minimap_texture_widget(minimap_layout, "minimap_fog", &self._size, FOG_OF_WAR_MASK_TEXTURE_NAME, game_client);
+        }
+
+        for y in 0..MINIMAP_TEXTURE_SIZE {
+            for x in 0..MINIMAP_TEXTURE_SIZE {
+                let texcoord = Vector2::new(
+                    (x as f32 + 0.5) / MINIMAP_TEXTURE_SIZE as f32,
+                    (y as f32 + 0.5) / MINIMAP_TEXTURE_SIZE as f32
+                );
+                let pixel_index = (y * MINIMAP_TEXTURE_SIZE + x) * 4;
+                self._background_pixels[pixel_index..(pixel_index + 4)].copy_from_slice(&get_height_color(height_map_data, &texcoord));
+            }
+        }
+        let texture_max = MINIMAP_TEXTURE_SIZE as i32 - 1;
+        game_client.get_project_scene_manager().upload_texture_pixels(
+            game_client.get_project_application().get_engine_application(),
+            MINIMAP_TEXTURE_NAME,
+            &self._background_pixels,
+            &Vector2::new(0, 0),
+            &Vector2::new(texture_max, texture_max)
+        );
+    }
+
+    pub fn contains_point(&self, pos: &Vector2<f32>) -> bool {
+        self._pos.x <= pos.x && pos.x <= self._pos.x + self._size.x && self._pos.y <= pos.y && pos.y <= self._pos.y + self._size.y
+    }
+
+    pub fn world_to_minimap(&self, height_map_data: &HeightMapData, position: &Vector3<f32>) -> Vector2<f32> {
+        let bounding_box = height_map_data.get_bounding_box();
+        let texcoord_x = 0.0f32.max(1.0f32.min((position.x - bounding_box._min.x) / bounding_box._size.x));
+        let texcoord_y = 0.0f32.max(1.0f32.min((position.z - bounding_box._min.z) / bounding_box._size.z));
+        Vector2::new(texcoord_x * self._size.x, texcoord_y * self._size.y)
+    }
+
+    pub fn minimap_to_world(&self, height_map_data: &HeightMapData, minimap_pos: &Vector2<f32>) -> Vector3<f32> {
+        let bounding_box = height_map_data.get_bounding_box();
+        let texcoord = Vector2::new(
+            0.0f32.max(1.0f32.min(minimap_pos.x / self._size.x)),
+            0.0f32.max(1.0f32.min(minimap_pos.y / self._size.y))
+        );
+        Vector3::new(
+            bounding_box._min.x + texcoord.x * bounding_box._size.x,
+            height_map_data.get_height_bilinear_by_texcoord(&texcoord, 0),
+            bounding_box._min.z + texcoord.y * bounding_box._size.z
+        )
+    }
+
+    pub fn add_objective_marker(&mut self, position: &Vector3<f32>) {
+        if self._objective_markers.len() <= self._objective_positions.len() {
+            let marker = create_minimap_marker_widget(ptr_as_mut(self._minimap_layout), "minimap_objective", MINIMAP_OBJECTIVE_SIZE, get_color32(255, 255, 0, 255));
+            self._objective_markers.push(marker);
+        }
+        self._objective_positions.push(position.clone_owned());
+    }
+
+    pub fn clear_objective_markers(&mut self) {
+        self._objective_positions.clear();
+        for marker in self._objective_markers.iter() {
+            ptr_as_mut(*marker).get_ui_component_mut().set_visible(false);
+        }
+    }
+
+    fn on_touch(&self, touched_pos: &Vector2<f32>, is_drag: bool) {
+        let game_client = ptr_as_ref(self._game_client);
+        let mouse_input_data = &game_client.get_project_application().get_engine_application()._mouse_input_data;
+        let height_map_data = game_client.get_project_scene_manager().get_height_map_data();
+        let game_controller = game_client.get_game_controller_mut();
+        let target_position = self.minimap_to_world(height_map_data, &(touched_pos - &self._pos));
+        if mouse_input_data._btn_r_pressed && false == is_drag {
+            if let Some(player_actor) = game_client.get_actor_manager_mut().get_player_actor_mut() {
+                player_actor.set_command_actor_move(&target_position);
+            }
+        } else if game_controller.is_view_mode(GameViewMode::TopViewMode) {
+            game_controller.move_camera_to(&target_position);
+        }
+    }
+
+    pub fn touch_down(ui_component: &mut UIComponentInstance, touched_pos: &Vector2<f32>, _touched_pos_delta: &Vector2<f32>) -> bool {
+        let minimap = ptr_as_ref(ui_component.get_user_data() as *const Minimap);
+        minimap.on_touch(touched_pos, false);
+        true
+    }
+
+    pub fn touch_move(ui_component: &mut UIComponentInstance, touched_pos: &Vector2<f32>, _touched_pos_delta: &Vector2<f32>) -> bool {
+        let minimap = ptr_as_ref(ui_component.get_user_data() as *const Minimap);
+        minimap.on_touch(touched_pos, true);
+        true
+    }
+
+    pub fn update_minimap(&mut self, game_client: &GameClient) {
+        let project_scene_manager = game_client.get_project_scene_manager();
+        let height_map_data = project_scene_manager.get_height_map_data();
+        let actor_manager = game_client.get_actor_manager();
+        let minimap_layout = ptr_as_mut(self._minimap_layout);
+
+        let fog_of_war_manager = game_client.get_fog_of_war_manager();
+
+        // actor blips, the hidden enemies are not shown
+        let player_actor = actor_manager.get_player_actor();
+        let mut blip_index: usize = 0;
+        for actor in actor_manager._actors.values() {
+            if false == fog_of_war_manager.is_visible_to_player(actor) {
+                continue;
+            }
+            if self._actor_blips.len() <= blip_index {
+                let blip = create_minimap_marker_widget(minimap_layout, "minimap_blip", MINIMAP_BLIP_SIZE, 0);
+                self._actor_blips.push(blip);
+            }
+            let blip_color = get_blip_color(actor, player_actor);
+            let blip_pos = self.world_to_minimap(height_map_data, actor.get_transform().get_position());
+            let ui_component = ptr_as_mut(self._actor_blips[blip_index]).get_ui_component_mut();
+            ui_component.set_color(blip_color);
+            ui_component.set_center(blip_pos.x, blip_pos.y);
+            ui_component.set_visible(true);
+            blip_index += 1;
+        }
+        for blip in self._actor_blips[blip_index..].iter() {
+            ptr_as_mut(*blip).get_ui_component_mut().set_visible(false);
+        }
+
+        // objective markers
+        for (marker, position) in self._objective_markers.iter().zip(self._objective_positions.iter()) {
+            let marker_pos = self.world_to_minimap(height_map_data, position);
+            let ui_component = ptr_as_mut(*marker).get_ui_component_mut();
+            ui_component.set_center(marker_pos.x, marker_pos.y);
+            ui_component.set_visible(true);
+        }
+
+        // camera frustum outline
+        let main_camera = project_scene_manager.get_main_camera();
+        let camera_position = main_camera.get_camera_position();
+        let window_size = &main_camera._window_size;
+        let screen_corners: [Vector2<i32>; 4] = [
+            Vector2::new(0, 0),
+            Vector2::new(window_size.x - 1, 0),
+            Vector2::new(window_size.x - 1, window_size.y - 1),
+            Vector2::new(0, window_size.y - 1),
+        ];
+        let mut outline: Vec<Vector2<f32>> = Vec::new();
+        for i in 0..screen_corners.len() {
+            let corner_start = &screen_corners[i];
+            let corner_end = &screen_corners[(i + 1) % screen_corners.len()];
+            for sample in 0..MINIMAP_FRUSTUM_EDGE_SAMPLES {
+                let ratio = sample as f32 / MINIMAP_FRUSTUM_EDGE_SAMPLES as f32;
+                let screen_pos: Vector2<i32> = Vector2::new(
+                    corner_start.x + ((corner_end.x - corner_start.x) as f32 * ratio) as i32,
+                    corner_start.y + ((corner_end.y - corner_start.y) as f32 * ratio) as i32
+                );
+                let ray_dir = main_camera.convert_screen_to_relative_world(&screen_pos).normalize();
+                let mut ground_position: Vector3<f32> = camera_position + &ray_dir * main_camera._far;
+                project_scene_manager.get_height_map_collision_point(camera_position, &ray_dir, -1.0, &mut ground_position);
+                outline.push(self.world_to_minimap(height_map_data, &ground_position));
+            }
+        }
+
+        // the outline is dotted with the pooled widgets
+        let mut dot_index: usize = 0;
+        for i in 0..outline.len() {
+            let line_start = &outline[i];
+            let line_end = &outline[(i + 1) % outline.len()];
+            let line_length = (line_end - line_start).norm();
+            let dot_count = 1.max((line_length / MINIMAP_FRUSTUM_DOT_SPACING) as usize);
+            for dot in 0..dot_count {
+                if self._frustum_dots.len() <= dot_index {
+                    let frustum_dot = create_minimap_marker_widget(minimap_layout, "minimap_frustum", MINIMAP_FRUSTUM_DOT_SIZE, get_color32(255, 255, 255, 255));
+                    self._frustum_dots.push(frustum_dot);
+                }
+                let dot_pos: Vector2<f32> = line_start.lerp(line_end, dot as f32 / dot_count as f32);
+                let ui_component = ptr_as_mut(self._frustum_dots[dot_index]).get_ui_component_mut();
+                ui_component.set_center(dot_pos.x, dot_pos.y);
+                ui_component.set_visible(true);
+                dot_index += 1;
+            }
+        }
+        for frustum_dot in self._frustum_dots[dot_index..].iter() {
+            ptr_as_mut(*frustum_dot).get_ui_component_mut().set_visible(false);
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod damage_number_widgets;
+pub mod event_log_widget;
 pub mod hit_point_widgets;
 pub mod hud;
+pub mod minimap;
+pub mod production_widget;
+pub mod respawn_widget;
+pub mod scoreboard_widget;
 pub mod target_info_widget;
\ No newline at end of file
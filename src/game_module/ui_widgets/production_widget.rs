@@ -0,0 +1,195 @@
+use std::os::raw::c_void;
+
+use nalgebra::Vector2;
+
+use rust_engine_3d::renderer::ui::*;
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use crate::game_module::buildings::building::BuildingInstance;
+use crate::game_module::game_client::GameClient;
+
+const PRODUCTION_WIDGET_WIDTH: f32 = 220.0;
+const PRODUCTION_LINE_HEIGHT: f32 = 24.0;
+const PRODUCTION_SHIP_BUTTON_COUNT: usize = 4;
+const PRODUCTION_QUEUE_BUTTON_COUNT: usize = 5;
+
+// the button reads the selected factory from the game controller when it is clicked
+pub struct ProductionButton {
+    pub _game_client: *const GameClient,
+    pub _widget: *const WidgetDefault,
+    pub _index: usize,
+    pub _is_queue_button: bool,
+}
+
+pub struct ProductionWidget {
+    pub _widget: *const WidgetDefault,
+    pub _pos: Vector2<f32>,
+    pub _size: Vector2<f32>,
+    pub _is_visible: bool,
+    pub _title_widget: *const WidgetDefault,
+    pub _progress_widget: *const WidgetDefault,
+    pub _ship_buttons: Vec<Box<ProductionButton>>,
+    pub _queue_buttons: Vec<Box<ProductionButton>>,
+}
+
+// Implementation
+fn create_production_line_widget(parent_widget: &mut dyn Widget, widget_name: &str, color: u32) -> *const WidgetDefault {
+    let line_widget = UIManager::create_widget(widget_name, UIWidgetTypes::Default);
+    let ui_component = ptr_as_mut(line_widget.as_ref()).get_ui_component_mut();
+    ui_component.set_size(PRODUCTION_WIDGET_WIDTH, PRODUCTION_LINE_HEIGHT);
+    ui_component.set_font_size(16.0);
+    ui_component.set_halign(HorizontalAlign::LEFT);
+    ui_component.set_valign(VerticalAlign::CENTER);
+    ui_component.set_color(color);
+    ui_component.set_font_color(get_color32(255, 255, 255, 255));
+    ui_component.set_margine(1.0);
+    ui_component.set_padding(4.0);
+    parent_widget.add_widget(&line_widget);
+    line_widget.as_ref() as *const dyn Widget as *const WidgetDefault
+}
+
+impl ProductionButton {
+    pub fn create_production_button(game_client: &GameClient, parent_widget: &mut dyn Widget, index: usize, is_queue_button: bool) -> Box<ProductionButton> {
+        let color = if is_queue_button { get_color32(100, 100, 100, 128) } else { get_color32(50, 100, 200, 128) };
+        let widget = create_production_line_widget(parent_widget, "production_button", color);
+        let ui_component = ptr_as_mut(widget).get_ui_component_mut();
+        ui_component.set_touchable(true);
+        static TOUCH_DOWN: CallbackTouchEvent = ProductionButton::touch_down;
+        ui_component.set_callback_touch_down(&TOUCH_DOWN);
+
+        let production_button = Box::new(ProductionButton {
+            _game_client: game_client,
+            _widget: widget,
+            _index: index,
+            _is_queue_button: is_queue_button,
+        });
+
+        // set user data
+        ui_component.set_user_data(production_button.as_ref() as *const ProductionButton as *const c_void);
+
+        production_button
+    }
+
+    pub fn set_button_text(&self, text: Option<&str>) {
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        match text {
+            Some(text) => {
+                ui_component.set_text(text);
+                ui_component.set_visible(true);
+            },
+            None => ui_component.set_visible(false),
+        }
+    }
+
+    pub fn touch_down(ui_component: &mut UIComponentInstance, _touched_pos: &Vector2<f32>, _touched_pos_delta: &Vector2<f32>) -> bool {
+        let production_button = ptr_as_ref(ui_component.get_user_data() as *const ProductionButton);
+        let game_client = ptr_as_ref(production_button._game_client);
+        let factory_handle = match game_client.get_game_controller().get_selected_factory() {
+            Some(factory_handle) => factory_handle,
+            None => return false,
+        };
+
+        let building_manager = game_client.get_building_manager_mut();
+        if production_button._is_queue_button {
+            building_manager.cancel_production(factory_handle, production_button._index);
+        } else {
+            let ship_data_name = match building_manager.get_building(factory_handle) {
+                Some(building) => building.get_building_data()._production_ship_data_names.get(production_button._index).cloned(),
+                None => None,
+            };
+            if let Some(ship_data_name) = ship_data_name {
+                if false == building_manager.queue_production(factory_handle, &ship_data_name) {
+                    log::info!("queue_production failed: {}", ship_data_name);
+                }
+            }
+        }
+        true
+    }
+}
+
+impl ProductionWidget {
+    pub fn create_production_widget(game_client: &GameClient, root_widget: &mut dyn Widget, pos: &Vector2<f32>) -> ProductionWidget {
+        let production_widget = UIManager::create_widget("production_widget", UIWidgetTypes::Default);
+        let layout_widget = ptr_as_mut(production_widget.as_ref());
+        let ui_component = layout_widget.get_ui_component_mut();
+        let line_count = 2 + PRODUCTION_SHIP_BUTTON_COUNT + PRODUCTION_QUEUE_BUTTON_COUNT;
+        let size = Vector2::new(PRODUCTION_WIDGET_WIDTH, PRODUCTION_LINE_HEIGHT * line_count as f32);
+        ui_component.set_pos(pos.x, pos.y);
+        ui_component.set_size(size.x, size.y);
+        ui_component.set_layout_type(UILayoutType::BoxLayout);
+        ui_component.set_layout_orientation(Orientation::VERTICAL);
+        ui_component.set_halign(HorizontalAlign::LEFT);
+        ui_component.set_valign(VerticalAlign::TOP);
+        ui_component.set_color(get_color32(0, 0, 0, 128));
+        ui_component.set_border_color(get_color32(255, 255, 255, 255));
+        ui_component.set_border(1.0);
+        ui_component.set_visible(false);
+        root_widget.add_widget(&production_widget);
+
+        let title_widget = create_production_line_widget(layout_widget, "production_title", get_color32(0, 0, 0, 0));
+        let ship_buttons: Vec<Box<ProductionButton>> = (0..PRODUCTION_SHIP_BUTTON_COUNT).map(|index| {
+            ProductionButton::create_production_button(game_client, layout_widget, index, false)
+        }).collect();
+        let progress_widget = create_production_line_widget(layout_widget, "production_progress", get_color32(0, 0, 0, 0));
+        let queue_buttons: Vec<Box<ProductionButton>> = (0..PRODUCTION_QUEUE_BUTTON_COUNT).map(|index| {
+            ProductionButton::create_production_button(game_client, layout_widget, index, true)
+        }).collect();
+
+        ProductionWidget {
+            _widget: production_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _pos: pos.clone_owned(),
+            _size: size,
+            _is_visible: false,
+            _title_widget: title_widget,
+            _progress_widget: progress_widget,
+            _ship_buttons: ship_buttons,
+            _queue_buttons: queue_buttons,
+        }
+    }
+
+    // the clicks on the visible widget are not passed to the world
+    pub fn contains_point(&self, pos: &Vector2<f32>) -> bool {
+        self._is_visible && self._pos.x <= pos.x && pos.x <= self._pos.x + self._size.x && self._pos.y <= pos.y && pos.y <= self._pos.y + self._size.y
+    }
+
+    pub fn hide_production_widget(&mut self) {
+        self._is_visible = false;
+        ptr_as_mut(self._widget).get_ui_component_mut().set_visible(false);
+    }
+
+    pub fn update_production_widget(&mut self, game_client: &GameClient, factory: &BuildingInstance) {
+        self._is_visible = true;
+        ptr_as_mut(self._widget).get_ui_component_mut().set_visible(true);
+        ptr_as_mut(self._title_widget).get_ui_component_mut().set_text(&format!("{} (X: deselect)", factory._building_data_name));
+
+        let project_resources = game_client.get_project_resources();
+        let building_data = factory.get_building_data();
+        for (index, ship_button) in self._ship_buttons.iter().enumerate() {
+            match building_data._production_ship_data_names.get(index) {
+                Some(ship_data_name) if project_resources.has_ship_data(ship_data_name) => {
+                    let cost = project_resources.get_ship_data(ship_data_name).borrow()._cost;
+                    ship_button.set_button_text(Some(&format!("+ {} ({})", ship_data_name, cost as i32)));
+                },
+                _ => ship_button.set_button_text(None),
+            }
+        }
+
+        let production_queue = factory.get_production_queue();
+        let progress_text = match production_queue.first() {
+            Some(ship_data_name) => {
+                let build_time = project_resources.get_ship_data(ship_data_name).borrow()._build_time;
+                let progress = if 0.0 < build_time { factory.get_production_time() / build_time } else { 1.0 };
+                format!("Building {}: {}%", ship_data_name, (progress.min(1.0) * 100.0) as i32)
+            },
+            None => "Right-click: rally point".to_string(),
+        };
+        ptr_as_mut(self._progress_widget).get_ui_component_mut().set_text(&progress_text);
+
+        for (index, queue_button) in self._queue_buttons.iter().enumerate() {
+            match production_queue.get(index) {
+                Some(ship_data_name) => queue_button.set_button_text(Some(&format!("x {}", ship_data_name))),
+                None => queue_button.set_button_text(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use nalgebra::Vector2;
+
+use rust_engine_3d::renderer::ui::{UIManager, Widget, UIWidgetTypes, HorizontalAlign, VerticalAlign, WidgetDefault};
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use rust_engine_3d::utilities::system::ptr_as_mut;
+use crate::game_module::game_client::GameClient;
+
+const RESPAWN_WIDGET_WIDTH: f32 = 300.0;
+const RESPAWN_WIDGET_HEIGHT: f32 = 40.0;
+
+// the countdown at the center of the screen while the player ship is destroyed
+pub struct RespawnWidget {
+    pub _widget: *const WidgetDefault,
+}
+
+impl RespawnWidget {
+    pub fn create_respawn_widget(root_widget: &mut dyn Widget, center: &Vector2<f32>) -> RespawnWidget {
+        let respawn_widget = UIManager::create_widget("respawn_widget", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(respawn_widget.as_ref()).get_ui_component_mut();
+        ui_component.set_size(RESPAWN_WIDGET_WIDTH, RESPAWN_WIDGET_HEIGHT);
+        ui_component.set_center(center.x, center.y);
+        ui_component.set_font_size(24.0);
+        ui_component.set_halign(HorizontalAlign::CENTER);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_color(get_color32(0, 0, 0, 128));
+        ui_component.set_font_color(get_color32(255, 255, 255, 255));
+        ui_component.set_visible(false);
+        root_widget.add_widget(&respawn_widget);
+
+        RespawnWidget {
+            _widget: respawn_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+        }
+    }
+
+    pub fn update_respawn_widget(&self, game_client: &GameClient) {
+        let actor_manager = game_client.get_actor_manager();
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        match actor_manager.get_player_respawn_time() {
+            Some(respawn_time) if false == game_client.get_game_controller().is_match_ended() => {
+                let text = match actor_manager.get_player_remaining_lives() {
+                    Some(remaining_lives) => format!("Respawn in {}  (lives: {})", respawn_time.ceil() as i32, remaining_lives),
+                    None => format!("Respawn in {}", respawn_time.ceil() as i32),
+                };
+                ui_component.set_text(&text);
+                ui_component.set_visible(true);
+            },
+            _ => ui_component.set_visible(false),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use nalgebra::Vector2;
+
+use rust_engine_3d::renderer::ui::{UIManager, Widget, UIWidgetTypes, HorizontalAlign, VerticalAlign, UILayoutType, Orientation, WidgetDefault};
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use rust_engine_3d::utilities::system::ptr_as_mut;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_modes::game_mode::GameModeType;
+
+const SCOREBOARD_MAX_LINES: usize = 6;
+const SCOREBOARD_LINE_WIDTH: f32 = 260.0;
+const SCOREBOARD_LINE_HEIGHT: f32 = 20.0;
+
+pub struct ScoreboardWidget {
+    pub _widget: *const WidgetDefault,
+    pub _lines: Vec<*const WidgetDefault>,
+}
+
+// Implementation
+fn format_time(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as i32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn format_score(score: f32, score_limit: f32) -> String {
+    if 0.0 < score_limit { format!("{} / {}", score as i32, score_limit as i32) } else { format!("{}", score as i32) }
+}
+
+impl ScoreboardWidget {
+    pub fn create_scoreboard_widget(root_widget: &mut dyn Widget, pos: &Vector2<f32>) -> ScoreboardWidget {
+        let scoreboard_widget = UIManager::create_widget("scoreboard_widget", UIWidgetTypes::Default);
+        let layout_widget = ptr_as_mut(scoreboard_widget.as_ref());
+        let ui_component = layout_widget.get_ui_component_mut();
+        ui_component.set_pos(pos.x, pos.y);
+        ui_component.set_size(SCOREBOARD_LINE_WIDTH, SCOREBOARD_LINE_HEIGHT * SCOREBOARD_MAX_LINES as f32);
+        ui_component.set_layout_type(UILayoutType::BoxLayout);
+        ui_component.set_layout_orientation(Orientation::VERTICAL);
+        ui_component.set_halign(HorizontalAlign::LEFT);
+        ui_component.set_valign(VerticalAlign::TOP);
+        ui_component.set_color(get_color32(0, 0, 0, 128));
+        ui_component.set_border_color(get_color32(255, 255, 255, 255));
+        ui_component.set_border(1.0);
+        ui_component.set_visible(false);
+        root_widget.add_widget(&scoreboard_widget);
+
+        let mut lines: Vec<*const WidgetDefault> = Vec::new();
+        for _ in 0..SCOREBOARD_MAX_LINES {
+            let line_widget = UIManager::create_widget("scoreboard_line", UIWidgetTypes::Default);
+            let ui_component = ptr_as_mut(line_widget.as_ref()).get_ui_component_mut();
+            ui_component.set_size(SCOREBOARD_LINE_WIDTH, SCOREBOARD_LINE_HEIGHT);
+            ui_component.set_font_size(16.0);
+            ui_component.set_halign(HorizontalAlign::LEFT);
+            ui_component.set_valign(VerticalAlign::CENTER);
+            ui_component.set_color(get_color32(0, 0, 0, 0));
+            ui_component.set_font_color(get_color32(255, 255, 255, 255));
+            ui_component.set_padding(4.0);
+            layout_widget.add_widget(&line_widget);
+            lines.push(line_widget.as_ref() as *const dyn Widget as *const WidgetDefault);
+        }
+
+        ScoreboardWidget {
+            _widget: scoreboard_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _lines: lines,
+        }
+    }
+
+    // the skirmish has no score
+    pub fn update_scoreboard_widget(&self, game_client: &GameClient) {
+        let game_mode_manager = game_client.get_game_mode_manager();
+        let game_mode_data = game_mode_manager.get_game_mode_data();
+        let game_mode_type = game_mode_data._game_mode_type;
+        let is_visible = GameModeType::Skirmish != game_mode_type;
+        ptr_as_mut(self._widget).get_ui_component_mut().set_visible(is_visible);
+        if false == is_visible {
+            return;
+        }
+
+        let mut texts: Vec<(String, u32)> = Vec::new();
+        let title = match game_mode_manager.get_remaining_time() {
+            Some(remaining_time) => format!("{}  {}", game_mode_type.get_display_name(), format_time(remaining_time)),
+            None => format!("{}  {}", game_mode_type.get_display_name(), format_time(game_mode_manager.get_elapsed_time())),
+        };
+        texts.push((title, get_color32(255, 255, 255, 255)));
+
+        match game_mode_type {
+            GameModeType::TeamDeathmatch | GameModeType::KingOfTheHill => {
+                let score_limit = game_mode_data._score_limit;
+                texts.push((format!("Player: {}", format_score(game_mode_manager.get_team_score(true), score_limit)), get_color32(100, 200, 255, 255)));
+                texts.push((format!("Enemy: {}", format_score(game_mode_manager.get_team_score(false), score_limit)), get_color32(255, 100, 100, 255)));
+                for capture_point in game_mode_manager.get_capture_points().iter() {
+                    let progress = (capture_point.get_capture_progress() * 100.0) as i32;
+                    let (state, color) = if capture_point.is_contested() {
+                        ("contested", get_color32(255, 255, 0, 255))
+                    } else {
+                        match capture_point.get_owner_team() {
+                            Some(true) => ("player", get_color32(100, 200, 255, 255)),
+                            Some(false) => ("enemy", get_color32(255, 100, 100, 255)),
+                            None => ("neutral", get_color32(200, 200, 200, 255)),
+                        }
+                    };
+                    texts.push((format!("{}: {} {}%", capture_point.get_zone_name(), state, progress), color));
+                }
+            },
+            GameModeType::Survival => {
+                let wave_text = if 0 < game_mode_data._wave_count {
+                    format!("Wave: {} / {}", game_mode_manager.get_spawned_wave_count(), game_mode_data._wave_count)
+                } else {
+                    format!("Wave: {}", game_mode_manager.get_spawned_wave_count())
+                };
+                texts.push((wave_text, get_color32(255, 100, 100, 255)));
+            },
+            GameModeType::Escort => {
+                let waypoint_count = game_mode_data._convoy_waypoints.len();
+                let waypoint_index = game_mode_manager.get_convoy_waypoint_index().min(waypoint_count);
+                texts.push((format!("Convoy: {} / {}", waypoint_index, waypoint_count), get_color32(100, 200, 255, 255)));
+            },
+            GameModeType::Skirmish => (),
+        }
+
+        for (index, line) in self._lines.iter().enumerate() {
+            let ui_component = ptr_as_mut(*line).get_ui_component_mut();
+            match texts.get(index) {
+                Some((text, color)) => {
+                    ui_component.set_text(text);
+                    ui_component.set_font_color(*color);
+                    ui_component.set_visible(true);
+                },
+                None => ui_component.set_visible(false),
+            }
+        }
+    }
+}
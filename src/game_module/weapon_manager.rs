@@ -1,110 +1,294 @@
-use std::collections::HashMap;
-use std::rc::Rc;
+use nalgebra::Vector3;
 
-use rust_engine_3d::application::audio_manager::AudioLoop;
 use rust_engine_3d::effect::effect_data::EffectCreateInfo;
 use rust_engine_3d::renderer::render_object::RenderObjectCreateInfo;
-use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_mut, ptr_as_ref};
+use crate::game_module::actors::actor::{ActorController, ActorHandle};
+use crate::game_module::buildings::building::BuildingHandle;
 use crate::game_module::game_client::GameClient;
-use crate::game_module::weapons::bullet::Bullet;
+use crate::game_module::game_constants::{
+    CAMERA_SHAKE_BULLET_IMPACT_RADIUS,
+    CAMERA_SHAKE_BULLET_IMPACT_TRAUMA,
+    CAMERA_SHAKE_SHIP_DESTROY_RADIUS,
+    CAMERA_SHAKE_SHIP_DESTROY_TRAUMA
+};
+use crate::game_module::handle::HandleMap;
+use crate::game_module::ui_widgets::damage_number_widgets::DamageNumberType;
+use crate::game_module::weapons::bullet::{Bullet, BulletHandle};
+use crate::game_module::weapons::explosion::{ExplosionData, PendingExplosion};
 use crate::game_module::weapons::weapon::WeaponTrait;
 
 
 pub struct WeaponManager {
     pub _game_client: *const GameClient,
-    pub _id_generator: u64,
-    pub _bullets_array: HashMap<u64, Rc<Bullet>>,
+    pub _bullets: HandleMap<Bullet>,
+    pub _pending_explosions: Vec<PendingExplosion>,
 }
 
 impl WeaponManager {
     pub fn create_weapon_manager() -> Box<WeaponManager> {
         Box::new(WeaponManager {
             _game_client: std::ptr::null(),
-            _id_generator: 0,
-            _bullets_array: HashMap::new(),
+            _bullets: HandleMap::new(),
+            _pending_explosions: Vec::new(),
         })
     }
     pub fn initialize_weapon_manager(&mut self, game_client: &GameClient) {
         self._game_client = game_client;
     }
     pub fn destroy_weapon_manager(&mut self) {
-        self._bullets_array.clear();
+        self._bullets.clear();
+        self._pending_explosions.clear();
     }
     pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
     pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
-    pub fn generate_id(&mut self) -> u64 {
-        let id = self._id_generator;
-        self._id_generator += 1;
-        id
+    pub fn get_bullets(&self) -> &HandleMap<Bullet> { &self._bullets }
+    pub fn get_bullet(&self, bullet_handle: BulletHandle) -> Option<&Bullet> { self._bullets.get(bullet_handle) }
+    pub fn regist_bullets(&mut self, bullet: Box<Bullet>) -> BulletHandle {
+        self._bullets.insert(bullet)
     }
-    pub fn regist_bullets(&mut self, bullet: &Rc<Bullet>) -> u64 {
-        let id = self.generate_id();
-        self._bullets_array.insert(id, bullet.clone());
-        id
+    pub fn unregist_bullets(&mut self, bullet_handle: BulletHandle) {
+        self._bullets.remove(bullet_handle);
     }
-    pub fn unregist_bullets(&mut self, id: u64) {
-        self._bullets_array.remove(&id);
-    }
-    pub fn fire_bullet(&mut self, weapon_ptr: *const dyn WeaponTrait, render_object_create_info: &RenderObjectCreateInfo) {
+    pub fn fire_bullet(&mut self, weapon: &dyn WeaponTrait, owner_actor: &ActorController, render_object_create_info: &RenderObjectCreateInfo) {
         let bullet_render_object = self.get_game_client().get_project_scene_manager_mut().add_static_render_object("bullet", render_object_create_info);
-        self.get_game_client().get_audio_manager_mut().create_audio_instance("assaultrifle1", AudioLoop::ONCE);
-        let weapon = ptr_as_ref(weapon_ptr);
+        self.get_game_client().play_audio("assaultrifle1");
         let bullet = Bullet::create_bullet(
-            weapon.get_owner_actor(),
-            weapon.get_owner_actor().get_velocity(),
+            owner_actor,
+            &weapon.get_weapon_data()._weapon_data_name,
             weapon.get_bullet_data(),
-            &bullet_render_object
+            &render_object_create_info._position,
+            &render_object_create_info._rotation,
+            bullet_render_object
         );
-        self.regist_bullets(&bullet);
+        self.regist_bullets(bullet);
+    }
+
+    // the explosion is applied at the next update, instigator_side None damages every side.
+    pub fn add_explosion(
+        &mut self,
+        explosion_data: &RcRefCell<ExplosionData>,
+        position: &Vector3<f32>,
+        instigator_actor: Option<ActorHandle>,
+        instigator_side: Option<bool>,
+        instigator_name: &str,
+        weapon_name: &str
+    ) {
+        self._pending_explosions.push(PendingExplosion {
+            _explosion_data: explosion_data.clone(),
+            _position: position.clone_owned(),
+            _instigator_actor: instigator_actor,
+            _instigator_side: instigator_side,
+            _instigator_name: instigator_name.to_string(),
+            _weapon_name: weapon_name.to_string(),
+        });
+    }
+
+    pub fn explode(&mut self, explosion: &PendingExplosion) {
+        let game_client = ptr_as_ref(self._game_client);
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let actor_manager = game_client.get_actor_manager_mut();
+        let building_manager = game_client.get_building_manager_mut();
+        let game_ui_manager = game_client.get_game_ui_manager_mut();
+        let explosion_data = explosion._explosion_data.borrow();
+        let position = &explosion._position;
+
+        // effects
+        for effect_data_name in explosion_data._explosion_effects.iter() {
+            let effect_create_info = EffectCreateInfo {
+                _effect_position: position.clone_owned(),
+                _effect_data_name: effect_data_name.clone(),
+                ..Default::default()
+            };
+            project_scene_manager.add_effect(effect_data_name, &effect_create_info);
+        }
+        if false == explosion_data._explosion_sound_bank.is_empty() {
+            game_client.play_audio_bank(&explosion_data._explosion_sound_bank);
+        }
+        game_client.get_game_controller_mut().add_camera_shake_at(CAMERA_SHAKE_SHIP_DESTROY_TRAUMA, position, CAMERA_SHAKE_SHIP_DESTROY_RADIUS.max(explosion_data._radius));
+
+        // the crater of the explosion in the air is stamped on the terrain below it
+        if 0.0 < explosion_data._crater_radius {
+            let crater_position = Vector3::new(position.x, project_scene_manager.get_height_bilinear(position, 0), position.z);
+            project_scene_manager.add_crater(&crater_position, explosion_data._crater_radius, explosion_data._crater_depth);
+        }
+
+        if explosion_data._radius <= 0.0 {
+            return;
+        }
+
+        // the damage numbers are only for the explosions of the player ship, the side is for the friendly fire
+        let is_player_instigator = explosion._instigator_actor.is_some() && explosion._instigator_actor == actor_manager._player_actor;
+
+        // actors
+        let explosion_center: Vector3<f32> = position + Vector3::new(0.0, 0.5, 0.0);
+        let mut destroyed_actors: Vec<ActorHandle> = Vec::new();
+        for actor in actor_manager._actors.values_mut() {
+            if Some(actor.is_player_actor()) == explosion._instigator_side || actor.get_ship().is_destroyed() {
+                continue;
+            }
+
+            let (actor_center, actor_radius) = {
+                let bound_box = actor.get_bound_box();
+                (bound_box._center.clone_owned(), bound_box._radius)
+            };
+            let to_actor: Vector3<f32> = &actor_center - &explosion_center;
+            let distance = to_actor.norm();
+            let falloff = explosion_data.get_falloff(0f32.max(distance - actor_radius) / explosion_data._radius);
+            if falloff <= 0.0 {
+                continue;
+            }
+            let dir: Vector3<f32> = if 0.0 < distance { &to_actor / distance } else { Vector3::y() };
+
+            // line of sight through the height map
+            let mut damage_scale = falloff;
+            if project_scene_manager.is_line_of_sight_blocked(&explosion_center, &actor_center) {
+                damage_scale *= explosion_data._occluded_damage_scale;
+            }
+
+            let (shield_damage, hull_damage) = actor.get_ship_mut().apply_damage(explosion_data._shield_damage * damage_scale, explosion_data._hull_damage * damage_scale);
+            if is_player_instigator {
+                game_ui_manager.show_damage_number(&actor_center, shield_damage, DamageNumberType::Shield);
+                game_ui_manager.show_damage_number(&actor_center, hull_damage, DamageNumberType::Hull);
+            }
+
+            // knockback
+            let velocity: Vector3<f32> = actor.get_velocity() + dir * (explosion_data._impulse * damage_scale / actor.get_ship().get_mass());
+            actor.get_controller_mut().set_velocity(&velocity);
+            actor.get_controller_mut().set_on_ground(false);
+
+            if actor.get_ship().is_destroyed() {
+                destroyed_actors.push(actor.get_actor_handle());
+            }
+        }
+        if is_player_instigator && false == destroyed_actors.is_empty() {
+            game_ui_manager.show_hit_marker(true);
+        }
+        for actor_handle in destroyed_actors.iter() {
+            actor_manager.destroy_actor(*actor_handle, &explosion._instigator_name, &explosion._weapon_name);
+        }
+
+        // buildings
+        let mut destroyed_buildings: Vec<BuildingHandle> = Vec::new();
+        for (building_handle, building) in building_manager._buildings.iter_mut() {
+            if Some(building.is_player_building()) == explosion._instigator_side || building.is_destroyed() {
+                continue;
+            }
+            let closest_point: Vector3<f32> = position.sup(building.get_collision_min()).inf(building.get_collision_max());
+            let falloff = explosion_data.get_falloff((&closest_point - position).norm() / explosion_data._radius);
+            if falloff <= 0.0 {
+                continue;
+            }
+
+            // line of sight through the height map
+            let mut damage_scale = falloff;
+            if project_scene_manager.is_line_of_sight_blocked(&explosion_center, &building.get_center()) {
+                damage_scale *= explosion_data._occluded_damage_scale;
+            }
+
+            building.apply_damage(explosion_data._hull_damage * damage_scale);
+            if building.is_destroyed() {
+                destroyed_buildings.push(building_handle);
+            }
+        }
+        for building_handle in destroyed_buildings.iter() {
+            building_manager.destroy_building(*building_handle, &explosion._instigator_name, &explosion._weapon_name);
+        }
+    }
+
+    pub fn update_explosions(&mut self) {
+        // the destroyed ships may queue the chained explosions
+        while false == self._pending_explosions.is_empty() {
+            let pending_explosions = std::mem::take(&mut self._pending_explosions);
+            for explosion in pending_explosions.iter() {
+                self.explode(explosion);
+            }
+        }
     }
 
     pub fn update_weapon_manager(&mut self, delta_time: f32) {
         let game_client = ptr_as_ref(self._game_client);
         let project_scene_manager = game_client.get_project_scene_manager_mut();
-        let audio_manager = game_client.get_audio_manager_mut();
         let actor_manager = game_client.get_actor_manager_mut();
+        let game_controller = game_client.get_game_controller_mut();
+        let game_ui_manager = game_client.get_game_ui_manager_mut();
+        let building_manager = game_client.get_building_manager_mut();
 
         // update bullet
-        let mut dead_bullets: Vec<(u64, *const Bullet)> = Vec::new();
-        for (id, bullet_ptr) in self._bullets_array.iter() {
-            let bullet = ptr_as_mut(bullet_ptr.as_ref());
-
+        let mut dead_bullets: Vec<BulletHandle> = Vec::new();
+        for (bullet_handle, bullet) in self._bullets.iter_mut() {
             bullet.update_bullet(delta_time, project_scene_manager);
 
+            // the destroyed owner does not scale the damage anymore
+            let damage_scale = match actor_manager.get_actor(bullet.get_owner_actor()) {
+                Some(owner_actor) => owner_actor.get_ship().get_damage_scale(),
+                None => 1.0,
+            };
+
+            // the damage numbers are only for the bullets of the player ship
+            let is_player_bullet = Some(bullet.get_owner_actor()) == actor_manager._player_actor;
+
             // check hit
+            if bullet._is_alive {
+                let bullet_position = bullet.get_transform_object().get_position().clone_owned();
+                let is_player_team = bullet.is_owner_player_actor();
+                let hit_actor = actor_manager._actors.values_mut().find(|actor| {
+                    let actor_bound_box = actor.get_bound_box();
+                    is_player_team != actor.is_player_actor() && (&actor_bound_box._center - &bullet_position).norm_squared() <= (actor_bound_box._radius * actor_bound_box._radius)
+                });
+                if let Some(actor) = hit_actor {
+                    let bullet_data = bullet.get_bullet_data();
+                    let (shield_damage, hull_damage) = actor.get_ship_mut().apply_damage(bullet_data._shield_damage * damage_scale, bullet_data._hull_damage * damage_scale);
+                    let is_destroyed = actor.get_ship().is_destroyed();
+                    let actor_handle = actor.get_actor_handle();
+                    if is_player_bullet {
+                        game_ui_manager.show_damage_number(&bullet_position, shield_damage, DamageNumberType::Shield);
+                        game_ui_manager.show_damage_number(&bullet_position, hull_damage, DamageNumberType::Hull);
+                        game_ui_manager.show_hit_marker(is_destroyed);
+                    }
+
+                    if is_destroyed {
+                        actor_manager.destroy_actor(actor_handle, bullet.get_owner_actor_name(), bullet.get_weapon_data_name());
+                    }
+                    bullet._is_alive = false;
+                    bullet._is_collided = true;
+                }
+            }
+
+            // check building hit
             if bullet._is_alive {
                 let bullet_position = bullet.get_transform_object().get_position();
-                let is_player_actor = bullet.get_owner_actor().is_player_actor();
-                for actor_wrapper in actor_manager._actors.values() {
-                    let actor = ptr_as_mut(actor_wrapper.as_ref());
-                    if is_player_actor != actor.is_player_actor() {
-                        let is_hit = {
-                            let actor_bound_box = actor.get_bound_box();
-                            let to_actor = &actor_bound_box._center - bullet_position;
-                            to_actor.norm_squared() <= (actor_bound_box._radius * actor_bound_box._radius)
-                        };
-
-                        if is_hit {
-                            actor_manager.remove_actor(project_scene_manager, actor);
-                            bullet._is_alive = false;
-                            bullet._is_collided = true;
-                            break;
-                        }
+                // the friendly buildings do not absorb the bullets
+                if let Some((building_handle, building)) = building_manager.find_building_at_mut(bullet_position, false == bullet.is_owner_player_actor()) {
+                    let hull_damage = building.apply_damage(bullet.get_bullet_data()._hull_damage * damage_scale);
+                    let is_destroyed = building.is_destroyed();
+                    if is_player_bullet {
+                        game_ui_manager.show_damage_number(bullet_position, hull_damage, DamageNumberType::Hull);
+                        game_ui_manager.show_hit_marker(is_destroyed);
                     }
+                    if is_destroyed {
+                        building_manager.destroy_building(building_handle, bullet.get_owner_actor_name(), bullet.get_weapon_data_name());
+                    }
+                    bullet._is_alive = false;
+                    bullet._is_collided = true;
                 }
             }
 
             if false == bullet._is_alive {
-                dead_bullets.push((*id, bullet_ptr.as_ref()));
+                dead_bullets.push(bullet_handle);
             }
         }
 
         // destroy bullets
-        for (id, bullet_ptr) in dead_bullets.iter() {
-            let bullet = ptr_as_mut(*bullet_ptr);
+        for bullet_handle in dead_bullets.iter() {
+            let bullet = match self._bullets.get(*bullet_handle) {
+                Some(bullet) => ptr_as_ref(bullet),
+                None => continue,
+            };
             if bullet._is_collided {
                 let bullet_transform = bullet.get_transform_object();
                 let bullet_data = bullet.get_bullet_data();
+                game_controller.add_camera_shake_at(CAMERA_SHAKE_BULLET_IMPACT_TRAUMA, bullet_transform.get_position(), CAMERA_SHAKE_BULLET_IMPACT_RADIUS);
                 let bullet_destroy_effect_count = bullet_data._bullet_destroy_effects.len();
                 if 0 < bullet_destroy_effect_count {
                     let effect_index: usize = if 1 < bullet_destroy_effect_count { rand::random::<usize>() % bullet_destroy_effect_count } else { 0 };
@@ -118,13 +302,44 @@ impl WeaponManager {
                 }
 
                 if false == bullet_data._bullet_destroy_sound_bank.is_empty() {
-                    audio_manager.create_audio_instance_from_bank(&bullet_data._bullet_destroy_sound_bank, AudioLoop::ONCE);
+                    game_client.play_audio_bank(&bullet_data._bullet_destroy_sound_bank);
+                }
+
+                // explosion
+                let project_resources = game_client.get_project_resources();
+                if false == bullet_data._explosion_data_name.is_empty() && project_resources.has_explosion_data(&bullet_data._explosion_data_name) {
+                    self.add_explosion(
+                        project_resources.get_explosion_data(&bullet_data._explosion_data_name),
+                        bullet_transform.get_position(),
+                        Some(bullet.get_owner_actor()),
+                        Some(bullet.is_owner_player_actor()),
+                        bullet.get_owner_actor_name(),
+                        bullet.get_weapon_data_name()
+                    );
+                }
+
+                // crater
+                let bullet_position = bullet_transform.get_position();
+                if 0.0 < bullet_data._crater_radius && bullet_position.y <= project_scene_manager.get_height_bilinear(bullet_position, 0) + bullet_data._crater_radius {
+                    project_scene_manager.add_crater(bullet_position, bullet_data._crater_radius, bullet_data._crater_depth);
+
+                    // the ships on the crater fall to the new ground
+                    for actor in actor_manager._actors.values_mut() {
+                        let to_actor = actor.get_transform().get_position() - bullet_position;
+                        if to_actor.x * to_actor.x + to_actor.z * to_actor.z < bullet_data._crater_radius * bullet_data._crater_radius {
+                            actor.get_controller_mut().set_on_ground(false);
+                        }
+                    }
                 }
             }
-            project_scene_manager.remove_static_render_object(&bullet._bullet_render_object.borrow()._render_object_name);
+            if let Some(bullet_render_object) = &bullet._bullet_render_object {
+                project_scene_manager.remove_static_render_object(&bullet_render_object.borrow()._render_object_name);
+            }
 
-            self.unregist_bullets(*id);
+            self.unregist_bullets(*bullet_handle);
         }
+
+        self.update_explosions();
     }
 
 
@@ -1,5 +1,3 @@
-use std::rc::Rc;
-
 use nalgebra::Vector3;
 use serde::{ Serialize, Deserialize };
 
@@ -7,7 +5,8 @@ use rust_engine_3d::renderer::render_object::RenderObjectData;
 use rust_engine_3d::renderer::transform_object::TransformObjectData;
 use rust_engine_3d::utilities::system::RcRefCell;
 use crate::application::project_scene_manager::ProjectSceneManager;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actors::actor::{ActorController, ActorHandle};
+use crate::game_module::handle::Handle;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Copy)]
 pub enum BulletType {
@@ -37,6 +36,9 @@ pub struct BulletData {
     pub _bullet_life_time: f32,
     pub _bullet_destroy_effects: Vec<String>,
     pub _bullet_destroy_sound_bank: String,
+    pub _crater_radius: f32,
+    pub _crater_depth: f32,
+    pub _explosion_data_name: String,
     pub _model_data_name: String
 }
 
@@ -51,62 +53,84 @@ impl Default for BulletData {
             _bullet_life_time: 10.0,
             _bullet_destroy_effects: Vec::new(),
             _bullet_destroy_sound_bank: "".to_string(),
+            _crater_radius: 0.0,
+            _crater_depth: 0.0,
+            _explosion_data_name: "".to_string(),
             _model_data_name: "".to_string(),
         }
     }
 }
 
+pub type BulletHandle = Handle<Bullet>;
+
 pub struct Bullet {
     pub _bullet_data: *const BulletData,
-    pub _owner_actor: *const ActorController,
+    // the owner may be destroyed before the bullet, the team and the name are kept for the hits
+    pub _owner_actor: ActorHandle,
+    pub _owner_is_player_actor: bool,
+    pub _owner_actor_name: String,
+    pub _weapon_data_name: String,
     pub _is_alive: bool,
     pub _is_collided: bool,
     pub _elapsed_time: f32,
-    pub _transform: *const TransformObjectData,
+    pub _transform_object: TransformObjectData,
     pub _initial_position: Vector3<f32>,
     pub _initial_velocity: Vector3<f32>,
-    pub _bullet_render_object: RcRefCell<RenderObjectData>,
+    // None on the dedicated server
+    pub _bullet_render_object: Option<RcRefCell<RenderObjectData>>,
 }
 
 
 // Implementation
 impl Bullet {
     pub fn create_bullet(
-        owner_actor: *const ActorController,
-        initial_velocity: &Vector3<f32>,
+        owner_actor: &ActorController,
+        weapon_data_name: &str,
         bullet_data: *const BulletData,
-        bullet_render_object: &RcRefCell<RenderObjectData>,
-    ) -> Rc<Bullet> {
-        Rc::new(Bullet {
-            _owner_actor: owner_actor,
-            _transform: &bullet_render_object.borrow()._transform_object,
-            _initial_position: bullet_render_object.borrow()._transform_object.get_position().clone_owned(),
-            _initial_velocity: initial_velocity.clone_owned(),
+        position: &Vector3<f32>,
+        rotation: &Vector3<f32>,
+        bullet_render_object: Option<RcRefCell<RenderObjectData>>,
+    ) -> Box<Bullet> {
+        let mut transform_object = TransformObjectData::new_transform_object_data();
+        transform_object.set_position(position);
+        transform_object.set_rotation(rotation);
+        transform_object.update_transform_object();
+        Box::new(Bullet {
+            _owner_actor: owner_actor.get_actor_handle(),
+            _owner_is_player_actor: owner_actor.is_player_actor(),
+            _owner_actor_name: owner_actor.get_actor_name(),
+            _weapon_data_name: weapon_data_name.to_string(),
+            _transform_object: transform_object,
+            _initial_position: position.clone_owned(),
+            _initial_velocity: owner_actor.get_velocity().clone_owned(),
             _bullet_data: bullet_data,
             _elapsed_time: 0.0,
             _is_alive: true,
             _is_collided: false,
-            _bullet_render_object: bullet_render_object.clone(),
+            _bullet_render_object: bullet_render_object,
         })
     }
-    pub fn get_owner_actor(&self) -> &ActorController {
-        unsafe { &*self._owner_actor }
-    }
-    pub fn get_owner_actor_mut(&self) -> &mut ActorController { unsafe { &mut *(self._owner_actor as *mut ActorController) } }
+    pub fn get_owner_actor(&self) -> ActorHandle { self._owner_actor }
+    pub fn is_owner_player_actor(&self) -> bool { self._owner_is_player_actor }
+    pub fn get_owner_actor_name(&self) -> &str { &self._owner_actor_name }
+    pub fn get_weapon_data_name(&self) -> &str { &self._weapon_data_name }
     pub fn get_bullet_type(&self) -> BulletType { self.get_bullet_data()._bullet_type }
     pub fn get_bullet_data(&self) -> &BulletData { unsafe { &*self._bullet_data } }
-    pub fn get_transform_object(&self) -> &TransformObjectData { unsafe { &*self._transform } }
-    pub fn get_transform_object_mut(&self) -> &mut TransformObjectData { unsafe { &mut *(self._transform as *mut TransformObjectData) } }
+    pub fn get_transform_object(&self) -> &TransformObjectData { &self._transform_object }
+    pub fn get_transform_object_mut(&mut self) -> &mut TransformObjectData { &mut self._transform_object }
     pub fn update_bullet(&mut self, delta_time: f32, project_scene_manager: &ProjectSceneManager) -> bool {
         if self._is_alive {
             let bullet_data = unsafe { &*self._bullet_data };
 
             // move bullet
-            let transform = unsafe { &mut *(self._transform as *mut TransformObjectData) };
+            let transform = &mut self._transform_object;
             let velocity = (&self._initial_velocity + transform.get_front() * bullet_data._bullet_speed) * delta_time;
             transform.move_position(&velocity);
+            if let Some(bullet_render_object) = &self._bullet_render_object {
+                bullet_render_object.borrow_mut()._transform_object.move_position(&velocity);
+            }
 
-            let current_position = transform.get_position();
+            let current_position = self._transform_object.get_position();
             // check bullet range
             if self._is_alive {
                 let move_distance = (current_position - &self._initial_position).norm();
@@ -0,0 +1,70 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+use rust_engine_3d::utilities::system::RcRefCell;
+use crate::game_module::actors::actor::ActorHandle;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ExplosionFalloffType {
+    Constant,
+    Linear,
+    Quadratic,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ExplosionData {
+    pub _radius: f32,
+    pub _shield_damage: f32,
+    pub _hull_damage: f32,
+    pub _damage_falloff: ExplosionFalloffType,
+    pub _impulse: f32,
+    // damage scale for the targets behind the terrain
+    pub _occluded_damage_scale: f32,
+    pub _crater_radius: f32,
+    pub _crater_depth: f32,
+    pub _explosion_effects: Vec<String>,
+    pub _explosion_sound_bank: String,
+}
+
+impl Default for ExplosionData {
+    fn default() -> ExplosionData {
+        ExplosionData {
+            _radius: 10.0,
+            _shield_damage: 20.0,
+            _hull_damage: 20.0,
+            _damage_falloff: ExplosionFalloffType::Linear,
+            _impulse: 20.0,
+            _occluded_damage_scale: 0.25,
+            _crater_radius: 0.0,
+            _crater_depth: 0.0,
+            _explosion_effects: Vec::new(),
+            _explosion_sound_bank: "".to_string(),
+        }
+    }
+}
+
+impl ExplosionData {
+    // distance_ratio is the distance divided by the radius
+    pub fn get_falloff(&self, distance_ratio: f32) -> f32 {
+        if 1.0 <= distance_ratio {
+            return 0.0;
+        }
+        let ratio = 1.0 - 0f32.max(distance_ratio);
+        match self._damage_falloff {
+            ExplosionFalloffType::Constant => 1.0,
+            ExplosionFalloffType::Linear => ratio,
+            ExplosionFalloffType::Quadratic => ratio * ratio,
+        }
+    }
+}
+
+// the explosion waiting for the next weapon manager update, side is the is_player_actor of the instigator.
+pub struct PendingExplosion {
+    pub _explosion_data: RcRefCell<ExplosionData>,
+    pub _position: Vector3<f32>,
+    pub _instigator_actor: Option<ActorHandle>,
+    pub _instigator_side: Option<bool>,
+    pub _instigator_name: String,
+    pub _weapon_name: String,
+}
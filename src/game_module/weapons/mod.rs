@@ -1,2 +1,3 @@
 pub mod bullet;
+pub mod explosion;
 pub mod weapon;
\ No newline at end of file
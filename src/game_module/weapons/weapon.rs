@@ -5,9 +5,11 @@ use rust_engine_3d::renderer::render_object::{RenderObjectData, RenderObjectCrea
 use rust_engine_3d::renderer::transform_object::TransformObjectData;
 use rust_engine_3d::utilities::system::{RcRefCell, newRcRefCell};
 use crate::application::project_scene_manager::ProjectSceneManager;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actors::actor::ActorHandle;
 use crate::game_module::game_client::GameClient;
 use crate::game_module::game_constants::{FIRE_PITCH_MIN, FIRE_PITCH_MAX};
+use crate::game_module::handle::Handle;
+use crate::game_module::ship::ship::ShipPowerType;
 use crate::game_module::weapons::bullet::{BulletType, BulletData};
 
 
@@ -31,6 +33,7 @@ pub const WEAPON_TYPES: [WeaponType; 5] = [
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct WeaponSlotData {
+    pub _weapon_data_name: String,
     pub _position: Vector3<f32>,
     pub _rotation: Vector3<f32>,
     pub _scale: Vector3<f32>,
@@ -39,6 +42,7 @@ pub struct WeaponSlotData {
 impl Default for WeaponSlotData {
     fn default() -> WeaponSlotData {
         WeaponSlotData {
+            _weapon_data_name: "beam_emitter".to_string(),
             _position: Vector3::zeros(),
             _rotation: Vector3::zeros(),
             _scale: Vector3::new(1.0, 1.0, 1.0),
@@ -46,6 +50,50 @@ impl Default for WeaponSlotData {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WeaponHeatData {
+    pub _heat_per_shot: f32,
+    pub _cooldown_rate: f32,
+    pub _overheat_lockout_time: f32,
+}
+
+impl Default for WeaponHeatData {
+    fn default() -> WeaponHeatData {
+        WeaponHeatData {
+            _heat_per_shot: 0.1,
+            _cooldown_rate: 0.3,
+            _overheat_lockout_time: 2.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WeaponAmmoData {
+    pub _magazine_size: i32,
+    pub _reload_time: f32,
+    pub _max_reserve_ammo: i32,
+}
+
+impl Default for WeaponAmmoData {
+    fn default() -> WeaponAmmoData {
+        WeaponAmmoData {
+            _magazine_size: 30,
+            _reload_time: 2.0,
+            _max_reserve_ammo: 300,
+        }
+    }
+}
+
+// the heat is normalized, the weapon is overheated when the heat reaches to 1.0
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum WeaponSupplyType {
+    Unlimited,
+    Heat(WeaponHeatData),
+    Ammo(WeaponAmmoData),
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct WeaponDataCreateInfo {
@@ -55,6 +103,8 @@ pub struct WeaponDataCreateInfo {
     pub _bullet_data_name: String,
     pub _model_data_name: String,
     pub _muzzle_position: Vector3<f32>,
+    pub _recoil: f32,
+    pub _weapon_supply_type: WeaponSupplyType,
 }
 
 impl Default for WeaponDataCreateInfo {
@@ -66,6 +116,8 @@ impl Default for WeaponDataCreateInfo {
             _bullet_data_name: "".to_string(),
             _model_data_name: "".to_string(),
             _muzzle_position: Vector3::zeros(),
+            _recoil: 0.1,
+            _weapon_supply_type: WeaponSupplyType::Unlimited,
         }
     }
 }
@@ -79,28 +131,47 @@ pub struct WeaponData {
     pub _bullet_data: RcRefCell<BulletData>,
     pub _model_data_name: String,
     pub _muzzle_position: Vector3<f32>,
+    pub _recoil: f32,
+    pub _weapon_supply_type: WeaponSupplyType,
+}
+
+pub struct WeaponSupply {
+    pub _fire_cool_time: f32,
+    pub _heat: f32,
+    pub _overheat_time: f32,
+    pub _magazine_ammo: i32,
+    pub _reserve_ammo: i32,
+    pub _reload_time: f32,
 }
 
+pub type WeaponHandle = Handle<dyn WeaponTrait>;
+
 pub trait WeaponTrait {
     fn initialize_weapon(&mut self);
     fn remove_weapon(&mut self, project_scene_manager: &mut ProjectSceneManager);
-    fn get_owner_actor(&self) -> &ActorController;
+    fn get_owner_actor(&self) -> ActorHandle;
     fn get_bullet_type(&self) -> BulletType;
     fn get_bullet_data(&self) -> &BulletData;
     fn get_weapon_type(&self) -> WeaponType;
     fn get_weapon_data(&self) -> &WeaponData;
-    fn get_weapon_render_object(&self) -> &RcRefCell<RenderObjectData>;
+    fn get_weapon_render_object(&self) -> Option<&RcRefCell<RenderObjectData>>;
+    fn get_weapon_supply(&self) -> &WeaponSupply;
+    fn get_weapon_supply_mut(&mut self) -> &mut WeaponSupply;
+    fn can_fire(&self) -> bool;
+    fn reload_weapon(&mut self);
     fn weapon_fire(&mut self, game_client: &GameClient, fire_start: &Vector3<f32>, fire_dir: &Vector3<f32>, target_position: &Vector3<f32>);
     fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32);
 }
 
 pub struct BeamEmitter {
-    pub _owner_actor: *const ActorController,
+    pub _owner_actor: ActorHandle,
     pub _weapon_data: RcRefCell<WeaponData>,
     pub _weapon_slot_transform: TransformObjectData,
     pub _transform_object: TransformObjectData,
     pub _muzzle_position: Vector3<f32>,
-    pub _weapon_render_object: RcRefCell<RenderObjectData>,
+    // None on the dedicated server
+    pub _weapon_render_object: Option<RcRefCell<RenderObjectData>>,
+    pub _weapon_supply: WeaponSupply,
 }
 
 // Implementation
@@ -114,24 +185,141 @@ impl WeaponData {
             _bullet_data: bullet_data.clone(),
             _model_data_name: weapon_data_create_info._model_data_name.clone(),
             _muzzle_position: weapon_data_create_info._muzzle_position.clone_owned(),
+            _recoil: weapon_data_create_info._recoil,
+            _weapon_supply_type: weapon_data_create_info._weapon_supply_type.clone(),
         })
     }
 }
 
+impl WeaponSupply {
+    pub fn create_weapon_supply(weapon_data: &WeaponData) -> WeaponSupply {
+        let (magazine_ammo, reserve_ammo) = match &weapon_data._weapon_supply_type {
+            WeaponSupplyType::Ammo(ammo_data) => (ammo_data._magazine_size, ammo_data._max_reserve_ammo),
+            _ => (0, 0),
+        };
+        WeaponSupply {
+            _fire_cool_time: 0.0,
+            _heat: 0.0,
+            _overheat_time: 0.0,
+            _magazine_ammo: magazine_ammo,
+            _reserve_ammo: reserve_ammo,
+            _reload_time: 0.0,
+        }
+    }
+
+    pub fn is_overheated(&self) -> bool { 0.0 < self._overheat_time }
+    pub fn is_reloading(&self) -> bool { 0.0 < self._reload_time }
+
+    pub fn can_fire(&self, weapon_data: &WeaponData) -> bool {
+        if 0.0 < self._fire_cool_time {
+            return false;
+        }
+        match &weapon_data._weapon_supply_type {
+            WeaponSupplyType::Unlimited => true,
+            WeaponSupplyType::Heat(_) => false == self.is_overheated(),
+            WeaponSupplyType::Ammo(_) => false == self.is_reloading() && 0 < self._magazine_ammo,
+        }
+    }
+
+    pub fn consume_shot(&mut self, weapon_data: &WeaponData, fire_rate_scale: f32) {
+        let rate_of_fire = weapon_data._rate_of_fire * fire_rate_scale;
+        if 0.0 < rate_of_fire {
+            self._fire_cool_time = 1.0 / rate_of_fire;
+        }
+        match &weapon_data._weapon_supply_type {
+            WeaponSupplyType::Unlimited => (),
+            WeaponSupplyType::Heat(heat_data) => {
+                self._heat += heat_data._heat_per_shot;
+                if 1.0 <= self._heat {
+                    self._heat = 1.0;
+                    self._overheat_time = heat_data._overheat_lockout_time;
+                }
+            },
+            WeaponSupplyType::Ammo(_) => {
+                self._magazine_ammo -= 1;
+                if self._magazine_ammo <= 0 {
+                    self.start_reload(weapon_data);
+                }
+            },
+        }
+    }
+
+    pub fn start_reload(&mut self, weapon_data: &WeaponData) {
+        if let WeaponSupplyType::Ammo(ammo_data) = &weapon_data._weapon_supply_type {
+            if false == self.is_reloading() && self._magazine_ammo < ammo_data._magazine_size && 0 < self._reserve_ammo {
+                self._reload_time = ammo_data._reload_time.max(f32::EPSILON);
+            }
+        }
+    }
+
+    pub fn add_reserve_ammo(&mut self, weapon_data: &WeaponData, ammo: i32) {
+        if let WeaponSupplyType::Ammo(ammo_data) = &weapon_data._weapon_supply_type {
+            self._reserve_ammo = ammo_data._max_reserve_ammo.min(self._reserve_ammo + ammo);
+        }
+    }
+
+    // returns the gauge ratio and the text for the hud
+    pub fn get_supply_gauge(&self, weapon_data: &WeaponData) -> (f32, String) {
+        match &weapon_data._weapon_supply_type {
+            WeaponSupplyType::Unlimited => (1.0, String::from("--")),
+            WeaponSupplyType::Heat(_) => {
+                if self.is_overheated() {
+                    (self._heat, String::from("Overheat"))
+                } else {
+                    (self._heat, format!("Heat: {}%", (self._heat * 100.0) as i32))
+                }
+            },
+            WeaponSupplyType::Ammo(ammo_data) => {
+                if self.is_reloading() {
+                    (1.0 - self._reload_time / ammo_data._reload_time.max(f32::EPSILON), String::from("Reloading"))
+                } else {
+                    (self._magazine_ammo as f32 / ammo_data._magazine_size.max(1) as f32, format!("Ammo: {} / {}", self._magazine_ammo, self._reserve_ammo))
+                }
+            },
+        }
+    }
+
+    pub fn update_weapon_supply(&mut self, weapon_data: &WeaponData, delta_time: f32) {
+        self._fire_cool_time = 0f32.max(self._fire_cool_time - delta_time);
+        match &weapon_data._weapon_supply_type {
+            WeaponSupplyType::Unlimited => (),
+            WeaponSupplyType::Heat(heat_data) => {
+                if self.is_overheated() {
+                    self._overheat_time = 0f32.max(self._overheat_time - delta_time);
+                } else {
+                    self._heat = 0f32.max(self._heat - heat_data._cooldown_rate * delta_time);
+                }
+            },
+            WeaponSupplyType::Ammo(ammo_data) => {
+                if self.is_reloading() {
+                    self._reload_time -= delta_time;
+                    if self._reload_time <= 0.0 {
+                        self._reload_time = 0.0;
+                        let reload_ammo = self._reserve_ammo.min(ammo_data._magazine_size - self._magazine_ammo);
+                        self._magazine_ammo += reload_ammo;
+                        self._reserve_ammo -= reload_ammo;
+                    }
+                }
+            },
+        }
+    }
+}
+
 impl BeamEmitter {
     pub fn create_beam_emitter(
-        owner_actor: *const ActorController,
+        owner_actor: ActorHandle,
         weapon_data: &RcRefCell<WeaponData>,
         weapon_slot_transform: &TransformObjectData,
-        weapon_render_object: &RcRefCell<RenderObjectData>,
+        weapon_render_object: Option<RcRefCell<RenderObjectData>>,
     ) -> Box<BeamEmitter> {
         Box::new(BeamEmitter {
             _weapon_data: weapon_data.clone(),
             _owner_actor: owner_actor,
             _weapon_slot_transform: weapon_slot_transform.clone(),
             _transform_object: TransformObjectData::new_transform_object_data(),
-            _weapon_render_object: weapon_render_object.clone(),
+            _weapon_render_object: weapon_render_object,
             _muzzle_position: Vector3::zeros(),
+            _weapon_supply: WeaponSupply::create_weapon_supply(&weapon_data.borrow()),
         })
     }
 }
@@ -140,15 +328,34 @@ impl WeaponTrait for BeamEmitter {
     fn initialize_weapon(&mut self) {
     }
     fn remove_weapon(&mut self, project_scene_manager: &mut ProjectSceneManager) {
-        project_scene_manager.remove_skeletal_render_object(&self._weapon_render_object.borrow()._render_object_name);
+        if let Some(weapon_render_object) = &self._weapon_render_object {
+            project_scene_manager.remove_skeletal_render_object(&weapon_render_object.borrow()._render_object_name);
+        }
     }
-    fn get_owner_actor(&self) -> &ActorController { unsafe { &*self._owner_actor } }
+    fn get_owner_actor(&self) -> ActorHandle { self._owner_actor }
     fn get_bullet_type(&self) -> BulletType { self.get_bullet_data()._bullet_type }
     fn get_bullet_data(&self) -> &BulletData { unsafe { &*self.get_weapon_data()._bullet_data.as_ptr() } }
     fn get_weapon_type(&self) -> WeaponType { self.get_weapon_data()._weapon_type }
     fn get_weapon_data(&self) -> &WeaponData { unsafe { &*self._weapon_data.as_ptr() } }
-    fn get_weapon_render_object(&self) -> &RcRefCell<RenderObjectData> { &self._weapon_render_object }
+    fn get_weapon_render_object(&self) -> Option<&RcRefCell<RenderObjectData>> { self._weapon_render_object.as_ref() }
+    fn get_weapon_supply(&self) -> &WeaponSupply { &self._weapon_supply }
+    fn get_weapon_supply_mut(&mut self) -> &mut WeaponSupply { &mut self._weapon_supply }
+    fn can_fire(&self) -> bool { self._weapon_supply.can_fire(self.get_weapon_data()) }
+    fn reload_weapon(&mut self) {
+        let weapon_data = unsafe { &*self._weapon_data.as_ptr() };
+        self._weapon_supply.start_reload(weapon_data);
+    }
     fn weapon_fire(&mut self, game_client: &GameClient, fire_start: &Vector3<f32>, fire_dir: &Vector3<f32>, target_position: &Vector3<f32>) {
+        if false == self.can_fire() {
+            return;
+        }
+
+        // the owner may be removed while the weapon is still referenced
+        let owner_actor = match game_client.get_actor_manager().get_actor(self._owner_actor) {
+            Some(owner_actor) => owner_actor,
+            None => return,
+        };
+
         let d: f32 = fire_dir.dot(&(&self._muzzle_position - fire_start));
         let new_target_position: Vector3<f32> = &self._muzzle_position + (target_position - fire_start) - fire_dir * d;
         let to_target: Vector3<f32> = (new_target_position - &self._muzzle_position).normalize();
@@ -162,9 +369,20 @@ impl WeaponTrait for BeamEmitter {
             ..Default::default()
         };
 
-        game_client.get_weapon_manager_mut().fire_bullet(self, &render_object_create_info);
+        game_client.get_weapon_manager_mut().fire_bullet(self, owner_actor, &render_object_create_info);
+        let weapon_data = unsafe { &*self._weapon_data.as_ptr() };
+        let fire_rate_scale = owner_actor.get_ship().get_power_scale(ShipPowerType::Weapons);
+        self._weapon_supply.consume_shot(weapon_data, fire_rate_scale);
+
+        // recoil
+        if Some(owner_actor.get_actor_handle()) == game_client.get_actor_manager()._player_actor {
+            game_client.get_game_controller_mut().add_camera_shake(self.get_weapon_data()._recoil);
+        }
     }
-    fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, _delta_time: f32) {
+    fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32) {
+        let weapon_data = unsafe { &*self._weapon_data.as_ptr() };
+        self._weapon_supply.update_weapon_supply(weapon_data, delta_time);
+
         let weapon_world_matrix = &ship_transform_object._matrix * &self._weapon_slot_transform._matrix;
         self._transform_object.set_position_rotation_scale(&weapon_world_matrix);
         if self._transform_object.update_transform_object() {
@@ -175,6 +393,8 @@ impl WeaponTrait for BeamEmitter {
                 self._transform_object.get_front() * muzzle_position.z +
                 self._transform_object.get_position();
         }
-        self._weapon_render_object.borrow_mut()._transform_object.set_position_rotation_scale(&weapon_world_matrix);
+        if let Some(weapon_render_object) = &self._weapon_render_object {
+            weapon_render_object.borrow_mut()._transform_object.set_position_rotation_scale(&weapon_world_matrix);
+        }
     }
 }
\ No newline at end of file
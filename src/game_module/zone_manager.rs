@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+
+use nalgebra::{Vector2, Vector3};
+
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use rust_engine_3d::utilities::system::ptr_as_ref;
+use crate::game_module::actors::actor::ActorHandle;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::ZONE_WIREFRAME_SEGMENTS;
+use crate::game_module::level_datas::spawn_point::SpawnPointType;
+use crate::game_module::level_datas::zone_data::{ZoneData, ZoneShape};
+use crate::game_module::scripts::script_event::{ScriptEvent, HOOK_ON_STAY_ZONE};
+use crate::game_module::zones::zone::{ZoneEvent, ZoneEventType, ZoneInstance};
+
+pub struct ZoneManager {
+    pub _game_client: *const GameClient,
+    pub _zones: Vec<ZoneInstance>,
+    pub _zone_events: Vec<ZoneEvent>,
+}
+
+// Implementation
+fn add_circle_lines(lines: &mut Vec<(Vector3<f32>, Vector3<f32>)>, center: &Vector3<f32>, axis_a: &Vector3<f32>, axis_b: &Vector3<f32>, radius: f32) {
+    let points: Vec<Vector3<f32>> = (0..ZONE_WIREFRAME_SEGMENTS).map(|index| {
+        let angle = std::f32::consts::PI * 2.0 * index as f32 / ZONE_WIREFRAME_SEGMENTS as f32;
+        center + axis_a * (angle.cos() * radius) + axis_b * (angle.sin() * radius)
+    }).collect();
+    for index in 0..points.len() {
+        lines.push((points[index], points[(index + 1) % points.len()]));
+    }
+}
+
+fn make_zone_wireframe_lines(zone_data: &ZoneData) -> Vec<(Vector3<f32>, Vector3<f32>)> {
+    let mut lines: Vec<(Vector3<f32>, Vector3<f32>)> = Vec::new();
+    let position = &zone_data._position;
+    match &zone_data._shape {
+        ZoneShape::Box { _size } => {
+            let half_size: Vector3<f32> = _size * 0.5;
+            let corner = |x: f32, y: f32, z: f32| position + Vector3::new(half_size.x * x, half_size.y * y, half_size.z * z);
+            // the 4 edges along each axis
+            for a in [-1.0, 1.0] {
+                for b in [-1.0, 1.0] {
+                    lines.push((corner(-1.0, a, b), corner(1.0, a, b)));
+                    lines.push((corner(a, -1.0, b), corner(a, 1.0, b)));
+                    lines.push((corner(a, b, -1.0), corner(a, b, 1.0)));
+                }
+            }
+        },
+        ZoneShape::Sphere { _radius } => {
+            add_circle_lines(&mut lines, position, &Vector3::x(), &Vector3::z(), *_radius);
+            add_circle_lines(&mut lines, position, &Vector3::x(), &Vector3::y(), *_radius);
+            add_circle_lines(&mut lines, position, &Vector3::y(), &Vector3::z(), *_radius);
+        },
+        ZoneShape::Cylinder { _radius, _height } => {
+            let top: Vector3<f32> = position + Vector3::new(0.0, *_height, 0.0);
+            add_circle_lines(&mut lines, position, &Vector3::x(), &Vector3::z(), *_radius);
+            add_circle_lines(&mut lines, &top, &Vector3::x(), &Vector3::z(), *_radius);
+            for offset in [Vector3::x(), -Vector3::x(), Vector3::z(), -Vector3::z()] {
+                lines.push((position + offset * *_radius, &top + offset * *_radius));
+            }
+        },
+    }
+    lines
+}
+
+impl ZoneManager {
+    pub fn create_zone_manager() -> Box<ZoneManager> {
+        Box::new(ZoneManager {
+            _game_client: std::ptr::null(),
+            _zones: Vec::new(),
+            _zone_events: Vec::new(),
+        })
+    }
+
+    pub fn initialize_zone_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+    pub fn destroy_zone_manager(&mut self) {
+        self._zones.clear();
+        self._zone_events.clear();
+    }
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_zones(&self) -> &[ZoneInstance] { &self._zones }
+    pub fn get_zone(&self, zone_name: &str) -> Option<&ZoneInstance> {
+        self._zones.iter().find(|zone| zone_name == zone.get_zone_name())
+    }
+    // the events of the last update
+    pub fn get_zone_events(&self) -> &[ZoneEvent] { &self._zone_events }
+
+    pub fn spawn_zones(&mut self) {
+        self.destroy_zone_manager();
+        let game_client = ptr_as_ref(self._game_client);
+        let level_data = game_client.get_project_scene_manager().get_level_data();
+        for zone_data in level_data._zone_datas.iter() {
+            self._zones.push(ZoneInstance::create_zone_instance(zone_data));
+        }
+        game_client.get_game_ui_manager_mut().update_objective_markers();
+    }
+
+    // the zone with the same name is replaced
+    pub fn add_zone(&mut self, zone_data: &ZoneData) {
+        self.remove_zone(&zone_data._zone_name);
+        self._zones.push(ZoneInstance::create_zone_instance(zone_data));
+        if zone_data.is_objective() {
+            self.get_game_client().get_game_ui_manager_mut().update_objective_markers();
+        }
+    }
+
+    pub fn remove_zone(&mut self, zone_name: &str) -> bool {
+        let zone_count = self._zones.len();
+        self._zones.retain(|zone| zone_name != zone.get_zone_name());
+        zone_count != self._zones.len()
+    }
+
+    fn update_zone_events(&mut self, game_client: &GameClient) {
+        self._zone_events.clear();
+        let actors = &game_client.get_actor_manager()._actors;
+        for zone in self._zones.iter_mut() {
+            let zone_data = &zone._zone_data;
+            let actors_inside: HashSet<ActorHandle> = actors.values().filter(|actor| {
+                zone_data.is_accepted(actor.is_player_actor(), actor.get_ship().get_ship_data()._ship_type) &&
+                    zone_data.contains_point(actor.get_transform().get_position())
+            }).map(|actor| actor.get_actor_handle()).collect();
+
+            for actor_handle in actors_inside.iter() {
+                let event_type = if zone._actors_inside.contains(actor_handle) { ZoneEventType::Stay } else { ZoneEventType::Enter };
+                self._zone_events.push(ZoneEvent { _zone_name: zone_data._zone_name.clone(), _actor_handle: *actor_handle, _event_type: event_type });
+            }
+            // the removed actors do not exit
+            for actor_handle in zone._actors_inside.iter() {
+                if false == actors_inside.contains(actor_handle) && actors.contains(*actor_handle) {
+                    self._zone_events.push(ZoneEvent { _zone_name: zone_data._zone_name.clone(), _actor_handle: *actor_handle, _event_type: ZoneEventType::Exit });
+                }
+            }
+            zone._actors_inside = actors_inside;
+        }
+    }
+
+    fn update_hazards(&self, game_client: &GameClient, delta_time: f32) {
+        let actor_manager = game_client.get_actor_manager_mut();
+        let mut destroyed_actors: Vec<(ActorHandle, &str)> = Vec::new();
+        for zone in self._zones.iter() {
+            let zone_data = zone.get_zone_data();
+            if false == zone_data.is_hazard() {
+                continue;
+            }
+            for actor_handle in zone.get_actors_inside().iter() {
+                let actor = match actor_manager.get_actor_mut(*actor_handle) {
+                    Some(actor) => actor,
+                    None => continue,
+                };
+                // the actor in the overlapped hazards is destroyed once
+                if actor.get_ship().is_destroyed() {
+                    continue;
+                }
+                actor.get_ship_mut().apply_damage(zone_data._shield_damage_per_second * delta_time, zone_data._hull_damage_per_second * delta_time);
+                if actor.get_ship().is_destroyed() {
+                    destroyed_actors.push((*actor_handle, zone.get_zone_name()));
+                }
+            }
+        }
+        for (actor_handle, zone_name) in destroyed_actors.iter() {
+            actor_manager.destroy_actor(*actor_handle, zone_name, "hazard");
+        }
+    }
+
+    fn update_objectives_and_waves(&mut self, game_client: &GameClient) {
+        let actor_manager = game_client.get_actor_manager_mut();
+        let game_ui_manager = game_client.get_game_ui_manager_mut();
+        let mut is_objective_changed = false;
+        for zone_event in self._zone_events.iter() {
+            if ZoneEventType::Enter != zone_event._event_type {
+                continue;
+            }
+            let zone = match self._zones.iter_mut().find(|zone| zone_event._zone_name == zone.get_zone_name()) {
+                Some(zone) => zone,
+                None => continue,
+            };
+
+            let is_player_actor = Some(zone_event._actor_handle) == actor_manager._player_actor;
+            if is_player_actor && zone.is_active_objective() {
+                zone._is_objective_completed = true;
+                game_ui_manager.add_objective_event_log(&zone._zone_data._objective_message);
+                is_objective_changed = true;
+            }
+
+            if false == zone._is_wave_spawned && false == zone._zone_data._wave_spawn_point_datas.is_empty() {
+                zone._is_wave_spawned = true;
+                for spawn_point_type in zone._zone_data._wave_spawn_point_datas.iter() {
+                    match spawn_point_type {
+                        SpawnPointType::Player(spawn_point_data) => { actor_manager.create_actor(game_client, spawn_point_data, true); },
+                        SpawnPointType::NonPlayer(spawn_point_data) => { actor_manager.create_actor(game_client, spawn_point_data, false); },
+                        _ => (),
+                    }
+                }
+                game_ui_manager.add_reinforcement_event_log(&format!("{}: the wave is coming", zone.get_zone_name()));
+            }
+        }
+        if is_objective_changed {
+            game_ui_manager.update_objective_markers();
+        }
+    }
+
+    // the stay events are sent only to the scripts with the hook, they come on every update
+    fn send_script_events(&self, game_client: &GameClient) {
+        let script_manager = game_client.get_script_manager_mut();
+        let has_stay_hook = script_manager.has_hook(HOOK_ON_STAY_ZONE);
+        for zone_event in self._zone_events.iter() {
+            let zone_name = zone_event._zone_name.clone();
+            let actor_id = zone_event._actor_handle.to_id();
+            match zone_event._event_type {
+                ZoneEventType::Enter => script_manager.add_script_event(ScriptEvent::EnterZone { _zone_name: zone_name, _actor_id: actor_id }),
+                ZoneEventType::Exit => script_manager.add_script_event(ScriptEvent::ExitZone { _zone_name: zone_name, _actor_id: actor_id }),
+                ZoneEventType::Stay if has_stay_hook => script_manager.add_script_event(ScriptEvent::StayZone { _zone_name: zone_name, _actor_id: actor_id }),
+                ZoneEventType::Stay => (),
+            }
+        }
+    }
+
+    pub fn update_zone_manager(&mut self, delta_time: f32) {
+        // the server runs the zones for the clients
+        let game_client = ptr_as_ref(self._game_client);
+        if game_client.get_network_manager().is_client() {
+            return;
+        }
+        self.update_zone_events(game_client);
+        self.update_hazards(game_client, delta_time);
+        self.update_objectives_and_waves(game_client);
+        self.send_script_events(game_client);
+    }
+
+    // the wireframes of the zones in the editor mode
+    pub fn render_zone_wireframes(&self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let main_camera = game_client.get_project_scene_manager().get_main_camera();
+        let camera_position = main_camera.get_camera_position();
+        let camera_front = -main_camera.get_camera_front() as Vector3<f32>;
+        let debug_line_manager = game_client.get_project_application().get_engine_application().get_debug_line_manager_mut();
+        for zone in self._zones.iter() {
+            let zone_data = zone.get_zone_data();
+            let color = if zone_data.is_hazard() {
+                get_color32(255, 64, 64, 255)
+            } else if zone_data.is_objective() {
+                get_color32(255, 255, 0, 255)
+            } else {
+                get_color32(0, 255, 255, 255)
+            };
+            for (start, end) in make_zone_wireframe_lines(zone_data).iter() {
+                // the points behind the camera can not be projected
+                if (start - camera_position).dot(&camera_front) <= 0.0 || (end - camera_position).dot(&camera_front) <= 0.0 {
+                    continue;
+                }
+                let screen_start: Vector2<f32> = main_camera.convert_world_to_screen(start, false);
+                let screen_end: Vector2<f32> = main_camera.convert_world_to_screen(end, false);
+                debug_line_manager.add_debug_line_2d(&screen_start, &screen_end, color);
+            }
+        }
+    }
+}
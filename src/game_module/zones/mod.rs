@@ -0,0 +1 @@
+pub mod zone;
\ No newline at end of file
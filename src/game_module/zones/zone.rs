@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+
+use crate::game_module::actors::actor::ActorHandle;
+use crate::game_module::level_datas::zone_data::ZoneData;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ZoneEventType {
+    Enter,
+    Exit,
+    Stay,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZoneEvent {
+    pub _zone_name: String,
+    pub _actor_handle: ActorHandle,
+    pub _event_type: ZoneEventType,
+}
+
+pub struct ZoneInstance {
+    pub _zone_data: ZoneData,
+    pub _actors_inside: HashSet<ActorHandle>,
+    pub _is_wave_spawned: bool,
+    pub _is_objective_completed: bool,
+}
+
+impl ZoneInstance {
+    pub fn create_zone_instance(zone_data: &ZoneData) -> ZoneInstance {
+        ZoneInstance {
+            _zone_data: zone_data.clone(),
+            _actors_inside: HashSet::new(),
+            _is_wave_spawned: false,
+            _is_objective_completed: false,
+        }
+    }
+
+    pub fn get_zone_name(&self) -> &str { &self._zone_data._zone_name }
+    pub fn get_zone_data(&self) -> &ZoneData { &self._zone_data }
+    pub fn get_actors_inside(&self) -> &HashSet<ActorHandle> { &self._actors_inside }
+    pub fn is_actor_inside(&self, actor_handle: ActorHandle) -> bool { self._actors_inside.contains(&actor_handle) }
+    pub fn is_active_objective(&self) -> bool {
+        self._zone_data.is_objective() && false == self._is_objective_completed
+    }
+}
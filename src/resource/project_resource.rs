@@ -15,27 +15,45 @@ use rust_engine_3d::vulkan_context::texture::TextureData;
 use rust_engine_3d::renderer::material::MaterialData;
 use rust_engine_3d::renderer::material_instance::MaterialInstanceData;
 use crate::application::project_scene_manager::SceneDataCreateInfo;
+use crate::game_module::buildings::building::BuildingData;
+use crate::game_module::game_modes::game_mode::GameModeData;
+use crate::game_module::game_options::GameOptions;
+use crate::game_module::pickups::drop_table::DropTableData;
+use crate::game_module::pickups::pickup::PickupData;
 use crate::game_module::ship::ship::{ShipDataCreateInfo, ShipData};
 use crate::game_module::ship::ship_controller::ShipControllerData;
 use crate::game_module::weapons::bullet::BulletData;
+use crate::game_module::weapons::explosion::ExplosionData;
 use crate::game_module::weapons::weapon::{WeaponDataCreateInfo, WeaponData};
 use crate::render_pass::render_pass;
 
 pub const SCENE_FILE_PATH: &str = "scenes";
+pub const SCRIPT_FILE_PATH: &str = "scripts";
 pub const BUILDING_DATA_FILE_PATH: &str = "game_datas/buildings";
 pub const BULLET_DATA_FILE_PATH: &str = "game_datas/bullets";
+pub const DROP_TABLE_DATA_FILE_PATH: &str = "game_datas/drop_tables";
+pub const EXPLOSION_DATA_FILE_PATH: &str = "game_datas/explosions";
+pub const GAME_MODE_DATA_FILE_PATH: &str = "game_datas/game_modes";
+pub const GAME_OPTIONS_FILE_PATH: &str = "game_datas/game_options";
+pub const PICKUP_DATA_FILE_PATH: &str = "game_datas/pickups";
 pub const SHIP_CONTROLLER_DATA_FILE_PATH: &str = "game_datas/ship_controllers";
 pub const SHIP_DATA_FILE_PATH: &str = "game_datas/ships";
 pub const WEAPON_DATA_FILE_PATH: &str = "game_datas/weapons";
 
 pub const EXT_SCENE: &str = "scene";
 pub const EXT_GAME_DATA: &str = "data";
+pub const EXT_SCRIPT: &str = "rhai";
 
 pub const DEFAULT_GAME_DATA_NAME: &str = "default";
 
 pub type SceneDataCreateInfoMap = ResourceDataMap<SceneDataCreateInfo>;
-pub type BuildingDataMap = ResourceDataMap<bool>;
+pub type ScriptDataMap = ResourceDataMap<String>;
+pub type BuildingDataMap = ResourceDataMap<BuildingData>;
 pub type BulletDataMap = ResourceDataMap<BulletData>;
+pub type DropTableDataMap = ResourceDataMap<DropTableData>;
+pub type ExplosionDataMap = ResourceDataMap<ExplosionData>;
+pub type GameModeDataMap = ResourceDataMap<GameModeData>;
+pub type PickupDataMap = ResourceDataMap<PickupData>;
 pub type ShipDataMap = ResourceDataMap<ShipData>;
 pub type ShipControllerDataMap = ResourceDataMap<ShipControllerData>;
 pub type WeaponDataMap = ResourceDataMap<WeaponData>;
@@ -44,11 +62,17 @@ pub type WeaponDataMap = ResourceDataMap<WeaponData>;
 pub struct ProjectResources {
     _engine_resources: *const EngineResources,
     _scene_data_create_infos_map: SceneDataCreateInfoMap,
+    _script_data_map: ScriptDataMap,
     _building_data_map: BuildingDataMap,
     _bullet_data_map: BulletDataMap,
+    _drop_table_data_map: DropTableDataMap,
+    _explosion_data_map: ExplosionDataMap,
+    _game_mode_data_map: GameModeDataMap,
+    _pickup_data_map: PickupDataMap,
     _ship_data_map: ShipDataMap,
     _ship_controller_data_map: ShipControllerDataMap,
     _weapon_data_map: WeaponDataMap,
+    _game_options: GameOptions,
 }
 
 impl ProjectResourcesBase for ProjectResources {
@@ -56,14 +80,12 @@ impl ProjectResourcesBase for ProjectResources {
         self._engine_resources = engine_resources;
     }
 
-    fn load_project_resources(&mut self, renderer_context: &RendererContext) {
-        self.load_scene_datas(renderer_context);
-        self.load_game_datas();
+    fn load_project_resources(&mut self, _renderer_context: &RendererContext) {
+        self.load_game_resources();
     }
 
-    fn destroy_project_resources(&mut self, renderer_context: &RendererContext) {
-        self.unload_game_datas();
-        self.unload_scene_datas(renderer_context);
+    fn destroy_project_resources(&mut self, _renderer_context: &RendererContext) {
+        self.unload_game_resources();
     }
     fn load_graphics_datas(&mut self, _renderer_context: &RendererContext) {
     }
@@ -137,11 +159,17 @@ impl ProjectResources {
         Box::new(ProjectResources {
             _engine_resources: std::ptr::null(),
             _scene_data_create_infos_map: SceneDataCreateInfoMap::new(),
+            _script_data_map: Default::default(),
             _building_data_map: Default::default(),
             _bullet_data_map: Default::default(),
+            _drop_table_data_map: Default::default(),
+            _explosion_data_map: Default::default(),
+            _game_mode_data_map: Default::default(),
+            _pickup_data_map: Default::default(),
             _ship_data_map: Default::default(),
             _ship_controller_data_map: Default::default(),
-            _weapon_data_map: Default::default()
+            _weapon_data_map: Default::default(),
+            _game_options: GameOptions::default(),
         })
     }
     pub fn get_engine_resources(&self) -> &EngineResources {
@@ -150,12 +178,33 @@ impl ProjectResources {
     pub fn get_engine_resources_mut(&self) -> &mut EngineResources {
         unsafe { &mut *(self._engine_resources as *mut EngineResources) }
     }
+    // the dedicated server has no engine resources, the files are collected from the resource directory.
     pub fn collect_resources(&self, dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
-        self.get_engine_resources().collect_resources(dir, extensions)
+        if false == self._engine_resources.is_null() {
+            return self.get_engine_resources().collect_resources(dir, extensions);
+        }
+        let mut resource_directory = PathBuf::from(PROJECT_RESOURCE_PATH);
+        resource_directory.push(dir);
+        let mut resource_files: Vec<PathBuf> = Vec::new();
+        collect_resource_files(&resource_directory, extensions, &mut resource_files);
+        resource_files
+    }
+
+    // the scene, the game and the script datas are read from the files without the renderer
+    pub fn load_game_resources(&mut self) {
+        self.load_scene_datas();
+        self.load_game_datas();
+        self.load_script_datas();
+    }
+
+    pub fn unload_game_resources(&mut self) {
+        self.unload_script_datas();
+        self.unload_game_datas();
+        self.unload_scene_datas();
     }
 
     // SceneData
-    pub fn load_scene_datas(&mut self, _renderer_context: &RendererContext) {
+    pub fn load_scene_datas(&mut self) {
         log::info!("    load_scene_datas");
         let scene_directory = PathBuf::from(SCENE_FILE_PATH);
         let scene_data_files: Vec<PathBuf> = self.collect_resources(&scene_directory, &[EXT_SCENE]);
@@ -167,7 +216,7 @@ impl ProjectResources {
         }
     }
 
-    pub fn unload_scene_datas(&mut self, _renderer_context: &RendererContext) {
+    pub fn unload_scene_datas(&mut self) {
         self._scene_data_create_infos_map.clear();
     }
 
@@ -192,20 +241,57 @@ impl ProjectResources {
         self._scene_data_create_infos_map.get(resource_name).unwrap()
     }
 
+    // the script sources are compiled by the script manager when the level starts
+    fn load_script_datas(&mut self) {
+        log::info!("    load_script_datas");
+        let script_directory = PathBuf::from(SCRIPT_FILE_PATH);
+        let script_files: Vec<PathBuf> = self.collect_resources(&script_directory, &[EXT_SCRIPT]);
+        for script_file in script_files {
+            let script_name = get_unique_resource_name(&self._script_data_map, &script_directory, &script_file);
+            let mut script_source = String::new();
+            system::load(&script_file).read_to_string(&mut script_source).expect("Failed to read script.");
+            self._script_data_map.insert(script_name.clone(), newRcRefCell(script_source));
+        }
+    }
+
+    fn unload_script_datas(&mut self) {
+        self._script_data_map.clear();
+    }
+
+    pub fn has_script_data(&self, resource_name: &str) -> bool {
+        self._script_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_script_data(&self, resource_name: &str) -> &RcRefCell<String> {
+        self._script_data_map.get(resource_name).unwrap()
+    }
+
     // Game Datas
     fn load_game_datas(&mut self) {
         log::info!("    load_game_datas");
+        self.load_explosion_datas();
         self.load_bullet_datas();
         self.load_weapon_datas();
         self.load_ship_controller_datas();
         self.load_ship_datas();
+        self.load_building_datas();
+        self.load_pickup_datas();
+        self.load_drop_table_datas();
+        self.load_game_mode_datas();
+        self.load_game_options();
     }
 
     fn unload_game_datas(&mut self) {
         self.unload_bullet_datas();
+        self.unload_explosion_datas();
         self.unload_weapon_datas();
         self.unload_ship_datas();
         self.unload_ship_controller_datas();
+        self.unload_building_datas();
+        self.unload_pickup_datas();
+        self.unload_drop_table_datas();
+        self.unload_game_mode_datas();
+        self._game_options = GameOptions::default();
     }
 
     // ship controller data
@@ -333,6 +419,46 @@ impl ProjectResources {
         self._bullet_data_map.get(resource_name).unwrap()
     }
 
+    // explosion data
+    fn load_explosion_datas(&mut self) {
+        let game_data_directory = PathBuf::from(EXPLOSION_DATA_FILE_PATH);
+
+        // create explosion data
+        let mut default_explosion_data_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        default_explosion_data_file_path.push(&game_data_directory);
+        default_explosion_data_file_path.push(&DEFAULT_GAME_DATA_NAME);
+        default_explosion_data_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == default_explosion_data_file_path.is_file() {
+            let default_explosion_data = ExplosionData::default();
+            let mut write_file = File::create(&default_explosion_data_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_explosion_data).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        // load explosion data
+        let game_data_files: Vec<PathBuf> = self.collect_resources(&game_data_directory, &[EXT_GAME_DATA]);
+        for game_data_file in game_data_files {
+            let game_data_name = get_unique_resource_name(&self._explosion_data_map, &game_data_directory, &game_data_file);
+            let loaded_contents = system::load(&game_data_file);
+            let explosion_data: ExplosionData = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+            self._explosion_data_map.insert(game_data_name.clone(), newRcRefCell(explosion_data));
+        }
+    }
+
+    fn unload_explosion_datas(&mut self) {
+        self._explosion_data_map.clear();
+    }
+
+    pub fn has_explosion_data(&self, resource_name: &str) -> bool {
+        self._explosion_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_explosion_data(&self, resource_name: &str) -> &RcRefCell<ExplosionData> {
+        self._explosion_data_map.get(resource_name).unwrap()
+    }
+
     // weapon data
     fn load_weapon_datas(&mut self) {
         let game_data_directory = PathBuf::from(WEAPON_DATA_FILE_PATH);
@@ -377,4 +503,205 @@ impl ProjectResources {
     pub fn get_weapon_data(&self, resource_name: &str) -> &RcRefCell<WeaponData> {
         self._weapon_data_map.get(resource_name).unwrap()
     }
+
+    // building data
+    fn load_building_datas(&mut self) {
+        let game_data_directory = PathBuf::from(BUILDING_DATA_FILE_PATH);
+
+        // create building data
+        let mut default_building_data_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        default_building_data_file_path.push(&game_data_directory);
+        default_building_data_file_path.push(&DEFAULT_GAME_DATA_NAME);
+        default_building_data_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == default_building_data_file_path.is_file() {
+            let default_building_data = BuildingData::default();
+            let mut write_file = File::create(&default_building_data_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_building_data).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        // load building data
+        let game_data_files: Vec<PathBuf> = self.collect_resources(&game_data_directory, &[EXT_GAME_DATA]);
+        for game_data_file in game_data_files {
+            let game_data_name = get_unique_resource_name(&self._building_data_map, &game_data_directory, &game_data_file);
+            let loaded_contents = system::load(&game_data_file);
+            let building_data: BuildingData = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+            self._building_data_map.insert(game_data_name.clone(), newRcRefCell(building_data));
+        }
+    }
+
+    fn unload_building_datas(&mut self) {
+        self._building_data_map.clear();
+    }
+
+    pub fn has_building_data(&self, resource_name: &str) -> bool {
+        self._building_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_building_data(&self, resource_name: &str) -> &RcRefCell<BuildingData> {
+        self._building_data_map.get(resource_name).unwrap()
+    }
+
+    // pickup data
+    fn load_pickup_datas(&mut self) {
+        let game_data_directory = PathBuf::from(PICKUP_DATA_FILE_PATH);
+
+        // create pickup data
+        let mut default_pickup_data_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        default_pickup_data_file_path.push(&game_data_directory);
+        default_pickup_data_file_path.push(&DEFAULT_GAME_DATA_NAME);
+        default_pickup_data_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == default_pickup_data_file_path.is_file() {
+            let default_pickup_data = PickupData::default();
+            let mut write_file = File::create(&default_pickup_data_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_pickup_data).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        // load pickup data
+        let game_data_files: Vec<PathBuf> = self.collect_resources(&game_data_directory, &[EXT_GAME_DATA]);
+        for game_data_file in game_data_files {
+            let game_data_name = get_unique_resource_name(&self._pickup_data_map, &game_data_directory, &game_data_file);
+            let loaded_contents = system::load(&game_data_file);
+            let pickup_data: PickupData = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+            self._pickup_data_map.insert(game_data_name.clone(), newRcRefCell(pickup_data));
+        }
+    }
+
+    fn unload_pickup_datas(&mut self) {
+        self._pickup_data_map.clear();
+    }
+
+    pub fn has_pickup_data(&self, resource_name: &str) -> bool {
+        self._pickup_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_pickup_data(&self, resource_name: &str) -> &RcRefCell<PickupData> {
+        self._pickup_data_map.get(resource_name).unwrap()
+    }
+
+    // drop table data
+    fn load_drop_table_datas(&mut self) {
+        let game_data_directory = PathBuf::from(DROP_TABLE_DATA_FILE_PATH);
+
+        // create drop table data
+        let mut default_drop_table_data_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        default_drop_table_data_file_path.push(&game_data_directory);
+        default_drop_table_data_file_path.push(&DEFAULT_GAME_DATA_NAME);
+        default_drop_table_data_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == default_drop_table_data_file_path.is_file() {
+            let default_drop_table_data = DropTableData::default();
+            let mut write_file = File::create(&default_drop_table_data_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_drop_table_data).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        // load drop table data
+        let game_data_files: Vec<PathBuf> = self.collect_resources(&game_data_directory, &[EXT_GAME_DATA]);
+        for game_data_file in game_data_files {
+            let game_data_name = get_unique_resource_name(&self._drop_table_data_map, &game_data_directory, &game_data_file);
+            let loaded_contents = system::load(&game_data_file);
+            let drop_table_data: DropTableData = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+            self._drop_table_data_map.insert(game_data_name.clone(), newRcRefCell(drop_table_data));
+        }
+    }
+
+    fn unload_drop_table_datas(&mut self) {
+        self._drop_table_data_map.clear();
+    }
+
+    pub fn has_drop_table_data(&self, resource_name: &str) -> bool {
+        self._drop_table_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_drop_table_data(&self, resource_name: &str) -> &RcRefCell<DropTableData> {
+        self._drop_table_data_map.get(resource_name).unwrap()
+    }
+
+    // game mode data
+    fn load_game_mode_datas(&mut self) {
+        let game_data_directory = PathBuf::from(GAME_MODE_DATA_FILE_PATH);
+
+        // create game mode data
+        let mut default_game_mode_data_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        default_game_mode_data_file_path.push(&game_data_directory);
+        default_game_mode_data_file_path.push(&DEFAULT_GAME_DATA_NAME);
+        default_game_mode_data_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == default_game_mode_data_file_path.is_file() {
+            let default_game_mode_data = GameModeData::default();
+            let mut write_file = File::create(&default_game_mode_data_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_game_mode_data).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        // load game mode data
+        let game_data_files: Vec<PathBuf> = self.collect_resources(&game_data_directory, &[EXT_GAME_DATA]);
+        for game_data_file in game_data_files {
+            let game_data_name = get_unique_resource_name(&self._game_mode_data_map, &game_data_directory, &game_data_file);
+            let loaded_contents = system::load(&game_data_file);
+            let game_mode_data: GameModeData = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+            self._game_mode_data_map.insert(game_data_name.clone(), newRcRefCell(game_mode_data));
+        }
+    }
+
+    fn unload_game_mode_datas(&mut self) {
+        self._game_mode_data_map.clear();
+    }
+
+    pub fn has_game_mode_data(&self, resource_name: &str) -> bool {
+        self._game_mode_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_game_mode_data(&self, resource_name: &str) -> &RcRefCell<GameModeData> {
+        self._game_mode_data_map.get(resource_name).unwrap()
+    }
+
+    // game options
+    fn load_game_options(&mut self) {
+        let mut game_options_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        game_options_file_path.push(GAME_OPTIONS_FILE_PATH);
+        game_options_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == game_options_file_path.is_file() {
+            let default_game_options = GameOptions::default();
+            let mut write_file = File::create(&game_options_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_game_options).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        if game_options_file_path.is_file() {
+            let loaded_contents = system::load(&game_options_file_path);
+            self._game_options = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+        }
+    }
+
+    pub fn get_game_options(&self) -> &GameOptions {
+        &self._game_options
+    }
+}
+
+fn collect_resource_files(dir: &Path, extensions: &[&str], resource_files: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_resource_files(&path, extensions, resource_files);
+        } else if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+            if extensions.contains(&extension) {
+                resource_files.push(path);
+            }
+        }
+    }
 }